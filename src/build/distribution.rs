@@ -0,0 +1,399 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use flate2::{Compression, write::GzEncoder};
+use regex::Regex;
+use zip::{ZipWriter, write::SimpleFileOptions};
+
+use crate::{
+    build::{package, resolve::ResolvedDependencies},
+    model::{Configuration, DistributionFormat, Project, ProjectInfo},
+    project::{ImplicitBuildTask, TaskOutput, TaskRunner},
+    util::consts,
+    workspace::paths::resolve_filepath,
+};
+
+pub struct ImplicitDistributionTask {
+    order: Vec<String>,
+}
+
+impl ImplicitDistributionTask {
+    pub fn new() -> Self {
+        ImplicitDistributionTask {
+            order: vec![
+                String::from("resolve"),
+                String::from("collect"),
+                String::from("compile"),
+                String::from("shade"),
+                String::from("package"),
+                String::from("distribute"),
+            ],
+        }
+    }
+}
+
+impl Default for ImplicitDistributionTask {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskRunner for ImplicitDistributionTask {
+    fn invoke(
+        &self,
+        _info: &ProjectInfo,
+        project: &Project,
+        configuration: &Configuration,
+        output: &mut TaskOutput<'_>,
+    ) -> Result<(), String> {
+        if configuration.entry().is_none() {
+            return Err(String::from(
+                "A distribution needs a main class for its launcher scripts.\nFix: set `entry = \"com.example.Main\"` in the current configuration.",
+            ));
+        }
+
+        let dependencies = ImplicitBuildTask::new().build(project, configuration, output)?;
+
+        output.step_started("Packaging", "distribution", 6);
+        match write_distribution(project, configuration, &dependencies) {
+            Ok(archive) => {
+                output.step_completed(
+                    "Packaging",
+                    "distribution",
+                    6,
+                    &format!("Written to {}", archive.display()),
+                );
+                Ok(())
+            }
+            Err(error) => {
+                output.step_failed("Packaging", "distribution", 6, &error);
+                Err(error)
+            }
+        }
+    }
+
+    fn phase_order(&self) -> &[String] {
+        self.order.as_ref()
+    }
+}
+
+fn write_distribution(
+    project: &Project,
+    configuration: &Configuration,
+    dependencies: &ResolvedDependencies,
+) -> Result<PathBuf, String> {
+    let mut regexes: HashMap<&str, Regex> = HashMap::new();
+    regexes.insert("envvars", Regex::new(r#"\{(.+?)}"#).unwrap());
+
+    let base_name = format!("{}-{}", project.info().name(), project.info().version());
+    let staging = PathBuf::from(consts::DISTRIBUTION_WORK_PATH).join(&base_name);
+    let launcher = configuration
+        .distribution()
+        .and_then(|distribution| distribution.launcher())
+        .map(String::as_str)
+        .unwrap_or(project.info().name());
+
+    stage_distribution(
+        &staging,
+        configuration,
+        project.info().name(),
+        launcher,
+        dependencies.paths(),
+        !dependencies.nested_jars().is_empty(),
+    )?;
+
+    let output_dir = PathBuf::from(resolve_filepath(
+        configuration.distribution_output_dir(),
+        configuration.environment(),
+        &regexes,
+    )?);
+    fs::create_dir_all(&output_dir).map_err(|e| {
+        format!(
+            "Could not create distribution folder \"{}\": {e}",
+            output_dir.display()
+        )
+    })?;
+
+    let format = configuration.distribution_format();
+    let archive = output_dir.join(format!("{base_name}.{}", format.extension()));
+    let entries = distribution_entries(&staging, &base_name)?;
+    match format {
+        DistributionFormat::Zip => write_zip(&archive, &entries)?,
+        DistributionFormat::TarGz => write_tar_gz(&archive, &entries)?,
+    }
+
+    Ok(archive)
+}
+
+/// Lays out `lib/` and `bin/` under `staging`, with the application jar pointing at its siblings.
+fn stage_distribution(
+    staging: &Path,
+    configuration: &Configuration,
+    project_name: &str,
+    launcher: &str,
+    dep_paths: &[PathBuf],
+    nested: bool,
+) -> Result<(), String> {
+    if staging.exists() {
+        fs::remove_dir_all(staging).map_err(|e| {
+            format!(
+                "Failed to clear distribution work folder \"{}\": {e}",
+                staging.display()
+            )
+        })?;
+    }
+
+    let lib_dir = staging.join("lib");
+    let bin_dir = staging.join("bin");
+    let libraries = package::copy_runtime_libraries(&lib_dir, dep_paths)?;
+
+    let app_jar = format!("{project_name}.jar");
+    package::copy_jar_with_manifest(
+        Path::new(consts::TARGET_JAR_PATH),
+        &lib_dir.join(&app_jar),
+        &package::jar_manifest(configuration, libraries, nested),
+    )?;
+
+    fs::create_dir_all(&bin_dir)
+        .map_err(|e| format!("Could not create folder \"{}\": {e}", bin_dir.display()))?;
+    let launcher_path = bin_dir.join(launcher);
+    fs::write(&launcher_path, launcher_script(&app_jar)).map_err(|e| {
+        format!(
+            "Failed to write launcher script \"{}\": {e}",
+            launcher_path.display()
+        )
+    })
+}
+
+fn launcher_script(app_jar: &str) -> String {
+    format!(
+        r#"#!/bin/sh
+APP_HOME="$(cd "$(dirname "$0")/.." && pwd)"
+
+if [ -n "$JAVA_HOME" ]; then
+    JAVACMD="$JAVA_HOME/bin/java"
+else
+    JAVACMD="java"
+fi
+
+exec "$JAVACMD" $JAVA_OPTS -jar "$APP_HOME/lib/{app_jar}" "$@"
+"#
+    )
+}
+
+/// Archive entries as (path on disk, path in archive, unix mode).
+fn distribution_entries(
+    staging: &Path,
+    base_name: &str,
+) -> Result<Vec<(PathBuf, String, u32)>, String> {
+    let mut entries = Vec::new();
+
+    for (folder, mode) in [("bin", 0o755), ("lib", 0o644)] {
+        let dir = staging.join(folder);
+        let read = dir
+            .read_dir()
+            .map_err(|e| format!("Could not read directory \"{}\": {e}", dir.display()))?;
+        let mut files: Vec<PathBuf> = read
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<_, _>>()
+            .map_err(|e| {
+                format!(
+                    "Could not read an entry in directory \"{}\": {e}",
+                    dir.display()
+                )
+            })?;
+        files.sort();
+
+        for file in files {
+            let name = format!(
+                "{base_name}/{folder}/{}",
+                file.file_name().unwrap().to_string_lossy()
+            );
+            entries.push((file, name, mode));
+        }
+    }
+
+    Ok(entries)
+}
+
+fn write_zip(archive: &Path, entries: &[(PathBuf, String, u32)]) -> Result<(), String> {
+    let write_error = |e: &dyn std::fmt::Display| {
+        format!(
+            "Failed to write distribution archive {}: {e}",
+            archive.display()
+        )
+    };
+    let file = File::create(archive).map_err(|e| write_error(&e))?;
+    let mut writer = ZipWriter::new(file);
+
+    for (path, name, mode) in entries {
+        let bytes =
+            fs::read(path).map_err(|e| format!("Failed to read \"{}\": {e}", path.display()))?;
+        writer
+            .start_file(
+                name.as_str(),
+                SimpleFileOptions::default().unix_permissions(*mode),
+            )
+            .map_err(|e| write_error(&e))?;
+        writer.write_all(&bytes).map_err(|e| write_error(&e))?;
+    }
+
+    writer.finish().map_err(|e| write_error(&e))?;
+    Ok(())
+}
+
+fn write_tar_gz(archive: &Path, entries: &[(PathBuf, String, u32)]) -> Result<(), String> {
+    let write_error = |e: &dyn std::fmt::Display| {
+        format!(
+            "Failed to write distribution archive {}: {e}",
+            archive.display()
+        )
+    };
+    let file = File::create(archive).map_err(|e| write_error(&e))?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+
+    for (path, name, mode) in entries {
+        let bytes =
+            fs::read(path).map_err(|e| format!("Failed to read \"{}\": {e}", path.display()))?;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(bytes.len() as u64);
+        header.set_mode(*mode);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, name, bytes.as_slice())
+            .map_err(|e| write_error(&e))?;
+    }
+
+    builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .map_err(|e| write_error(&e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{TempDir, with_current_dir};
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+    use toml::Table;
+    use zip::ZipArchive;
+
+    fn configuration() -> Configuration {
+        Configuration::from(
+            String::from("main"),
+            &r#"entry = "com.example.Main""#.parse::<Table>().unwrap(),
+            String::from("Demo"),
+            String::from("1.0.0"),
+        )
+        .unwrap()
+    }
+
+    fn write_target_jar() {
+        fs::create_dir_all(consts::WORK_DIR).unwrap();
+        let mut writer = ZipWriter::new(File::create(consts::TARGET_JAR_PATH).unwrap());
+        writer
+            .start_file("com/example/Main.class", SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"class").unwrap();
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn launcher_script_honors_java_home_and_java_opts() {
+        let script = launcher_script("Demo.jar");
+
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains("JAVACMD=\"$JAVA_HOME/bin/java\""));
+        assert!(script.contains("$JAVA_OPTS -jar \"$APP_HOME/lib/Demo.jar\" \"$@\""));
+    }
+
+    #[test]
+    fn zip_distribution_contains_relative_class_path_and_executable_launcher() {
+        let temp = TempDir::new("distribution-zip");
+        let dependency = temp.path().join("gson-2.10.jar");
+        fs::write(&dependency, "gson").unwrap();
+
+        with_current_dir(temp.path(), || {
+            write_target_jar();
+            let staging = PathBuf::from(consts::DISTRIBUTION_WORK_PATH).join("Demo-1.0.0");
+            stage_distribution(
+                &staging,
+                &configuration(),
+                "Demo",
+                "demo",
+                std::slice::from_ref(&dependency),
+                false,
+            )
+            .unwrap();
+
+            let entries = distribution_entries(&staging, "Demo-1.0.0").unwrap();
+            write_zip(Path::new("Demo-1.0.0.zip"), &entries).unwrap();
+        });
+
+        let mut archive =
+            ZipArchive::new(File::open(temp.path().join("Demo-1.0.0.zip")).unwrap()).unwrap();
+        assert_eq!(
+            archive.by_name("Demo-1.0.0/bin/demo").unwrap().unix_mode(),
+            Some(0o100755)
+        );
+        assert!(archive.by_name("Demo-1.0.0/lib/gson-2.10.jar").is_ok());
+
+        let mut app_jar = Vec::new();
+        archive
+            .by_name("Demo-1.0.0/lib/Demo.jar")
+            .unwrap()
+            .read_to_end(&mut app_jar)
+            .unwrap();
+        let mut app_jar = ZipArchive::new(std::io::Cursor::new(app_jar)).unwrap();
+        let mut manifest = String::new();
+        app_jar
+            .by_name("META-INF/MANIFEST.MF")
+            .unwrap()
+            .read_to_string(&mut manifest)
+            .unwrap();
+        assert!(manifest.contains("Class-Path: gson-2.10.jar"));
+        assert!(manifest.contains("Main-Class: com.example.Main"));
+    }
+
+    #[test]
+    fn tar_gz_distribution_preserves_launcher_mode() {
+        let temp = TempDir::new("distribution-tar");
+
+        with_current_dir(temp.path(), || {
+            write_target_jar();
+            let staging = PathBuf::from(consts::DISTRIBUTION_WORK_PATH).join("Demo-1.0.0");
+            stage_distribution(&staging, &configuration(), "Demo", "Demo", &[], false).unwrap();
+
+            let entries = distribution_entries(&staging, "Demo-1.0.0").unwrap();
+            write_tar_gz(Path::new("Demo-1.0.0.tar.gz"), &entries).unwrap();
+        });
+
+        let file = File::open(temp.path().join("Demo-1.0.0.tar.gz")).unwrap();
+        let mut archive = tar::Archive::new(GzDecoder::new(file));
+        let entries: Vec<(String, u32)> = archive
+            .entries()
+            .unwrap()
+            .map(|entry| {
+                let entry = entry.unwrap();
+                (
+                    entry.path().unwrap().to_string_lossy().to_string(),
+                    entry.header().mode().unwrap(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            entries,
+            vec![
+                (String::from("Demo-1.0.0/bin/Demo"), 0o755),
+                (String::from("Demo-1.0.0/lib/Demo.jar"), 0o644),
+            ]
+        );
+    }
+}
@@ -0,0 +1,338 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use regex::Regex;
+
+use crate::{
+    build::package,
+    model::{Configuration, Project, ProjectInfo},
+    output,
+    project::{ImplicitBuildTask, TaskOutput, TaskRunner},
+    util::{consts, exit_code},
+    workspace::paths::resolve_filepath,
+};
+
+pub struct ImplicitImageTask {
+    order: Vec<String>,
+}
+
+impl ImplicitImageTask {
+    pub fn new() -> Self {
+        ImplicitImageTask {
+            order: vec![
+                String::from("resolve"),
+                String::from("collect"),
+                String::from("compile"),
+                String::from("shade"),
+                String::from("package"),
+                String::from("modules"),
+                String::from("link"),
+                String::from("assemble"),
+            ],
+        }
+    }
+}
+
+impl Default for ImplicitImageTask {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskRunner for ImplicitImageTask {
+    fn invoke(
+        &self,
+        _info: &ProjectInfo,
+        project: &Project,
+        configuration: &Configuration,
+        output: &mut TaskOutput<'_>,
+    ) -> Result<(), String> {
+        let Some(entry) = configuration.entry() else {
+            return Err(String::from(
+                "A runtime image needs a main class to launch.\nFix: set `entry = \"com.example.Main\"` in the current configuration.",
+            ));
+        };
+
+        let dependencies = ImplicitBuildTask::new().build(project, configuration, output)?;
+
+        let mut regexes: HashMap<&str, Regex> = HashMap::new();
+        regexes.insert("envvars", Regex::new(r#"\{(.+?)}"#).unwrap());
+        let output_dir = PathBuf::from(resolve_filepath(
+            configuration.image_output_dir(),
+            configuration.environment(),
+            &regexes,
+        )?);
+        let project_name = project.info().name();
+
+        output.step_started("Analyzing", "modules", 6);
+        let analyzed_jars = [dependencies.paths(), dependencies.nested_jars()].concat();
+        let modules = match required_modules(configuration, &analyzed_jars, output) {
+            Ok(modules) => {
+                output.step_completed("Analyzing", "modules", 6, &modules.join(", "));
+                modules
+            }
+            Err(error) => {
+                output.step_failed("Analyzing", "modules", 6, &error);
+                return Err(error);
+            }
+        };
+
+        output.step_started("Linking", "runtime", 7);
+        if let Err(error) = link_runtime(&modules, &output_dir, output) {
+            output.step_failed("Linking", "runtime", 7, &error);
+            return Err(error);
+        }
+        output.step_completed("Linking", "runtime", 7, "Done");
+
+        output.step_started("Assembling", "image", 8);
+        let launcher = configuration
+            .image()
+            .and_then(|image| image.launcher())
+            .map(String::as_str)
+            .unwrap_or(project_name);
+        if let Err(error) =
+            assemble_image(&output_dir, project_name, launcher, entry, &analyzed_jars)
+        {
+            output.step_failed("Assembling", "image", 8, &error);
+            return Err(error);
+        }
+        output.step_completed(
+            "Assembling",
+            "image",
+            8,
+            &format!("Written to {}", output_dir.display()),
+        );
+
+        Ok(())
+    }
+
+    fn phase_order(&self) -> &[String] {
+        self.order.as_ref()
+    }
+}
+
+fn required_modules(
+    configuration: &Configuration,
+    dep_paths: &[PathBuf],
+    output: &mut TaskOutput<'_>,
+) -> Result<Vec<String>, String> {
+    let mut jdeps_command = Command::new("jdeps");
+    jdeps_command.args(["--ignore-missing-deps", "--print-module-deps"]);
+    jdeps_command.args(["--multi-release", &multi_release(configuration)]);
+
+    if !dep_paths.is_empty() {
+        let classpath = dep_paths
+            .iter()
+            .map(|path| path.to_string_lossy().to_string())
+            .collect::<Vec<_>>()
+            .join(&consts::java_seperator().to_string());
+        jdeps_command.args(["--class-path", &classpath]);
+    }
+
+    jdeps_command.arg(consts::TARGET_JAR_PATH);
+    jdeps_command.args(dep_paths);
+
+    let out = jdeps_command.output().map_err(|e| {
+        format!(
+            "Failed to run jdeps: {e}\nFix: install a JDK 11 or newer and ensure `jdeps` is on your PATH."
+        )
+    })?;
+    output::log_process_output(output.renderer(), &[], &out.stderr);
+
+    if !out.status.success() {
+        exit_code::record_external_process_exit_code(out.status);
+        return Err(format!("jdeps failed with status {}", out.status));
+    }
+
+    let mut modules = parse_module_deps(&String::from_utf8_lossy(&out.stdout));
+    if let Some(extra) = configuration.image().and_then(|image| image.modules()) {
+        for module in extra {
+            if !modules.contains(module) {
+                modules.push(module.clone());
+            }
+        }
+    }
+
+    Ok(modules)
+}
+
+fn multi_release(configuration: &Configuration) -> String {
+    match configuration.java_version() {
+        version if version >= 9 => version.to_string(),
+        _ => String::from("base"),
+    }
+}
+
+fn parse_module_deps(stdout: &str) -> Vec<String> {
+    let mut modules: Vec<String> = stdout
+        .lines()
+        .flat_map(|line| line.split(','))
+        .map(str::trim)
+        .filter(|module| !module.is_empty())
+        .map(String::from)
+        .collect();
+
+    if modules.is_empty() {
+        modules.push(String::from("java.base"));
+    }
+
+    modules
+}
+
+fn link_runtime(
+    modules: &[String],
+    output_dir: &Path,
+    output: &mut TaskOutput<'_>,
+) -> Result<(), String> {
+    let runtime_dir = output_dir.join("runtime");
+    if runtime_dir.exists() {
+        fs::remove_dir_all(&runtime_dir).map_err(|e| {
+            format!(
+                "Failed to remove previous runtime \"{}\": {e}",
+                runtime_dir.display()
+            )
+        })?;
+    }
+    fs::create_dir_all(output_dir).map_err(|e| {
+        format!(
+            "Could not create image folder \"{}\": {e}",
+            output_dir.display()
+        )
+    })?;
+
+    let mut jlink_command = Command::new("jlink");
+    jlink_command.args(["--add-modules", &modules.join(",")]);
+    jlink_command.args(["--strip-debug", "--no-header-files", "--no-man-pages"]);
+    jlink_command.arg("--output").arg(&runtime_dir);
+
+    match jlink_command.output() {
+        Ok(out) => {
+            output::log_process_output(output.renderer(), &out.stdout, &out.stderr);
+
+            if !out.status.success() {
+                exit_code::record_external_process_exit_code(out.status);
+                return Err(format!("jlink failed with status {}", out.status));
+            }
+        }
+        Err(e) => {
+            return Err(format!(
+                "Failed to run jlink: {e}\nFix: install a JDK 9 or newer and ensure `jlink` is on your PATH."
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn assemble_image(
+    output_dir: &Path,
+    project_name: &str,
+    launcher: &str,
+    entry: &str,
+    dep_paths: &[PathBuf],
+) -> Result<(), String> {
+    let lib_dir = output_dir.join("lib");
+    let bin_dir = output_dir.join("bin");
+    for dir in [&lib_dir, &bin_dir] {
+        if dir.exists() {
+            fs::remove_dir_all(dir)
+                .map_err(|e| format!("Failed to clear \"{}\": {e}", dir.display()))?;
+        }
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Could not create folder \"{}\": {e}", dir.display()))?;
+    }
+
+    let app_jar = lib_dir.join(format!("{project_name}.jar"));
+    fs::copy(consts::TARGET_JAR_PATH, &app_jar).map_err(|e| {
+        format!(
+            "Failed to copy application jar to \"{}\": {e}",
+            app_jar.display()
+        )
+    })?;
+
+    package::copy_runtime_libraries(&lib_dir, dep_paths)?;
+
+    let launcher_path = bin_dir.join(launcher);
+    fs::write(&launcher_path, launcher_script(entry)).map_err(|e| {
+        format!(
+            "Failed to write launcher script \"{}\": {e}",
+            launcher_path.display()
+        )
+    })?;
+    make_executable(&launcher_path)
+}
+
+fn launcher_script(entry: &str) -> String {
+    format!(
+        r#"#!/bin/sh
+IMAGE_HOME="$(cd "$(dirname "$0")/.." && pwd)"
+exec "$IMAGE_HOME/runtime/bin/java" $JAVA_OPTS -cp "$IMAGE_HOME/lib/*" {entry} "$@"
+"#
+    )
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<(), String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+        .map_err(|e| format!("Failed to mark \"{}\" as executable: {e}", path.display()))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<(), String> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{TempDir, with_current_dir};
+
+    #[test]
+    fn parse_module_deps_splits_jdeps_output_and_defaults_to_base() {
+        assert_eq!(
+            parse_module_deps("java.base,java.logging,java.sql\n"),
+            vec!["java.base", "java.logging", "java.sql"]
+        );
+        assert_eq!(parse_module_deps("\n"), vec!["java.base"]);
+    }
+
+    #[test]
+    fn assemble_image_copies_jars_and_writes_launcher() {
+        let temp = TempDir::new("image-assemble");
+        let dependency = temp.path().join("gson-2.10.jar");
+        fs::write(&dependency, "gson").unwrap();
+
+        with_current_dir(temp.path(), || {
+            fs::create_dir_all(consts::WORK_DIR).unwrap();
+            fs::write(consts::TARGET_JAR_PATH, "app").unwrap();
+
+            assemble_image(
+                Path::new("image"),
+                "Demo",
+                "demo",
+                "com.example.Main",
+                std::slice::from_ref(&dependency),
+            )
+            .unwrap();
+        });
+
+        let image = temp.path().join("image");
+        assert_eq!(
+            fs::read_to_string(image.join("lib/Demo.jar")).unwrap(),
+            "app"
+        );
+        assert_eq!(
+            fs::read_to_string(image.join("lib/gson-2.10.jar")).unwrap(),
+            "gson"
+        );
+        let launcher = fs::read_to_string(image.join("bin/demo")).unwrap();
+        assert!(launcher.contains("\"$IMAGE_HOME/runtime/bin/java\" $JAVA_OPTS"));
+        assert!(launcher.contains("com.example.Main \"$@\""));
+    }
+}
@@ -2,9 +2,9 @@ use std::collections::HashMap;
 
 use regex::Regex;
 
-use crate::build::resolve::resolve_dependencies;
+use crate::build::resolve::{ResolvedDependencies, resolve_dependencies};
 use crate::build::task::{TaskOutput, TaskRunner};
-use crate::build::{compile, package, shade, sources};
+use crate::build::{compile, nest, package, shade, sources};
 use crate::model::{Configuration, Project, ProjectInfo};
 
 #[derive(Clone)]
@@ -40,70 +40,32 @@ impl TaskRunner for ImplicitBuildTask {
         configuration: &Configuration,
         output: &mut TaskOutput<'_>,
     ) -> Result<(), String> {
-        let mut regexes: HashMap<&str, Regex> = HashMap::new();
-        regexes.insert("envvars", Regex::new(r#"\{(.+?)}"#).unwrap());
+        self.build(project, configuration, output).map(|_| ())
+    }
 
-        output.step_started("Resolving", "dependencies", 1);
-        let dependencies = match resolve_dependencies(project, configuration, &regexes) {
-            Ok(dependencies) => {
-                output.step_completed(
-                    "Resolving",
-                    "dependencies",
-                    1,
-                    resolve_message(configuration),
-                );
-                dependencies
-            }
-            Err(error) => {
-                output.step_failed("Resolving", "dependencies", 1, &error);
-                return Err(error);
-            }
-        };
+    fn phase_order(&self) -> &[String] {
+        self.order.as_ref()
+    }
+}
 
-        output.step_started("Collecting", "sources", 2);
-        let copied_files = match sources::collect_sources(configuration) {
-            Ok(copied_files) => {
-                output.step_completed(
-                    "Collecting",
-                    "sources",
-                    2,
-                    &format!(
-                        "{} source {}",
-                        copied_files.len(),
-                        plural(copied_files.len())
-                    ),
-                );
-                copied_files
-            }
-            Err(error) => {
-                output.step_failed("Collecting", "sources", 2, &error);
-                return Err(error);
-            }
-        };
+impl ImplicitBuildTask {
+    /// Runs the build phases and hands back the resolved dependencies for tasks that extend the build.
+    pub(crate) fn build(
+        &self,
+        project: &Project,
+        configuration: &Configuration,
+        output: &mut TaskOutput<'_>,
+    ) -> Result<ResolvedDependencies, String> {
+        let mut regexes: HashMap<&str, Regex> = HashMap::new();
+        regexes.insert("envvars", Regex::new(r#"\{(.+?)}"#).unwrap());
 
-        output.step_started("Compiling", "classes", 3);
-        if let Err(error) = compile::compile_sources(
-            configuration,
-            copied_files.clone(),
-            dependencies.classpath().as_deref(),
-            output.renderer(),
-        ) {
-            output.step_failed("Compiling", "classes", 3, &error);
-            return Err(error);
-        }
-        output.step_completed(
-            "Compiling",
-            "classes",
-            3,
-            &format!(
-                "{} source {}",
-                copied_files.len(),
-                plural(copied_files.len())
-            ),
-        );
+        let dependencies = resolve(project, configuration, &regexes, output)?;
+        compile(configuration, &dependencies, output)?;
 
         output.step_started("Shading", "dependencies", 4);
-        if let Err(error) = shade::shade_jars(dependencies.shaded_jars()) {
+        if let Err(error) = shade::shade_jars(dependencies.shaded_jars()).and_then(|()| {
+            nest::nest_jars(configuration, dependencies.nested_jars(), output.renderer())
+        }) {
             output.step_failed("Shading", "dependencies", 4, &error);
             return Err(error);
         }
@@ -111,7 +73,7 @@ impl TaskRunner for ImplicitBuildTask {
             "Shading",
             "dependencies",
             4,
-            shade_message(dependencies.shaded_jars().len()),
+            shade_message(dependencies.shaded_jars().len() + dependencies.nested_jars().len()),
         );
 
         output.step_started("Packaging", "jar", 5);
@@ -119,6 +81,7 @@ impl TaskRunner for ImplicitBuildTask {
             configuration,
             dependencies.paths(),
             dependencies.shaded_jars(),
+            dependencies.nested_jars(),
             configuration.targets(),
             &regexes,
             output.renderer(),
@@ -131,12 +94,83 @@ impl TaskRunner for ImplicitBuildTask {
         };
         output.step_completed("Packaging", "jar", 5, &format!("Hash #{package_hash}"));
 
-        Ok(())
+        Ok(dependencies)
     }
+}
 
-    fn phase_order(&self) -> &[String] {
-        self.order.as_ref()
+/// Runs the resolving phase, logging any warnings the dependencies raised.
+pub(crate) fn resolve(
+    project: &Project,
+    configuration: &Configuration,
+    regexes: &HashMap<&str, Regex>,
+    output: &mut TaskOutput<'_>,
+) -> Result<ResolvedDependencies, String> {
+    output.step_started("Resolving", "dependencies", 1);
+    match resolve_dependencies(project, configuration, regexes) {
+        Ok(dependencies) => {
+            output.step_completed(
+                "Resolving",
+                "dependencies",
+                1,
+                resolve_message(configuration),
+            );
+            for warning in dependencies.warnings() {
+                output.log(warning);
+            }
+            Ok(dependencies)
+        }
+        Err(error) => {
+            output.step_failed("Resolving", "dependencies", 1, &error);
+            Err(error)
+        }
+    }
+}
+
+/// Runs the collecting and compiling phases against the resolved classpath.
+pub(crate) fn compile(
+    configuration: &Configuration,
+    dependencies: &ResolvedDependencies,
+    output: &mut TaskOutput<'_>,
+) -> Result<(), String> {
+    output.step_started("Collecting", "sources", 2);
+    let copied_files = match sources::collect_sources(configuration) {
+        Ok(copied_files) => {
+            output.step_completed(
+                "Collecting",
+                "sources",
+                2,
+                &format!(
+                    "{} source {}",
+                    copied_files.len(),
+                    plural(copied_files.len())
+                ),
+            );
+            copied_files
+        }
+        Err(error) => {
+            output.step_failed("Collecting", "sources", 2, &error);
+            return Err(error);
+        }
+    };
+
+    let source_count = copied_files.len();
+    output.step_started("Compiling", "classes", 3);
+    if let Err(error) = compile::compile_sources(
+        configuration,
+        copied_files,
+        dependencies.classpath().as_deref(),
+        output.renderer(),
+    ) {
+        output.step_failed("Compiling", "classes", 3, &error);
+        return Err(error);
     }
+    output.step_completed(
+        "Compiling",
+        "classes",
+        3,
+        &format!("{source_count} source {}", plural(source_count)),
+    );
+    Ok(())
 }
 
 fn resolve_message(configuration: &Configuration) -> &'static str {
@@ -157,7 +191,7 @@ fn resolve_message(configuration: &Configuration) -> &'static str {
 
 fn shade_message(shaded_jars: usize) -> &'static str {
     match shaded_jars {
-        0 => "No shaded jars",
+        0 => "No shaded or nested jars",
         _ => "Done",
     }
 }
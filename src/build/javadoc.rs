@@ -74,6 +74,9 @@ impl TaskRunner for ImplicitJavadocTask {
         let dependencies = match resolve_dependencies(project, configuration, &regexes) {
             Ok(dependencies) => {
                 output.step_completed("Resolving", "dependencies", 2, "Done");
+                for warning in dependencies.warnings() {
+                    output.log(warning);
+                }
                 dependencies
             }
             Err(error) => {
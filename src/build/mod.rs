@@ -1,11 +1,15 @@
 pub mod compile;
 pub mod defined;
+pub mod distribution;
+pub mod image;
 pub mod implicit;
 pub mod javadoc;
+pub mod nest;
 pub mod package;
 pub mod resolve;
 pub mod run;
 pub mod shade;
+pub mod shell;
 pub mod sources;
 pub mod task;
 
@@ -0,0 +1,81 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::{
+    build::package,
+    java::launcher,
+    model::Configuration,
+    output::{self, OutputRenderer},
+    util::{consts, exit_code},
+};
+
+/// Stages nested dependency jars and the compiled bootstrap launcher for `package_jar` to add to the target jar.
+pub fn nest_jars(
+    configuration: &Configuration,
+    nested_jars: &[PathBuf],
+    renderer: &mut dyn OutputRenderer,
+) -> Result<(), String> {
+    let nested_path = PathBuf::from(consts::NESTED_OUT_PATH);
+    if nested_path.exists() {
+        fs::remove_dir_all(&nested_path)
+            .map_err(|e| format!("Failed to remove nested work folder: {e}"))?;
+    }
+
+    if nested_jars.is_empty() {
+        return Ok(());
+    }
+
+    if configuration.entry().is_none() {
+        return Err(String::from(
+            "Nested dependencies are loaded by a bootstrap launcher, which needs a main class to hand off to.\nFix: set `entry = \"com.example.Main\"` in the current configuration, or use `package = \"shade\"` for library jars.",
+        ));
+    }
+
+    package::copy_runtime_libraries(&nested_path.join(launcher::NESTED_LIBRARY_DIR), nested_jars)?;
+    compile_launcher(configuration, &nested_path, renderer)
+}
+
+fn compile_launcher(
+    configuration: &Configuration,
+    output_dir: &Path,
+    renderer: &mut dyn OutputRenderer,
+) -> Result<(), String> {
+    let source_file =
+        PathBuf::from(consts::LAUNCHER_SOURCE_PATH).join(launcher::NESTED_LAUNCHER_SOURCE_FILE);
+    if let Some(parent) = source_file.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Could not create launcher source folder: {e}"))?;
+    }
+    fs::write(&source_file, launcher::NESTED_LAUNCHER_SOURCE)
+        .map_err(|e| format!("Failed to write launcher source: {e}"))?;
+
+    let mut javac_command = Command::new("javac");
+    javac_command.args(["--release", &launcher_release(configuration)]);
+    javac_command.args(["-Xlint:-options", "-d"]);
+    javac_command.arg(output_dir);
+    javac_command.arg(&source_file);
+
+    match javac_command.output() {
+        Ok(out) => {
+            output::log_process_output(renderer, &out.stdout, &out.stderr);
+
+            if !out.status.success() {
+                exit_code::record_external_process_exit_code(out.status);
+                return Err(format!(
+                    "javac failed to compile the nested jar launcher with status {}",
+                    out.status
+                ));
+            }
+        }
+        Err(e) => return Err(format!("Failed to run javac command: {e}")),
+    }
+
+    Ok(())
+}
+
+fn launcher_release(configuration: &Configuration) -> String {
+    configuration.java_version().max(8).to_string()
+}
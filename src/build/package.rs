@@ -1,41 +1,40 @@
-use std::{collections::HashMap, fs, path::PathBuf, process::Command};
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 use regex::Regex;
 use sha256::digest;
+use zip::{ZipArchive, ZipWriter, write::SimpleFileOptions};
 
+use crate::java::launcher;
 use crate::java::manifest::{Manifest, ManifestEntry};
 use crate::model::Configuration;
 use crate::output::{self, OutputRenderer};
 use crate::util::{consts, exit_code};
 use crate::workspace::paths::resolve_filepath;
 
+const LIBRARY_DIR: &str = "lib";
+/// Lists the jars Wisteria copied into a target's `lib/` folder, one file name per line.
+const COPIED_LIBRARIES_FILE: &str = ".wisteria-libraries";
+
 pub fn package_jar(
     configuration: &Configuration,
     dep_paths: &[PathBuf],
     shaded_jars: &[PathBuf],
+    nested_jars: &[PathBuf],
     targets: Option<&Vec<String>>,
     regexes: &HashMap<&str, Regex>,
     renderer: &mut dyn OutputRenderer,
 ) -> Result<String, String> {
-    let mut manifest: Manifest = Manifest::new();
-    manifest.add_entry(ManifestEntry::CreatedBy {
-        signature: String::from("Wisteria 3"),
-    });
-
-    if let Some(entry) = configuration.entry() {
-        manifest.add_entry(ManifestEntry::MainClass {
-            class: entry.clone(),
-        })
-    }
-
-    if !dep_paths.is_empty() {
-        let dep_strings: Vec<String> = dep_paths
-            .iter()
-            .map(|p| p.to_string_lossy().to_string())
-            .collect();
-
-        manifest.add_entry(ManifestEntry::ClassPath { path: dep_strings })
-    }
+    let dep_strings: Vec<String> = dep_paths
+        .iter()
+        .map(|p| p.to_string_lossy().to_string())
+        .collect();
+    let manifest = jar_manifest(configuration, dep_strings, !nested_jars.is_empty());
 
     let manifest_path = PathBuf::from(consts::MANIFEST_DIR);
     if manifest_path.exists() {
@@ -70,29 +69,11 @@ pub fn package_jar(
     }
 
     if !shaded_jars.is_empty() {
-        let mut jar_update_command: Command = Command::new("jar");
-        jar_update_command.args([
-            "-uf",
-            consts::TARGET_JAR_PATH,
-            "-C",
-            consts::SHADED_OUT_PATH,
-            ".",
-        ]);
-
-        match jar_update_command.output() {
-            Ok(output) => {
-                output::log_process_output(renderer, &output.stdout, &output.stderr);
-
-                if !output.status.success() {
-                    exit_code::record_external_process_exit_code(output.status);
-                    return Err(format!(
-                        "jar shade update failed with status {}",
-                        output.status
-                    ));
-                }
-            }
-            Err(e) => return Err(format!("Failed to update package with shaded jars: {e}")),
-        }
+        update_jar(consts::SHADED_OUT_PATH, "shade", "shaded", renderer)?;
+    }
+
+    if !nested_jars.is_empty() {
+        update_jar(consts::NESTED_OUT_PATH, "nest", "nested", renderer)?;
     }
 
     let bytes: Vec<u8> = fs::read(consts::TARGET_JAR_PATH)
@@ -116,11 +97,384 @@ pub fn package_jar(
                 })?;
             }
 
-            fs::write(&target, &bytes)
-                .map_err(|e| format!("Failed to write to target {target}: {e}"))?;
+            if configuration.copy_libraries() {
+                let lib_dir = target_path
+                    .parent()
+                    .unwrap_or(Path::new(""))
+                    .join(LIBRARY_DIR);
+                let libraries = replace_runtime_libraries(&lib_dir, dep_paths)?
+                    .iter()
+                    .map(|name| format!("{LIBRARY_DIR}/{name}"))
+                    .collect();
+                copy_jar_with_manifest(
+                    Path::new(consts::TARGET_JAR_PATH),
+                    &target_path,
+                    &jar_manifest(configuration, libraries, !nested_jars.is_empty()),
+                )?;
+            } else {
+                fs::write(&target, &bytes)
+                    .map_err(|e| format!("Failed to write to target {target}: {e}"))?;
+            }
             renderer.log(&format!("Successfully written target {target}"));
         }
     }
 
     Ok(hash)
 }
+
+fn update_jar(
+    directory: &str,
+    label: &str,
+    jars_label: &str,
+    renderer: &mut dyn OutputRenderer,
+) -> Result<(), String> {
+    let mut jar_update_command: Command = Command::new("jar");
+    jar_update_command.args(["-uf", consts::TARGET_JAR_PATH, "-C", directory, "."]);
+
+    match jar_update_command.output() {
+        Ok(output) => {
+            output::log_process_output(renderer, &output.stdout, &output.stderr);
+
+            if !output.status.success() {
+                exit_code::record_external_process_exit_code(output.status);
+                return Err(format!(
+                    "jar {label} update failed with status {}",
+                    output.status
+                ));
+            }
+        }
+        Err(e) => {
+            return Err(format!(
+                "Failed to update package with {jars_label} jars: {e}"
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// With nested jars, `Main-Class` points at the bootstrap launcher and the real entry moves to its own attribute.
+pub(crate) fn jar_manifest(
+    configuration: &Configuration,
+    class_path: Vec<String>,
+    nested: bool,
+) -> Manifest {
+    let mut manifest: Manifest = Manifest::new();
+    manifest.add_entry(ManifestEntry::CreatedBy {
+        signature: String::from("Wisteria 3"),
+    });
+
+    if let Some(entry) = configuration.entry() {
+        if nested {
+            manifest.add_entry(ManifestEntry::MainClass {
+                class: String::from(launcher::NESTED_LAUNCHER_CLASS),
+            });
+            manifest.add_entry(ManifestEntry::NestedMainClass {
+                class: entry.clone(),
+            });
+        } else {
+            manifest.add_entry(ManifestEntry::MainClass {
+                class: entry.clone(),
+            })
+        }
+    }
+
+    if !class_path.is_empty() {
+        manifest.add_entry(ManifestEntry::ClassPath { path: class_path })
+    }
+
+    manifest
+}
+
+/// Copies the packaged jar to `destination`, replacing its manifest with the given one.
+pub(crate) fn copy_jar_with_manifest(
+    source: &Path,
+    destination: &Path,
+    manifest: &Manifest,
+) -> Result<(), String> {
+    let source_file =
+        File::open(source).map_err(|e| format!("Failed to open jar {}: {e}", source.display()))?;
+    let mut archive = ZipArchive::new(source_file)
+        .map_err(|e| format!("Failed to open jar {}: {e}", source.display()))?;
+    let destination_file = File::create(destination)
+        .map_err(|e| format!("Failed to create jar {}: {e}", destination.display()))?;
+    let mut writer = ZipWriter::new(destination_file);
+    let write_error =
+        |e: &dyn std::fmt::Display| format!("Failed to write jar {}: {e}", destination.display());
+
+    writer
+        .add_directory("META-INF/", SimpleFileOptions::default())
+        .map_err(|e| write_error(&e))?;
+    writer
+        .start_file("META-INF/MANIFEST.MF", SimpleFileOptions::default())
+        .map_err(|e| write_error(&e))?;
+    writer
+        .write_all(manifest.to_file().as_bytes())
+        .map_err(|e| write_error(&e))?;
+
+    for index in 0..archive.len() {
+        let file = archive
+            .by_index_raw(index)
+            .map_err(|e| format!("Failed to read jar {}: {e}", source.display()))?;
+        if matches!(file.name(), "META-INF/" | "META-INF/MANIFEST.MF") {
+            continue;
+        }
+
+        writer.raw_copy_file(file).map_err(|e| write_error(&e))?;
+    }
+
+    writer.finish().map_err(|e| write_error(&e))?;
+    Ok(())
+}
+
+/// Copies runtime dependency jars into `lib_dir`, returning their file names in classpath order.
+pub(crate) fn copy_runtime_libraries(
+    lib_dir: &Path,
+    dep_paths: &[PathBuf],
+) -> Result<Vec<String>, String> {
+    let file_names = library_file_names(dep_paths)?;
+    fs::create_dir_all(lib_dir)
+        .map_err(|e| format!("Could not create folder \"{}\": {e}", lib_dir.display()))?;
+
+    for (dep, file_name) in dep_paths
+        .iter()
+        .filter(|path| path.is_file())
+        .zip(&file_names)
+    {
+        fs::copy(dep, lib_dir.join(file_name)).map_err(|e| {
+            format!(
+                "Failed to copy dependency \"{}\" to \"{}\": {e}",
+                dep.display(),
+                lib_dir.display()
+            )
+        })?;
+    }
+
+    Ok(file_names)
+}
+
+/// Replaces the jars a previous build copied into `lib_dir`, so jars of removed dependencies do
+/// not linger on the classpath of the copied target. Only jars recorded in the
+/// `.wisteria-libraries` file are removed; any other file in the folder is left alone.
+fn replace_runtime_libraries(lib_dir: &Path, dep_paths: &[PathBuf]) -> Result<Vec<String>, String> {
+    if let Ok(lib_dir) = lib_dir.canonicalize()
+        && let Some(dep) = dep_paths.iter().find(|dep| {
+            dep.canonicalize()
+                .is_ok_and(|dep| dep.starts_with(&lib_dir))
+        })
+    {
+        return Err(format!(
+            "Dependency \"{}\" lives in \"{}\", which receives the copied runtime libraries.\nFix: move the target to another folder, or move the dependency out of that folder.",
+            dep.display(),
+            lib_dir.display()
+        ));
+    }
+
+    let record = lib_dir.join(COPIED_LIBRARIES_FILE);
+    let previous: Vec<String> = if record.is_file() {
+        fs::read_to_string(&record)
+            .map_err(|e| format!("Could not read \"{}\": {e}", record.display()))?
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect()
+    } else if lib_dir
+        .read_dir()
+        .is_ok_and(|mut entries| entries.next().is_some())
+    {
+        return Err(format!(
+            "Library folder \"{}\" already exists and was not created by Wisteria.\nFix: move or rename that folder, or move the target to another folder.",
+            lib_dir.display()
+        ));
+    } else {
+        Vec::new()
+    };
+
+    for file_name in library_file_names(dep_paths)? {
+        if !previous.contains(&file_name) && lib_dir.join(&file_name).exists() {
+            return Err(format!(
+                "\"{}\" was not copied by Wisteria and would be overwritten by a runtime dependency.\nFix: move that file out of \"{}\".",
+                lib_dir.join(&file_name).display(),
+                lib_dir.display()
+            ));
+        }
+    }
+
+    for file_name in &previous {
+        let path = lib_dir.join(Path::new(file_name).file_name().unwrap_or_default());
+        if path.is_file() {
+            fs::remove_file(&path).map_err(|e| {
+                format!("Failed to remove stale library \"{}\": {e}", path.display())
+            })?;
+        }
+    }
+
+    let file_names = copy_runtime_libraries(lib_dir, dep_paths)?;
+    let mut contents = file_names.join("\n");
+    contents.push('\n');
+    fs::write(&record, contents)
+        .map_err(|e| format!("Failed to write \"{}\": {e}", record.display()))?;
+
+    Ok(file_names)
+}
+
+fn library_file_names(dep_paths: &[PathBuf]) -> Result<Vec<String>, String> {
+    let mut file_names: Vec<String> = Vec::new();
+
+    for dep in dep_paths.iter().filter(|path| path.is_file()) {
+        let file_name = dep
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if file_names.contains(&file_name) {
+            return Err(format!(
+                "Two runtime dependencies share the file name \"{file_name}\".\nFix: rename one of the dependency jars, or shade one of them into the application jar."
+            ));
+        }
+
+        file_names.push(file_name);
+    }
+
+    Ok(file_names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use std::io::Read;
+    use toml::Table;
+
+    #[test]
+    fn copy_jar_with_manifest_replaces_manifest_and_keeps_entries() {
+        let temp = TempDir::new("package-manifest");
+        let source = temp.path().join("source.jar");
+        let destination = temp.path().join("destination.jar");
+        let mut writer = ZipWriter::new(File::create(&source).unwrap());
+        writer
+            .start_file("META-INF/MANIFEST.MF", SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"Class-Path: /cache/gson.jar\n").unwrap();
+        writer
+            .start_file("com/example/Main.class", SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(b"class").unwrap();
+        writer.finish().unwrap();
+
+        let configuration = Configuration::from(
+            String::from("main"),
+            &r#"entry = "com.example.Main""#.parse::<Table>().unwrap(),
+            String::from("Demo"),
+            String::from("1.0.0"),
+        )
+        .unwrap();
+        let manifest = jar_manifest(&configuration, vec![String::from("lib/gson.jar")], false);
+
+        copy_jar_with_manifest(&source, &destination, &manifest).unwrap();
+
+        let mut archive = ZipArchive::new(File::open(&destination).unwrap()).unwrap();
+        let mut contents = String::new();
+        archive
+            .by_name("META-INF/MANIFEST.MF")
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert!(contents.contains("Main-Class: com.example.Main"));
+        assert!(contents.contains("Class-Path: lib/gson.jar"));
+        assert!(!contents.contains("/cache/"));
+        assert!(archive.by_name("com/example/Main.class").is_ok());
+    }
+
+    #[test]
+    fn nested_manifest_points_main_class_at_bootstrap_launcher() {
+        let configuration = Configuration::from(
+            String::from("main"),
+            &r#"entry = "com.example.Main""#.parse::<Table>().unwrap(),
+            String::from("Demo"),
+            String::from("1.0.0"),
+        )
+        .unwrap();
+
+        let manifest = jar_manifest(&configuration, Vec::new(), true).to_file();
+
+        assert!(manifest.contains(&format!(
+            "Main-Class: {}\n",
+            launcher::NESTED_LAUNCHER_CLASS
+        )));
+        assert!(manifest.contains("Wisteria-Main-Class: com.example.Main\n"));
+    }
+
+    #[test]
+    fn copy_runtime_libraries_rejects_duplicate_file_names() {
+        let temp = TempDir::new("package-libraries");
+        fs::create_dir_all(temp.path().join("a")).unwrap();
+        fs::create_dir_all(temp.path().join("b")).unwrap();
+        fs::write(temp.path().join("a/lib.jar"), "a").unwrap();
+        fs::write(temp.path().join("b/lib.jar"), "b").unwrap();
+        let lib_dir = temp.path().join("lib");
+
+        let copied = copy_runtime_libraries(&lib_dir, &[temp.path().join("a/lib.jar")]).unwrap();
+        assert_eq!(copied, vec!["lib.jar"]);
+
+        let error = copy_runtime_libraries(
+            &temp.path().join("other"),
+            &[temp.path().join("a/lib.jar"), temp.path().join("b/lib.jar")],
+        )
+        .unwrap_err();
+        assert!(error.contains("share the file name"));
+    }
+
+    #[test]
+    fn replace_runtime_libraries_removes_stale_jars() {
+        let temp = TempDir::new("package-replace-libraries");
+        fs::create_dir_all(temp.path().join("cache")).unwrap();
+        fs::write(temp.path().join("cache/removed.jar"), "removed").unwrap();
+        fs::write(temp.path().join("cache/current.jar"), "current").unwrap();
+        let lib_dir = temp.path().join("target/lib");
+
+        replace_runtime_libraries(&lib_dir, &[temp.path().join("cache/removed.jar")]).unwrap();
+        let copied =
+            replace_runtime_libraries(&lib_dir, &[temp.path().join("cache/current.jar")]).unwrap();
+
+        assert_eq!(copied, vec!["current.jar"]);
+        assert!(lib_dir.join("current.jar").exists());
+        assert!(!lib_dir.join("removed.jar").exists());
+        assert!(
+            replace_runtime_libraries(&lib_dir, &[lib_dir.join("current.jar")])
+                .unwrap_err()
+                .contains("receives the copied runtime libraries")
+        );
+    }
+
+    #[test]
+    fn replace_runtime_libraries_keeps_files_it_did_not_copy() {
+        let temp = TempDir::new("package-replace-user-libraries");
+        fs::create_dir_all(temp.path().join("cache")).unwrap();
+        fs::write(temp.path().join("cache/gson.jar"), "gson").unwrap();
+        let lib_dir = temp.path().join("target/lib");
+        fs::create_dir_all(&lib_dir).unwrap();
+        fs::write(lib_dir.join("user.jar"), "user").unwrap();
+
+        let error =
+            replace_runtime_libraries(&lib_dir, &[temp.path().join("cache/gson.jar")]).unwrap_err();
+        assert!(error.contains("was not created by Wisteria"));
+        assert!(lib_dir.join("user.jar").exists());
+
+        fs::remove_file(lib_dir.join("user.jar")).unwrap();
+        replace_runtime_libraries(&lib_dir, &[temp.path().join("cache/gson.jar")]).unwrap();
+        fs::write(lib_dir.join("notes.txt"), "notes").unwrap();
+        fs::write(lib_dir.join("user.jar"), "user").unwrap();
+
+        replace_runtime_libraries(&lib_dir, &[temp.path().join("cache/gson.jar")]).unwrap();
+
+        assert!(lib_dir.join("gson.jar").exists());
+        assert_eq!(
+            fs::read_to_string(lib_dir.join("notes.txt")).unwrap(),
+            "notes"
+        );
+        assert_eq!(
+            fs::read_to_string(lib_dir.join("user.jar")).unwrap(),
+            "user"
+        );
+    }
+}
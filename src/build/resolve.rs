@@ -3,7 +3,8 @@ use std::{collections::HashMap, path::PathBuf};
 use regex::Regex;
 
 use crate::{
-    dependency::resolver::ResolveContext,
+    dependency::DependencyReference,
+    dependency::resolver::{ResolveContext, ResolveJob, resolve_concurrently},
     model::lockfile::try_read_lockfile,
     model::{Configuration, Project},
     project::UpdateContext,
@@ -13,7 +14,9 @@ use crate::{
 pub struct ResolvedDependencies {
     paths: Vec<PathBuf>,
     shaded_jars: Vec<PathBuf>,
+    nested_jars: Vec<PathBuf>,
     classpath: Option<String>,
+    warnings: Vec<String>,
 }
 
 impl ResolvedDependencies {
@@ -25,9 +28,18 @@ impl ResolvedDependencies {
         &self.shaded_jars
     }
 
+    pub fn nested_jars(&self) -> &[PathBuf] {
+        &self.nested_jars
+    }
+
     pub fn classpath(&self) -> Option<String> {
         self.classpath.clone()
     }
+
+    /// Problems that did not stop resolution, such as downloads that could not be verified.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
 }
 
 pub(crate) fn resolve_dependencies(
@@ -38,13 +50,18 @@ pub(crate) fn resolve_dependencies(
     let mut paths: Vec<PathBuf> = Vec::new();
     let mut compile_paths: Vec<PathBuf> = Vec::new();
     let mut shaded_jars: Vec<PathBuf> = Vec::new();
+    let mut nested_jars: Vec<PathBuf> = Vec::new();
     let mut classpath: Option<String> = None;
+    let mut warnings: Vec<String> = Vec::new();
     let lockfile = try_read_lockfile()?;
 
     let mut failed_downloads: Vec<(String, String)> = Vec::new();
     if let Some(dependencies) = configuration.dependencies() {
+        let mut references: Vec<&DependencyReference> = Vec::new();
+        let mut jobs: Vec<ResolveJob<'_>> = Vec::new();
         for reference in dependencies.iter() {
-            let Some((name, dep)) = project.dependencies().get_key_value(reference.name()) else {
+            let Some((name, dependency)) = project.dependencies().get_key_value(reference.name())
+            else {
                 failed_downloads.push((
                     reference.name().to_string(),
                     String::from("dependency is not declared in [dependencies]"),
@@ -56,35 +73,50 @@ pub(crate) fn resolve_dependencies(
                 continue;
             }
 
-            {
-                let updated = match dep.resolve(
+            references.push(reference);
+            jobs.push(ResolveJob {
+                name,
+                dependency,
+                context: ResolveContext::for_dependency(
+                    UpdateContext::TaskInvoked,
+                    lockfile.as_ref(),
                     name,
-                    configuration.environment(),
-                    regexes,
-                    ResolveContext::for_dependency(
-                        UpdateContext::TaskInvoked,
-                        lockfile.as_ref(),
-                        name,
-                    ),
-                ) {
-                    Ok(p) => p,
-                    Err(e) => {
-                        failed_downloads.push((name.clone(), e));
-                        continue;
-                    }
-                };
-
-                if reference.is_shaded() {
-                    shaded_jars.extend(updated.paths().cloned());
-                }
+                ),
+            });
+        }
 
-                if reference.scope().is_on_compile_classpath() {
-                    compile_paths.extend(updated.paths().cloned());
-                }
+        let results = resolve_concurrently(
+            &jobs,
+            project.info().concurrency(),
+            configuration.environment(),
+            regexes,
+            |_| {},
+        );
 
-                if reference.scope().is_on_runtime_classpath() && !reference.is_shaded() {
-                    paths.extend(updated.paths().cloned());
+        for ((reference, job), result) in references.into_iter().zip(&jobs).zip(results) {
+            let updated = match result {
+                Ok(updated) => updated,
+                Err(e) => {
+                    failed_downloads.push((job.name.to_string(), e));
+                    continue;
                 }
+            };
+            warnings.extend(updated.warnings.iter().cloned());
+
+            if reference.is_shaded() {
+                shaded_jars.extend(updated.paths().cloned());
+            }
+
+            if reference.is_nested() {
+                nested_jars.extend(updated.paths().cloned());
+            }
+
+            if reference.scope().is_on_compile_classpath() {
+                compile_paths.extend(updated.paths().cloned());
+            }
+
+            if reference.scope().is_on_runtime_classpath() && reference.packaging().is_none() {
+                paths.extend(updated.paths().cloned());
             }
         }
 
@@ -107,7 +139,9 @@ pub(crate) fn resolve_dependencies(
     Ok(ResolvedDependencies {
         paths,
         shaded_jars,
+        nested_jars,
         classpath,
+        warnings,
     })
 }
 
@@ -152,8 +186,9 @@ mod tests {
         let provided = temp.path().join("provided.jar");
         let runtime = temp.path().join("runtime.jar");
         let shaded = temp.path().join("shaded.jar");
+        let nested = temp.path().join("nested.jar");
         let test = temp.path().join("test.jar");
-        for jar in [&compile, &provided, &runtime, &shaded, &test] {
+        for jar in [&compile, &provided, &runtime, &shaded, &nested, &test] {
             fs::write(jar, "").unwrap();
         }
 
@@ -172,6 +207,7 @@ mod tests {
                 provided_dep = {{ path = "{}" }}
                 runtime_dep = {{ path = "{}" }}
                 shaded_dep = {{ path = "{}" }}
+                nested_dep = {{ path = "{}" }}
                 test_dep = {{ path = "{}" }}
 
                 [configuration.main]
@@ -180,6 +216,7 @@ mod tests {
                     {{ name = "provided_dep", scope = "provided" }},
                     {{ name = "runtime_dep", scope = "runtime" }},
                     {{ name = "shaded_dep", scope = "compile", package = "shade" }},
+                    {{ name = "nested_dep", scope = "runtime", package = "nest" }},
                     {{ name = "test_dep", scope = "test" }},
                 ]
                 "#,
@@ -187,6 +224,7 @@ mod tests {
                 provided.display(),
                 runtime.display(),
                 shaded.display(),
+                nested.display(),
                 test.display(),
             ),
         )
@@ -199,6 +237,7 @@ mod tests {
         let provided = provided.canonicalize().unwrap();
         let runtime = runtime.canonicalize().unwrap();
         let shaded = shaded.canonicalize().unwrap();
+        let nested = nested.canonicalize().unwrap();
         let test = test.canonicalize().unwrap();
         let classpath = resolved.classpath().unwrap();
 
@@ -213,6 +252,9 @@ mod tests {
         assert!(!contains_path(resolved.paths(), &provided));
         assert!(!contains_path(resolved.paths(), &shaded));
         assert!(!contains_path(resolved.paths(), &test));
+        assert!(!contains_path(resolved.paths(), &nested));
+        assert!(!classpath.contains(&nested.to_string_lossy().to_string()));
         assert_eq!(resolved.shaded_jars(), &[shaded]);
+        assert_eq!(resolved.nested_jars(), &[nested]);
     }
 }
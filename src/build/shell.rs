@@ -0,0 +1,321 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    time::SystemTime,
+};
+
+use regex::Regex;
+
+use crate::{
+    build::{implicit, resolve::ResolvedDependencies},
+    cli::args::StartupFlags,
+    model::{Configuration, Project, ProjectInfo},
+    project::{TaskOutput, TaskRunner},
+    util::{consts, exit_code},
+    workspace::files,
+};
+
+pub struct ImplicitShellTask {
+    order: Vec<String>,
+    flags: StartupFlags,
+}
+
+impl ImplicitShellTask {
+    pub fn new(flags: StartupFlags) -> Self {
+        ImplicitShellTask {
+            order: vec![
+                String::from("resolve"),
+                String::from("collect"),
+                String::from("compile"),
+                String::from("shell"),
+            ],
+            flags,
+        }
+    }
+}
+
+impl TaskRunner for ImplicitShellTask {
+    fn invoke(
+        &self,
+        _info: &ProjectInfo,
+        project: &Project,
+        configuration: &Configuration,
+        output: &mut TaskOutput<'_>,
+    ) -> Result<(), String> {
+        let mut regexes: HashMap<&str, Regex> = HashMap::new();
+        regexes.insert("envvars", Regex::new(r#"\{(.+?)}"#).unwrap());
+
+        let dependencies = implicit::resolve(project, configuration, &regexes, output)?;
+
+        let project_file = self
+            .flags
+            .use_project
+            .as_deref()
+            .unwrap_or(consts::PROJECT_FILE);
+        if classes_up_to_date(configuration, Path::new(project_file))? {
+            output.step_started("Collecting", "sources", 2);
+            output.step_completed("Collecting", "sources", 2, "Up to date");
+            output.step_started("Compiling", "classes", 3);
+            output.step_completed("Compiling", "classes", 3, "Up to date");
+        } else {
+            implicit::compile(configuration, &dependencies, output)?;
+        }
+
+        output.step_started("Starting", "jshell", 4);
+        output.suspend();
+        match self.run(configuration, &dependencies) {
+            Ok(()) => {
+                output.step_completed("Starting", "jshell", 4, "Done");
+                Ok(())
+            }
+            Err(error) => {
+                output.step_failed("Starting", "jshell", 4, &error);
+                Err(error)
+            }
+        }
+    }
+
+    fn phase_order(&self) -> &[String] {
+        self.order.as_ref()
+    }
+}
+
+impl ImplicitShellTask {
+    fn run(
+        &self,
+        configuration: &Configuration,
+        dependencies: &ResolvedDependencies,
+    ) -> Result<(), String> {
+        let mut jshell_command = Command::new("jshell");
+        jshell_command.args(["--class-path", &shell_classpath(dependencies)]);
+
+        if let Some(imports) = configuration.shell_imports()
+            && !imports.is_empty()
+        {
+            fs::write(consts::SHELL_STARTUP_FILE, startup_script(imports)).map_err(|e| {
+                format!(
+                    "Failed to write jshell startup file \"{}\": {e}",
+                    consts::SHELL_STARTUP_FILE
+                )
+            })?;
+            jshell_command.args(["--startup", "DEFAULT"]);
+            jshell_command.args(["--startup", consts::SHELL_STARTUP_FILE]);
+        }
+
+        jshell_command.args(&self.flags.passed_args);
+
+        let status = match jshell_command.status() {
+            Ok(s) => s,
+            Err(e) => {
+                return Err(format!(
+                    "Failed to start jshell: {e}\nFix: install a JDK 9 or newer and ensure `jshell` is on your PATH."
+                ));
+            }
+        };
+
+        if !status.success() {
+            exit_code::record_external_process_exit_code(status);
+            return Err(format!("jshell exited with status {status}"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Compiled classes come first, followed by every runtime, nested and compile-time dependency exactly once.
+fn shell_classpath(dependencies: &ResolvedDependencies) -> String {
+    let mut entries: Vec<String> = vec![String::from(consts::BINARY_OUT_PATH)];
+
+    let compile_entries = dependencies.classpath().unwrap_or_default();
+    let compile_entries = compile_entries
+        .split(consts::java_seperator())
+        .filter(|entry| !entry.is_empty())
+        .map(String::from);
+    let runtime_entries = dependencies
+        .paths()
+        .iter()
+        .chain(dependencies.nested_jars())
+        .map(|path| path.to_string_lossy().to_string());
+
+    for entry in runtime_entries.chain(compile_entries) {
+        if !entries.contains(&entry) {
+            entries.push(entry);
+        }
+    }
+
+    entries.join(&consts::java_seperator().to_string())
+}
+
+fn startup_script(imports: &[String]) -> String {
+    imports
+        .iter()
+        .map(|import| format!("import {};\n", import.trim().trim_end_matches(';')))
+        .collect()
+}
+
+/// Classes are reused when every source folder, source file, the project file, the selected
+/// configuration and the lockfile are older than the newest class file. Source folders are
+/// included so that deleting a source also invalidates the classes compiled from it.
+fn classes_up_to_date(configuration: &Configuration, project_file: &Path) -> Result<bool, String> {
+    let classes = files::collect_files_with_extension(Path::new(consts::BINARY_OUT_PATH), "class")?;
+    let Some(newest_class) = newest_modification(&classes) else {
+        return Ok(false);
+    };
+
+    let mut inputs: Vec<PathBuf> = vec![
+        project_file.to_path_buf(),
+        PathBuf::from(consts::METADATA_FILE),
+        PathBuf::from(consts::LOCKFILE),
+    ];
+    for source in configuration.sources().into_iter().flatten() {
+        collect_folders(Path::new(source), &mut inputs)?;
+        inputs.extend(files::collect_files_with_extension(
+            Path::new(source),
+            "java",
+        )?);
+    }
+
+    Ok(newest_modification(&inputs).is_none_or(|newest_input| newest_input <= newest_class))
+}
+
+fn collect_folders(path: &Path, folders: &mut Vec<PathBuf>) -> Result<(), String> {
+    if !path.is_dir() {
+        return Ok(());
+    }
+
+    folders.push(path.to_path_buf());
+    let entries = fs::read_dir(path)
+        .map_err(|e| format!("Could not read directory \"{}\": {e}", path.display()))?;
+    for entry in entries {
+        let entry =
+            entry.map_err(|e| format!("Could not read directory \"{}\": {e}", path.display()))?;
+        if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            collect_folders(&entry.path(), folders)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn newest_modification(paths: &[PathBuf]) -> Option<SystemTime> {
+    paths
+        .iter()
+        .filter_map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{TempDir, with_current_dir};
+    use std::time::Duration;
+    use toml::Table;
+
+    fn configuration(toml: &str) -> Configuration {
+        Configuration::from(
+            String::from("main"),
+            &toml.parse::<Table>().unwrap(),
+            String::from("Demo"),
+            String::from("1.0.0"),
+        )
+        .unwrap()
+    }
+
+    fn set_modified(path: &Path, time: SystemTime) {
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+    }
+
+    #[test]
+    fn startup_script_writes_one_import_per_entry() {
+        let imports = vec![
+            String::from("com.example.*"),
+            String::from("static com.example.Util.*;"),
+        ];
+
+        assert_eq!(
+            startup_script(&imports),
+            "import com.example.*;\nimport static com.example.Util.*;\n"
+        );
+    }
+
+    #[test]
+    fn classes_up_to_date_compares_sources_against_compiled_output() {
+        let temp = TempDir::new("shell-up-to-date");
+        fs::create_dir_all(temp.path().join("src")).unwrap();
+        fs::create_dir_all(temp.path().join(consts::BINARY_OUT_PATH)).unwrap();
+        let source = temp.path().join("src/Main.java");
+        let class = temp.path().join(consts::BINARY_OUT_PATH).join("Main.class");
+        let project_file = temp.path().join(consts::PROJECT_FILE);
+        fs::write(&source, "class Main {}").unwrap();
+        fs::write(&project_file, "").unwrap();
+
+        let old = SystemTime::now() - Duration::from_secs(60);
+        set_modified(&source, old);
+        set_modified(&project_file, old);
+
+        with_current_dir(temp.path(), || {
+            let configuration = configuration(r#"sources = [ "src/" ]"#);
+            let project_file = Path::new(consts::PROJECT_FILE);
+
+            assert!(!classes_up_to_date(&configuration, project_file).unwrap());
+
+            fs::write(&class, "").unwrap();
+            assert!(classes_up_to_date(&configuration, project_file).unwrap());
+
+            set_modified(&source, SystemTime::now() + Duration::from_secs(60));
+            assert!(!classes_up_to_date(&configuration, project_file).unwrap());
+        });
+    }
+
+    #[test]
+    fn classes_up_to_date_checks_configuration_lockfile_and_deleted_sources() {
+        let temp = TempDir::new("shell-up-to-date-inputs");
+        fs::create_dir_all(temp.path().join("src")).unwrap();
+        fs::create_dir_all(temp.path().join(".wisteria")).unwrap();
+        fs::create_dir_all(temp.path().join(consts::BINARY_OUT_PATH)).unwrap();
+        let source = temp.path().join("src/Main.java");
+        let class = temp.path().join(consts::BINARY_OUT_PATH).join("Main.class");
+        let project_file = temp.path().join("other.toml");
+        let metadata = temp.path().join(consts::METADATA_FILE);
+        let lockfile = temp.path().join(consts::LOCKFILE);
+        fs::write(&source, "class Main {}").unwrap();
+        fs::write(&project_file, "").unwrap();
+        fs::write(&metadata, "").unwrap();
+        fs::write(&lockfile, "").unwrap();
+        fs::write(&class, "").unwrap();
+
+        let old = SystemTime::now() - Duration::from_secs(60);
+        for path in [&source, &project_file, &metadata, &lockfile] {
+            set_modified(path, old);
+        }
+        fs::File::open(temp.path().join("src"))
+            .unwrap()
+            .set_modified(old)
+            .unwrap();
+
+        with_current_dir(temp.path(), || {
+            let configuration = configuration(r#"sources = [ "src/" ]"#);
+            let project_file = Path::new("other.toml");
+            assert!(classes_up_to_date(&configuration, project_file).unwrap());
+
+            let future = SystemTime::now() + Duration::from_secs(60);
+            for input in ["other.toml", consts::METADATA_FILE, consts::LOCKFILE] {
+                set_modified(Path::new(input), future);
+                assert!(!classes_up_to_date(&configuration, project_file).unwrap());
+                set_modified(Path::new(input), old);
+            }
+            assert!(classes_up_to_date(&configuration, project_file).unwrap());
+
+            fs::remove_file("src/Main.java").unwrap();
+            fs::File::open("src").unwrap().set_modified(future).unwrap();
+            assert!(!classes_up_to_date(&configuration, project_file).unwrap());
+        });
+    }
+}
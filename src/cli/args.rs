@@ -11,6 +11,17 @@ pub struct StartupFlags {
     pub no_refresh: bool,
     pub no_git: bool,
     pub output_mode: OutputMode,
+    pub jobs: Option<usize>,
+    pub offline: bool,
+    pub locked: bool,
+    pub dry_run: bool,
+    pub why: Option<String>,
+    pub scope: Option<String>,
+    pub to: Option<String>,
+    pub name: Option<String>,
+    pub sync: bool,
+    pub database: Option<String>,
+    pub fail_on: Option<String>,
     pub passed_args: Vec<String>,
 }
 
@@ -47,6 +58,13 @@ pub fn load_arguments(args: &mut Vec<String>) -> StartupFlags {
                 "minimal" => flags.minimal = true,
                 "norefresh" => flags.no_refresh = true,
                 "nogit" => flags.no_git = true,
+                "offline" => flags.offline = true,
+                "locked" => flags.locked = true,
+                "dry-run" => flags.dry_run = true,
+                "frozen" => {
+                    flags.locked = true;
+                    flags.offline = true;
+                }
                 "output" | "format" => match flag_value(
                     flag,
                     inline_value,
@@ -62,6 +80,78 @@ pub fn load_arguments(args: &mut Vec<String>) -> StartupFlags {
                     },
                     None => exit(1),
                 },
+                "jobs" => match flag_value(
+                    flag,
+                    inline_value,
+                    &mut args_iter,
+                    "Must specify how many dependencies may be resolved at once.",
+                ) {
+                    Some(value) => match value.parse::<usize>() {
+                        Ok(jobs) if jobs > 0 => flags.jobs = Some(jobs),
+                        _ => {
+                            println!(
+                                "Invalid value \"{value}\" for --jobs flag. Expected a whole number of at least 1."
+                            );
+                            exit(1)
+                        }
+                    },
+                    None => exit(1),
+                },
+                "sync" => flags.sync = true,
+                "scope" => match flag_value(
+                    flag,
+                    inline_value,
+                    &mut args_iter,
+                    "Expected one of [compile, runtime, provided, test].",
+                ) {
+                    Some(value) => flags.scope = Some(value),
+                    None => exit(1),
+                },
+                "to" => match flag_value(
+                    flag,
+                    inline_value,
+                    &mut args_iter,
+                    "Must specify the configuration to add the dependency to.",
+                ) {
+                    Some(value) => flags.to = Some(value),
+                    None => exit(1),
+                },
+                "name" => match flag_value(
+                    flag,
+                    inline_value,
+                    &mut args_iter,
+                    "Must specify the name to declare the dependency under.",
+                ) {
+                    Some(value) => flags.name = Some(value),
+                    None => exit(1),
+                },
+                "why" => match flag_value(
+                    flag,
+                    inline_value,
+                    &mut args_iter,
+                    "Must specify the dependency name or jar file name to explain.",
+                ) {
+                    Some(value) => flags.why = Some(value),
+                    None => exit(1),
+                },
+                "database" => match flag_value(
+                    flag,
+                    inline_value,
+                    &mut args_iter,
+                    "Must specify the OSV database directory, JSON file, or zip archive.",
+                ) {
+                    Some(value) => flags.database = Some(value),
+                    None => exit(1),
+                },
+                "fail-on" => match flag_value(
+                    flag,
+                    inline_value,
+                    &mut args_iter,
+                    "Expected one of [low, medium, high, critical].",
+                ) {
+                    Some(value) => flags.fail_on = Some(value),
+                    None => exit(1),
+                },
                 "project" => match flag_value(
                     flag,
                     inline_value,
@@ -0,0 +1,60 @@
+use std::process::exit;
+
+use crate::cli::args::StartupFlags;
+use crate::cli::commands::update::trigger_update;
+use crate::cli::commands::{align_versions, or_exit, project_file};
+use crate::dependency::{DependencyScope, policy};
+use crate::model::project_edit::{MavenCoordinates, ProjectEditor};
+use crate::model::{Metadata, Project};
+use crate::workspace::download;
+
+pub fn trigger_add(args: &[String], flags: &StartupFlags) {
+    if args.len() != 4 {
+        println!(
+            "Expected a dependency source and its coordinates.\nFix: run `wisteria add maven <group>:<artifact>[:<version>]`."
+        );
+        exit(1)
+    }
+
+    if args[2] != "maven" {
+        println!(
+            "Cannot add \"{}\" dependencies from the command line.\nFix: run `wisteria add maven <group>:<artifact>[:<version>]`, or declare the dependency in project.toml.",
+            args[2]
+        );
+        exit(1)
+    }
+
+    let coordinates = or_exit(MavenCoordinates::parse(&args[3]));
+    let scope = match &flags.scope {
+        Some(scope) => or_exit(DependencyScope::try_from(scope.clone()).map_err(|error| {
+            format!("{error}.\nFix: use one of [compile, runtime, provided, test].")
+        })),
+        None => DependencyScope::Compile,
+    };
+    let configuration = match &flags.to {
+        Some(configuration) => configuration.clone(),
+        None => Metadata::load().unwrap_or_default().configuration,
+    };
+    let name = flags
+        .name
+        .clone()
+        .unwrap_or_else(|| coordinates.artifact_id.clone());
+
+    let mut editor = or_exit(ProjectEditor::open(&project_file(flags)));
+    or_exit(editor.add_maven_dependency(&name, &coordinates));
+    or_exit(editor.add_reference(&configuration, &name, scope));
+    or_exit(editor.save());
+    println!("Added {name} to [dependencies.maven] and [configuration.{configuration}].");
+
+    if flags.sync {
+        let args = [String::from("wisteria"), String::from("update"), name];
+        let project = Project::from_with_flags(flags.use_project.clone(), flags.clone());
+        trigger_update(
+            align_versions(project, download::offline() || policy::locked()),
+            &args,
+            flags,
+        );
+    } else {
+        println!("Run `wisteria update {name}` to download and lock it.");
+    }
+}
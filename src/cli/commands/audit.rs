@@ -0,0 +1,237 @@
+use std::{path::Path, process::exit};
+
+use serde_json::json;
+
+use crate::cli::args::StartupFlags;
+use crate::cli::commands::dependencies::require_lockfile_or_exit;
+use crate::cli::commands::{or_exit, project_or_exit};
+use crate::dependency::Dependency;
+use crate::dependency::osv::{Finding, OsvDatabase, Severity};
+use crate::model::{Lockfile, Project};
+use crate::output::{self, OutputMode};
+
+/// Advisories found for one locked Maven dependency.
+struct AuditReport<'a> {
+    name: &'a str,
+    version: &'a str,
+    findings: Vec<Finding>,
+}
+
+pub fn trigger_audit(project: Result<Project, String>, args: &[String], flags: &StartupFlags) {
+    let project: Project = project_or_exit(project);
+    let mut output = output::renderer(flags.output_mode);
+
+    if args.len() > 2 {
+        output.log(
+            "`wisteria audit` checks every locked Maven dependency and does not accept dependency names.\nFix: run `wisteria audit --database <path>` without additional arguments."
+        );
+        exit(1)
+    }
+
+    let Some(database) = &flags.database else {
+        output.log(
+            "No OSV database was given.\nFix: download https://osv-vulnerabilities.storage.googleapis.com/Maven/all.zip and run `wisteria audit --database <path to all.zip>`.",
+        );
+        exit(1)
+    };
+    let threshold = or_exit(Severity::load(flags.fail_on.as_deref().unwrap_or("low")));
+    let lockfile = require_lockfile_or_exit();
+
+    output.operation_started("audit", 1);
+    output.step_started("audit", "Checking", "wisteria.lock", 1, 1);
+    let database = match OsvDatabase::load(Path::new(database)) {
+        Ok(database) => database,
+        Err(e) => {
+            output.step_failed("audit", "Checking", "wisteria.lock", 1, 1, &e);
+            output.operation_completed("audit", "Audit finished with errors.");
+            exit(1)
+        }
+    };
+
+    let reports = audit_lockfile(&project, &lockfile, &database);
+    let count = reports
+        .iter()
+        .map(|report| report.findings.len())
+        .sum::<usize>();
+    let failing = reports
+        .iter()
+        .flat_map(|report| &report.findings)
+        .filter(|finding| fails(finding, threshold))
+        .count();
+    output.step_completed(
+        "audit",
+        "Checking",
+        "wisteria.lock",
+        1,
+        1,
+        &format!("{count} {}", advisory_label(count)),
+    );
+    output.operation_completed("audit", "Audited dependencies");
+
+    for report in &reports {
+        for finding in &report.findings {
+            match flags.output_mode {
+                OutputMode::Json => println!(
+                    "{}",
+                    json!({
+                        "type": "advisory",
+                        "dependency": report.name,
+                        "version": report.version,
+                        "id": finding.id,
+                        "aliases": finding.aliases,
+                        "summary": finding.summary,
+                        "severity": finding.severity.map(|s| s.to_string()),
+                        "fixed": finding.fixed,
+                        "fails": fails(finding, threshold),
+                    })
+                ),
+                _ => output.log(&finding_line(report, finding)),
+            }
+        }
+    }
+
+    if failing > 0 {
+        output.log(&format!(
+            "{failing} {} at or above {threshold} severity.\nFix: update the affected dependencies to a fixed version, or raise the threshold with `--fail-on`.",
+            advisory_label(failing)
+        ));
+        exit(1)
+    }
+}
+
+/// Checks each declared Maven dependency at the version locked in wisteria.lock.
+fn audit_lockfile<'a>(
+    project: &'a Project,
+    lockfile: &'a Lockfile,
+    database: &OsvDatabase,
+) -> Vec<AuditReport<'a>> {
+    let mut names: Vec<&String> = project.dependencies().keys().collect();
+    names.sort_unstable();
+
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let Dependency::FetchFromMaven {
+                group_id,
+                artifact_id,
+                ..
+            } = &project.dependencies()[name]
+            else {
+                return None;
+            };
+            let version = lockfile.artifact_for_dependency(name)?.version()?;
+            let findings = database.findings(group_id, artifact_id, version);
+
+            (!findings.is_empty()).then_some(AuditReport {
+                name,
+                version,
+                findings,
+            })
+        })
+        .collect()
+}
+
+/// Advisories without a severity count as low, so they fail unless a higher threshold is set.
+fn fails(finding: &Finding, threshold: Severity) -> bool {
+    finding.severity.unwrap_or(Severity::Low) >= threshold
+}
+
+fn advisory_label(count: usize) -> &'static str {
+    match count {
+        1 => "advisory",
+        _ => "advisories",
+    }
+}
+
+fn finding_line(report: &AuditReport<'_>, finding: &Finding) -> String {
+    let mut line = format!("{} {}: {}", report.name, report.version, finding.id);
+    if !finding.aliases.is_empty() {
+        line.push_str(&format!(" ({})", finding.aliases.join(", ")));
+    }
+
+    let severity = finding
+        .severity
+        .map(|severity| severity.to_string())
+        .unwrap_or(String::from("unknown severity"));
+    line.push_str(&format!(" [{severity}]"));
+    if let Some(summary) = &finding.summary {
+        line.push_str(&format!(" {summary}"));
+    }
+
+    if finding.fixed.is_empty() {
+        line.push_str(". No fixed version is known.");
+    } else {
+        line.push_str(&format!(". Fixed in {}.", finding.fixed.join(", ")));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::model::LockfileArtifact;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn reports_advisories_for_locked_maven_versions() {
+        let temp = TempDir::new("audit-lockfile");
+        let project_file = temp.path().join("project.toml");
+        fs::write(
+            &project_file,
+            r#"
+            [project]
+            name = "Demo"
+            version = "1.0.0"
+            description = "Demo project"
+
+            [dependencies.maven]
+            gson = { group_id = "com.google.code.gson", artifact_id = "gson" }
+
+            [configuration.main]
+            sources = [ "src" ]
+            dependencies = [ "gson" ]
+            "#,
+        )
+        .unwrap();
+        fs::write(
+            temp.path().join("gson.json"),
+            r#"{
+                "id": "GHSA-4jrv-ppp4-jm57",
+                "aliases": ["CVE-2022-25647"],
+                "summary": "Deserialization of Untrusted Data in Gson",
+                "affected": [{
+                    "package": { "ecosystem": "Maven", "name": "com.google.code.gson:gson" },
+                    "ranges": [{
+                        "type": "ECOSYSTEM",
+                        "events": [{ "introduced": "0" }, { "fixed": "2.8.9" }]
+                    }]
+                }],
+                "database_specific": { "severity": "HIGH" }
+            }"#,
+        )
+        .unwrap();
+
+        let project = Project::from(Some(project_file.to_string_lossy().to_string())).unwrap();
+        let lockfile = Lockfile::from_artifacts_for_test(vec![LockfileArtifact::new(
+            String::from("gson"),
+            String::from("maven"),
+            Some(String::from("2.8.5")),
+            String::from("https://repo1.maven.org/maven2/gson-2.8.5.jar"),
+            String::from(".wisteria/cache/gson-2.8.5.jar"),
+            String::from("abc123"),
+        )]);
+        let database = OsvDatabase::load(&temp.path().join("gson.json")).unwrap();
+
+        let reports = audit_lockfile(&project, &lockfile, &database);
+        assert_eq!(reports.len(), 1);
+        let finding = &reports[0].findings[0];
+        assert_eq!(
+            finding_line(&reports[0], finding),
+            "gson 2.8.5: GHSA-4jrv-ppp4-jm57 (CVE-2022-25647) [high] Deserialization of Untrusted Data in Gson. Fixed in 2.8.9."
+        );
+        assert!(fails(finding, Severity::High));
+        assert!(!fails(finding, Severity::Critical));
+    }
+}
@@ -1,11 +1,14 @@
 use std::{collections::HashSet, process::exit};
 
-use crate::dependency::resolver::ResolvedDependency;
+use serde_json::{Value, json};
+
+use crate::cli::commands::verify::lockfile_disagreements;
+use crate::dependency::policy;
 use crate::model::lockfile::{
-    lockable_artifacts, lockable_artifacts_to_toml, lockfile_artifacts_to_toml, try_read_lockfile,
-    write_lockfile,
+    LockfileChange, lockfile_artifacts_to_toml, try_read_lockfile, write_lockfile,
 };
 use crate::model::{Lockfile, LockfileArtifact, Project};
+use crate::output::{OutputMode, OutputRenderer};
 
 pub(crate) struct DependencySelection {
     names: Vec<String>,
@@ -92,16 +95,23 @@ pub(crate) fn require_lockfile_or_exit() -> Lockfile {
     }
 }
 
-pub(crate) fn write_full_lockfile_or_exit(resolved_dependencies: &[ResolvedDependency]) {
-    let toml = match lockable_artifacts_to_toml(resolved_dependencies) {
-        Ok(toml) => toml,
-        Err(error) => {
-            println!("{error}");
-            exit(1)
-        }
-    };
+/// Under `--locked` or `--frozen`, exits unless `wisteria.lock` exists and agrees with project.toml.
+pub(crate) fn require_locked_project_or_exit(project: &Project) {
+    if !policy::locked() {
+        return;
+    }
 
-    write_lockfile_or_exit(&toml);
+    let lockfile = require_lockfile_or_exit();
+    let issues = lockfile_disagreements(project, &lockfile);
+    if issues.is_empty() {
+        return;
+    }
+
+    println!("project.toml and wisteria.lock disagree, which is not allowed with --locked:");
+    for issue in issues {
+        println!("- {issue}");
+    }
+    exit(1)
 }
 
 pub(crate) fn write_full_lockfile_artifacts_or_exit(artifacts: Vec<LockfileArtifact>) {
@@ -116,11 +126,13 @@ pub(crate) fn write_full_lockfile_artifacts_or_exit(artifacts: Vec<LockfileArtif
     write_lockfile_or_exit(&toml);
 }
 
-pub(crate) fn write_partial_lockfile_or_exit(
+/// The existing lock entries of every dependency not in `target_dependencies`, plus the newly
+/// resolved entries of those that are.
+pub(crate) fn merged_lockfile_artifacts(
     existing_lockfile: Option<&Lockfile>,
-    resolved_dependencies: &[ResolvedDependency],
+    mut resolved_artifacts: Vec<LockfileArtifact>,
     target_dependencies: &[String],
-) {
+) -> Vec<LockfileArtifact> {
     let target_names: HashSet<&str> = target_dependencies.iter().map(String::as_str).collect();
     let mut artifacts: Vec<LockfileArtifact> = existing_lockfile
         .map(|lockfile| {
@@ -133,47 +145,79 @@ pub(crate) fn write_partial_lockfile_or_exit(
         })
         .unwrap_or_default();
 
-    artifacts.extend(lockable_artifacts(resolved_dependencies));
+    artifacts.append(&mut resolved_artifacts);
+    artifacts
+}
 
-    let toml = match lockfile_artifacts_to_toml(artifacts) {
-        Ok(toml) => toml,
-        Err(error) => {
-            println!("{error}");
-            exit(1)
+/// Prints lockfile changes as `+` added, `-` removed and `~` changed entries, or as
+/// `lockfile_change` events with JSON output. An empty hash is one that is not known yet, as in
+/// `update --dry-run`, and is reported as `null`.
+pub(crate) fn print_lockfile_changes(
+    output: &mut dyn OutputRenderer,
+    mode: OutputMode,
+    changes: &[LockfileChange],
+) {
+    for change in changes {
+        match mode {
+            OutputMode::Json => println!("{}", lockfile_change_event(change)),
+            _ => output.log(&lockfile_change_line(change)),
         }
-    };
+    }
+}
 
-    write_lockfile_or_exit(&toml);
+fn lockfile_change_line(change: &LockfileChange) -> String {
+    match change {
+        LockfileChange::Added(artifact) => {
+            format!("+ {} {}", artifact.name(), artifact_version(artifact))
+        }
+        LockfileChange::Removed(artifact) => {
+            format!("- {} {}", artifact.name(), artifact_version(artifact))
+        }
+        LockfileChange::Changed { old, new } if old.version() != new.version() => format!(
+            "~ {} {} -> {}",
+            new.name(),
+            artifact_version(old),
+            artifact_version(new)
+        ),
+        LockfileChange::Changed { old, new } if old.hash() != new.hash() => format!(
+            "~ {} {} hash {} -> {}",
+            new.name(),
+            artifact_version(new),
+            short_hash(old.hash()),
+            short_hash(new.hash())
+        ),
+        LockfileChange::Changed { new, .. } => format!(
+            "~ {} {} (source, URL or signing key changed)",
+            new.name(),
+            artifact_version(new)
+        ),
+    }
 }
 
-pub(crate) fn write_partial_lockfile_artifacts_or_exit(
-    existing_lockfile: Option<&Lockfile>,
-    mut resolved_artifacts: Vec<LockfileArtifact>,
-    target_dependencies: &[String],
-) {
-    let target_names: HashSet<&str> = target_dependencies.iter().map(String::as_str).collect();
-    let mut artifacts: Vec<LockfileArtifact> = existing_lockfile
-        .map(|lockfile| {
-            lockfile
-                .artifacts()
-                .iter()
-                .filter(|artifact| !target_names.contains(artifact.name()))
-                .cloned()
-                .collect()
-        })
-        .unwrap_or_default();
+fn lockfile_change_event(change: &LockfileChange) -> Value {
+    let (kind, old, new) = match change {
+        LockfileChange::Added(artifact) => ("added", None, Some(artifact)),
+        LockfileChange::Removed(artifact) => ("removed", Some(artifact), None),
+        LockfileChange::Changed { old, new } => ("changed", Some(old), Some(new)),
+    };
 
-    artifacts.append(&mut resolved_artifacts);
+    json!({
+        "type": "lockfile_change",
+        "change": kind,
+        "dependency": change.name(),
+        "old_version": old.and_then(LockfileArtifact::version),
+        "new_version": new.and_then(LockfileArtifact::version),
+        "old_hash": old.map(LockfileArtifact::hash).filter(|hash| !hash.is_empty()),
+        "new_hash": new.map(LockfileArtifact::hash).filter(|hash| !hash.is_empty()),
+    })
+}
 
-    let toml = match lockfile_artifacts_to_toml(artifacts) {
-        Ok(toml) => toml,
-        Err(error) => {
-            println!("{error}");
-            exit(1)
-        }
-    };
+fn artifact_version(artifact: &LockfileArtifact) -> &str {
+    artifact.version().unwrap_or("(unversioned)")
+}
 
-    write_lockfile_or_exit(&toml);
+fn short_hash(hash: &str) -> &str {
+    hash.get(..12).unwrap_or(hash)
 }
 
 fn all_dependency_names(project: &Project) -> Vec<String> {
@@ -258,4 +302,51 @@ mod tests {
 
         assert_eq!(duplicate_dependency_name(&names), None);
     }
+
+    fn artifact(version: &str, hash: &str) -> LockfileArtifact {
+        LockfileArtifact::new(
+            String::from("gson"),
+            String::from("maven"),
+            Some(String::from(version)),
+            format!("https://example.com/gson-{version}.jar"),
+            format!(".wisteria/cache/gson-{version}.jar"),
+            String::from(hash),
+        )
+    }
+
+    #[test]
+    fn lockfile_change_lines_show_versions_or_hashes() {
+        let old = artifact("2.10", "0123456789abcdef");
+
+        assert_eq!(
+            lockfile_change_line(&LockfileChange::Added(old.clone())),
+            "+ gson 2.10"
+        );
+        assert_eq!(
+            lockfile_change_line(&LockfileChange::Changed {
+                old: old.clone(),
+                new: artifact("2.11.0", "fedcba9876543210"),
+            }),
+            "~ gson 2.10 -> 2.11.0"
+        );
+        assert_eq!(
+            lockfile_change_line(&LockfileChange::Changed {
+                old,
+                new: artifact("2.10", "fedcba9876543210"),
+            }),
+            "~ gson 2.10 hash 0123456789ab -> fedcba987654"
+        );
+    }
+
+    #[test]
+    fn lockfile_change_event_reports_unknown_hashes_as_null() {
+        let event = lockfile_change_event(&LockfileChange::Changed {
+            old: artifact("2.10", "0123456789abcdef"),
+            new: artifact("2.11.0", ""),
+        });
+
+        assert_eq!(event["old_hash"], "0123456789abcdef");
+        assert!(event["new_hash"].is_null());
+        assert_eq!(event["new_version"], "2.11.0");
+    }
 }
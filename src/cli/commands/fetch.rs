@@ -11,7 +11,7 @@ use crate::cli::commands::project_or_exit;
 use crate::model::{Lockfile, LockfileArtifact, Project};
 use crate::output;
 use crate::util::consts;
-use crate::workspace::{download, files, paths};
+use crate::workspace::{download, files, global_cache, paths, signature};
 
 pub fn trigger_fetch(project: Result<Project, String>, args: &[String], flags: &StartupFlags) {
     let _project: Project = project_or_exit(project);
@@ -142,23 +142,55 @@ fn valid_lockfile_dependencies_message(lockfile: &Lockfile) -> String {
 }
 
 fn fetch_lockfile_artifact(artifact: &LockfileArtifact) -> Result<String, String> {
-    let cache_path = PathBuf::from(artifact.cache_path());
-    let replacing_existing = cache_path.exists();
+    let status = fetch_artifact_file(
+        artifact.name(),
+        artifact.fetch_url(),
+        artifact.cache_path(),
+        artifact.hash(),
+    )?;
+    restore_signature(artifact)?;
+    for attachment in artifact.attachments() {
+        fetch_artifact_file(
+            &format!("{} {}", artifact.name(), attachment.kind()),
+            attachment.fetch_url(),
+            attachment.cache_path(),
+            attachment.hash(),
+        )?;
+    }
+    Ok(status)
+}
+
+/// Makes sure `cache_path` holds the file locked with `locked_hash`, restoring or downloading it
+/// when it does not.
+fn fetch_artifact_file(
+    name: &str,
+    fetch_url: &str,
+    cache_path: &str,
+    locked_hash: &str,
+) -> Result<String, String> {
+    let path = PathBuf::from(cache_path);
+    let replacing_existing = path.exists();
     if replacing_existing {
-        let hash = files::generate_sha2_for_file(&cache_path)?;
-        if hash == artifact.hash() {
+        let hash = files::generate_sha2_for_file(&path)?;
+        if hash == locked_hash {
             return Ok(String::from("Already cached"));
         }
     }
 
-    let temp_path = temporary_artifact_path(artifact.cache_path());
-    paths::ensure_parents(artifact.cache_path())?;
+    if global_cache::restore(locked_hash, &path)? {
+        return Ok(String::from("Restored from the global cache"));
+    }
+
+    let temp_path = temporary_artifact_path(cache_path);
+    paths::ensure_parents(cache_path)?;
     paths::ensure_parents(path_to_str(&temp_path)?)?;
 
     let size = match download::download_silent(
-        artifact.name().to_string(),
-        artifact.fetch_url().to_string(),
+        name.to_string(),
+        fetch_url.to_string(),
         temp_path.to_string_lossy().to_string(),
+        None,
+        None,
     ) {
         Ok(size) => size,
         Err(error) => {
@@ -175,28 +207,61 @@ fn fetch_lockfile_artifact(artifact: &LockfileArtifact) -> Result<String, String
         }
     };
 
-    if hash != artifact.hash() {
+    if hash != locked_hash {
         let cleanup_note = cleanup_temp_artifact(&temp_path);
         return Err(format!(
-            "Downloaded artifact from `{}` does not match the hash in `{}`; the existing cached artifact was not replaced.{cleanup_note}",
-            artifact.fetch_url(),
+            "Downloaded artifact from `{fetch_url}` does not match the hash in `{}`; the existing cached artifact was not replaced.{cleanup_note}",
             consts::LOCKFILE
         ));
     }
 
-    fs::rename(&temp_path, &cache_path).map_err(|e| {
+    fs::rename(&temp_path, &path).map_err(|e| {
         let cleanup_note = cleanup_temp_artifact(&temp_path);
         format!(
-            "Downloaded artifact matched the lockfile, but failed to replace `{}`: {e}{cleanup_note}",
-            artifact.cache_path()
+            "Downloaded artifact matched the lockfile, but failed to replace `{cache_path}`: {e}{cleanup_note}"
         )
     })?;
 
+    let store_note = match global_cache::store(&path, locked_hash) {
+        Ok(()) => String::new(),
+        Err(error) => format!(" (not added to the global cache: {error})"),
+    };
+
     if replacing_existing {
-        Ok(format!("Re-fetched {:.3} MB", size))
+        Ok(format!("Re-fetched {:.3} MB{store_note}", size))
     } else {
-        Ok(format!("Fetched {:.3} MB", size))
+        Ok(format!("Fetched {:.3} MB{store_note}", size))
+    }
+}
+
+/// Makes sure an artifact locked with a signing key has its signature cached, and that the signature
+/// still comes from the locked key.
+fn restore_signature(artifact: &LockfileArtifact) -> Result<(), String> {
+    let Some(signing_key) = artifact.signing_key() else {
+        return Ok(());
+    };
+
+    let signature_path = signature::signature_path(artifact.cache_path());
+    let fingerprint = signature::verify_published_signature(
+        artifact.name(),
+        artifact.fetch_url(),
+        artifact.cache_path(),
+    )
+    .inspect_err(|_| {
+        let _ = fs::remove_file(&signature_path);
+    })?;
+
+    if fingerprint != signing_key {
+        let _ = fs::remove_file(&signature_path);
+        return Err(format!(
+            "`{}` is signed by key {fingerprint}, but `{}` records key {signing_key}.\nFix: find out why the signer changed, then run `wisteria update {}` to lock the new key.",
+            artifact.fetch_url(),
+            consts::LOCKFILE,
+            artifact.name()
+        ));
     }
+
+    Ok(())
 }
 
 fn temporary_artifact_path(cache_path: &str) -> PathBuf {
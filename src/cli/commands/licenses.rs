@@ -0,0 +1,361 @@
+use std::{collections::BTreeMap, process::exit};
+
+use serde_json::json;
+
+use crate::cli::args::StartupFlags;
+use crate::cli::commands::dependencies::read_lockfile_or_exit;
+use crate::cli::commands::project_or_exit;
+use crate::dependency::license::{LicensePolicy, spdx_identifier};
+use crate::dependency::sources::maven;
+use crate::dependency::{Dependency, DependencyReference};
+use crate::model::{Lockfile, Project};
+use crate::output::{self, OutputMode};
+
+/// The licenses of one dependency, from `[licenses.declared]` or its POM, or why they could not
+/// be read.
+struct DependencyLicenses {
+    version: Option<String>,
+    licenses: Vec<String>,
+    error: Option<String>,
+}
+
+pub fn trigger_licenses(project: Result<Project, String>, args: &[String], flags: &StartupFlags) {
+    let project: Project = project_or_exit(project);
+    let mut output = output::renderer(flags.output_mode);
+
+    if args.len() > 2 {
+        output.log(
+            "`wisteria licenses` reports every configuration and does not accept arguments.\nFix: run `wisteria licenses` without additional arguments."
+        );
+        exit(1)
+    }
+
+    let lockfile = read_lockfile_or_exit();
+    let mut names: Vec<&str> = project
+        .info()
+        .configurations()
+        .values()
+        .flat_map(|configuration| configuration.dependencies().into_iter().flatten())
+        .map(DependencyReference::name)
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+
+    let size = names.len();
+    output.operation_started("licenses", size);
+    let mut found: BTreeMap<&str, DependencyLicenses> = BTreeMap::new();
+    let mut failed = false;
+    for (index, name) in names.into_iter().enumerate() {
+        output.step_started("licenses", "Reading", name, index + 1, size);
+        match dependency_licenses(&project, lockfile.as_ref(), name) {
+            Ok(licenses) => {
+                let status = if licenses.licenses.is_empty() {
+                    String::from("Unknown")
+                } else {
+                    licenses.licenses.join(", ")
+                };
+                output.step_completed("licenses", "Reading", name, index + 1, size, &status);
+                found.insert(name, licenses);
+            }
+            Err(e) => {
+                failed = true;
+                output.step_failed(
+                    "licenses",
+                    "Reading",
+                    name,
+                    index + 1,
+                    size,
+                    &format!("Could not read the licenses of {name}: {e}"),
+                );
+                found.insert(
+                    name,
+                    DependencyLicenses {
+                        version: None,
+                        licenses: Vec::new(),
+                        error: Some(e),
+                    },
+                );
+            }
+        }
+    }
+    output.operation_completed(
+        "licenses",
+        if failed {
+            "License check finished with errors."
+        } else {
+            "Read dependency licenses"
+        },
+    );
+
+    let mut configuration_names: Vec<&String> = project.info().configurations().keys().collect();
+    configuration_names.sort_unstable();
+
+    let mut violations = 0;
+    let mut unreadable = 0;
+    for configuration_name in configuration_names {
+        let configuration = &project.info().configurations()[configuration_name];
+        let references = configuration.dependencies().into_iter().flatten();
+        if flags.output_mode != OutputMode::Json {
+            output.log(&format!("[configuration.{configuration_name}]"));
+        }
+
+        for reference in references {
+            let licenses = &found[reference.name()];
+            let violation = license_violation(project.license_policy(), reference, licenses);
+            if violation.is_some() {
+                violations += 1;
+            }
+            if licenses.error.is_some() && is_shipped(reference) {
+                unreadable += 1;
+            }
+
+            match flags.output_mode {
+                OutputMode::Json => println!(
+                    "{}",
+                    json!({
+                        "type": "dependency_license",
+                        "configuration": configuration_name,
+                        "dependency": reference.name(),
+                        "version": licenses.version,
+                        "scope": reference.scope().to_string(),
+                        "packaging": reference.packaging().map(|p| p.to_string()),
+                        "licenses": licenses.licenses,
+                        "violation": violation,
+                        "error": licenses.error,
+                    })
+                ),
+                _ => output.log(&license_line(reference, licenses, violation.as_deref())),
+            }
+        }
+    }
+
+    if violations > 0 {
+        output.log(&format!(
+            "{violations} shipped dependency {} a license which is not allowed.\nFix: replace the dependency, change `allow` or `deny` in [licenses], or record the license of a dependency without a POM under [licenses.declared].",
+            match violations {
+                1 => "reference carries",
+                _ => "references carry",
+            }
+        ));
+    }
+    if unreadable > 0 {
+        output.log(&format!(
+            "The licenses of {unreadable} shipped dependency {} could not be read, so they were not checked.\nFix: run `wisteria fetch` while online so locked POMs can be read, or record the license under [licenses.declared].",
+            match unreadable {
+                1 => "reference",
+                _ => "references",
+            }
+        ));
+    }
+    if violations > 0 || unreadable > 0 {
+        exit(1)
+    }
+}
+
+fn dependency_licenses(
+    project: &Project,
+    lockfile: Option<&Lockfile>,
+    name: &str,
+) -> Result<DependencyLicenses, String> {
+    let locked = lockfile.and_then(|lockfile| lockfile.artifact_for_dependency(name));
+    let version = locked
+        .and_then(|artifact| artifact.version())
+        .map(String::from);
+    if let Some(declared) = project.license_policy().declared(name) {
+        return Ok(DependencyLicenses {
+            version,
+            licenses: declared.to_vec(),
+            error: None,
+        });
+    }
+
+    let licenses = match project.dependencies().get(name) {
+        Some(Dependency::FetchFromMaven {
+            repositories,
+            classifier,
+            ..
+        }) => {
+            let Some(locked) = locked else {
+                return Err(format!(
+                    "{name} is not locked, so its POM is unknown.\nFix: run `wisteria sync {name}`."
+                ));
+            };
+
+            let mut licenses: Vec<String> =
+                maven::locked_licenses(repositories, classifier.as_ref(), locked)?
+                    .iter()
+                    .filter_map(spdx_identifier)
+                    .collect();
+            licenses.dedup();
+            licenses
+        }
+        _ => Vec::new(),
+    };
+
+    Ok(DependencyLicenses {
+        version,
+        licenses,
+        error: None,
+    })
+}
+
+/// Only references which end up in what the project ships are held to `[licenses]`. Licenses
+/// which could not be read are reported as failures instead.
+fn license_violation(
+    policy: &LicensePolicy,
+    reference: &DependencyReference,
+    licenses: &DependencyLicenses,
+) -> Option<String> {
+    (is_shipped(reference) && licenses.error.is_none())
+        .then(|| policy.violation(&licenses.licenses))
+        .flatten()
+}
+
+fn is_shipped(reference: &DependencyReference) -> bool {
+    reference.scope().is_on_runtime_classpath() || reference.is_shaded() || reference.is_nested()
+}
+
+fn license_line(
+    reference: &DependencyReference,
+    licenses: &DependencyLicenses,
+    violation: Option<&str>,
+) -> String {
+    let mut line = format!("  {}", reference.name());
+    if let Some(version) = &licenses.version {
+        line.push_str(&format!(" {version}"));
+    }
+    line.push_str(&format!(" [{}", reference.scope()));
+    if let Some(packaging) = reference.packaging() {
+        line.push_str(&format!(", {packaging}"));
+    }
+    line.push_str("]: ");
+
+    if licenses.error.is_some() {
+        line.push_str("could not be read");
+    } else if licenses.licenses.is_empty() {
+        line.push_str("unknown");
+    } else {
+        line.push_str(&licenses.licenses.join(", "));
+    }
+    if let Some(violation) = violation {
+        line.push_str(&format!(" (not allowed: {violation})"));
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::dependency::{DependencyScope, PackagingType};
+    use crate::model::LockfileArtifact;
+    use crate::test_support::{TempDir, with_current_dir};
+
+    #[test]
+    fn reads_cached_poms_and_checks_shipped_references() {
+        let temp = TempDir::new("licenses-report");
+        let project_file = temp.path().join("project.toml");
+        fs::write(
+            &project_file,
+            r#"
+            [project]
+            name = "Demo"
+            version = "1.0.0"
+            description = "Demo project"
+
+            [dependencies.maven]
+            gson = { group_id = "com.google.code.gson", artifact_id = "gson", version = "2.11.0" }
+
+            [dependencies.archive]
+            local = { path = "lib/local.jar" }
+
+            [licenses]
+            allow = [ "MIT" ]
+
+            [licenses.declared]
+            local = "MIT"
+
+            [configuration.main]
+            sources = [ "src" ]
+            dependencies = [ "gson", "local" ]
+            "#,
+        )
+        .unwrap();
+        let cache = temp
+            .path()
+            .join(".wisteria/cache/com.google.code.gson/gson/2.11.0");
+        fs::create_dir_all(&cache).unwrap();
+        fs::write(
+            cache.join("gson.pom"),
+            r#"<project>
+              <licenses>
+                <license><name>The Apache Software License, Version 2.0</name></license>
+              </licenses>
+            </project>"#,
+        )
+        .unwrap();
+        let lockfile = Lockfile::from_artifacts_for_test(vec![LockfileArtifact::new(
+            String::from("gson"),
+            String::from("maven"),
+            Some(String::from("2.11.0")),
+            String::from(
+                "https://repo1.maven.org/maven2/com/google/code/gson/gson/2.11.0/gson-2.11.0.jar",
+            ),
+            String::from(".wisteria/cache/com.google.code.gson/gson/2.11.0/gson.jar"),
+            String::from("abc123"),
+        )]);
+
+        with_current_dir(temp.path(), || {
+            let project = Project::from(Some(project_file.to_string_lossy().to_string())).unwrap();
+            let gson = dependency_licenses(&project, Some(&lockfile), "gson").unwrap();
+            let local = dependency_licenses(&project, Some(&lockfile), "local").unwrap();
+            assert_eq!(gson.licenses, vec!["Apache-2.0"]);
+            assert_eq!(local.licenses, vec!["MIT"]);
+
+            let policy = project.license_policy();
+            let reference =
+                |scope, packaging| DependencyReference::new(String::from("gson"), scope, packaging);
+            let compile = reference(DependencyScope::Compile, None);
+            let violation = license_violation(policy, &compile, &gson);
+            assert_eq!(violation.as_deref(), Some("Apache-2.0 is not allowed"));
+            assert_eq!(
+                license_line(&compile, &gson, violation.as_deref()),
+                "  gson 2.11.0 [compile]: Apache-2.0 (not allowed: Apache-2.0 is not allowed)"
+            );
+            assert!(
+                license_violation(
+                    policy,
+                    &reference(DependencyScope::Provided, Some(PackagingType::Shade)),
+                    &gson
+                )
+                .is_some()
+            );
+            assert!(
+                license_violation(policy, &reference(DependencyScope::Test, None), &gson).is_none()
+            );
+        });
+    }
+
+    #[test]
+    fn unreadable_licenses_are_not_checked_against_the_policy() {
+        let policy = LicensePolicy::load(Some(&toml::Value::Table(
+            r#"deny = [ "GPL-3.0-only" ]"#.parse().unwrap(),
+        )))
+        .unwrap();
+        let reference =
+            DependencyReference::new(String::from("gson"), DependencyScope::Compile, None);
+        let licenses = DependencyLicenses {
+            version: None,
+            licenses: Vec::new(),
+            error: Some(String::from("gson is not locked")),
+        };
+
+        assert!(is_shipped(&reference));
+        assert!(license_violation(&policy, &reference, &licenses).is_none());
+        assert_eq!(
+            license_line(&reference, &licenses, None),
+            "  gson [compile]: could not be read"
+        );
+    }
+}
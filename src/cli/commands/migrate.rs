@@ -1,9 +1,11 @@
-use std::{path::PathBuf, process::exit};
+use std::process::exit;
 
 use crate::{
-    cli::{args::StartupFlags, commands::print_header},
+    cli::{
+        args::StartupFlags,
+        commands::{print_header, project_file},
+    },
     model::migration::migrate_wisteria2_project_file,
-    util::consts,
 };
 
 pub fn trigger_migrate(args: &[String], flags: &StartupFlags) {
@@ -28,12 +30,7 @@ pub fn trigger_migrate2(flags: &StartupFlags) {
 fn trigger_wisteria2_migration(flags: &StartupFlags) {
     print_header();
 
-    let project_file = PathBuf::from(
-        flags
-            .use_project
-            .clone()
-            .unwrap_or_else(|| String::from(consts::PROJECT_FILE)),
-    );
+    let project_file = project_file(flags);
 
     match migrate_wisteria2_project_file(&project_file) {
         Ok(migration) => {
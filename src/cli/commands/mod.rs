@@ -1,22 +1,33 @@
-use std::{collections::HashMap, process::exit};
+use std::{collections::HashMap, path::PathBuf, process::exit};
 
 use regex::Regex;
 
-use crate::dependency::resolver::{ResolveContext, ResolvedDependency};
-use crate::dependency::{Dependency, UpdateContext};
+use crate::cli::args::StartupFlags;
+use crate::dependency::UpdateContext;
+use crate::dependency::resolver::{
+    ResolveContext, ResolveEvent, ResolveJob, ResolvedDependency, resolve_concurrently,
+};
 use crate::model::{Configuration, Lockfile, Project};
 use crate::output::OutputRenderer;
+use crate::util::consts;
 
+pub mod add;
+pub mod audit;
 pub mod clean;
 pub mod create;
 pub mod dependencies;
 pub mod fetch;
 pub mod info;
+pub mod licenses;
 pub mod migrate;
+pub mod outdated;
 pub mod refresh;
+pub mod remove;
+pub mod sbom;
 pub mod switch;
 pub mod sync;
 pub mod task;
+pub mod tree;
 pub mod update;
 pub mod verify;
 
@@ -41,6 +52,37 @@ pub(crate) fn project_or_exit(project: Result<Project, String>) -> Project {
     }
 }
 
+/// Aligns versions with the project's BOMs, which must happen after the network settings are
+/// configured. With `cached_only`, only BOMs whose POMs are cached are read.
+pub(crate) fn align_versions(
+    project: Result<Project, String>,
+    cached_only: bool,
+) -> Result<Project, String> {
+    let mut project = project?;
+    project.align_versions(cached_only)?;
+    Ok(project)
+}
+
+/// The project file given with `--project`, or project.toml.
+pub(crate) fn project_file(flags: &StartupFlags) -> PathBuf {
+    PathBuf::from(
+        flags
+            .use_project
+            .clone()
+            .unwrap_or_else(|| String::from(consts::PROJECT_FILE)),
+    )
+}
+
+pub(crate) fn or_exit<T>(result: Result<T, String>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(error) => {
+            println!("{error}");
+            exit(1)
+        }
+    }
+}
+
 pub(crate) fn configuration_or_exit<'a>(
     project: &'a Project,
     configuration_name: &str,
@@ -100,7 +142,7 @@ impl<'a> CommandOutput<'a> {
 pub(crate) fn update_dependencies_with_context(
     output: CommandOutput<'_>,
     targets: &[String],
-    dependencies: &HashMap<String, Dependency>,
+    project: &Project,
     environment: &HashMap<String, String>,
     regexes: &HashMap<&str, Regex>,
     context: UpdateContext,
@@ -115,43 +157,17 @@ pub(crate) fn update_dependencies_with_context(
     let size = targets.len();
     output.renderer.operation_started(output.operation, size);
 
+    let mut jobs: Vec<ResolveJob<'_>> = Vec::new();
+    let mut steps: Vec<usize> = Vec::new();
     for (index, target) in targets.iter().enumerate() {
-        let step = index + 1;
-        match dependencies.get_key_value(target) {
-            Some((name, dep)) => {
-                output
-                    .renderer
-                    .step_started(output.operation, action, name, step, size);
-                match dep.resolve(
+        match project.dependencies().get_key_value(target) {
+            Some((name, dependency)) => {
+                jobs.push(ResolveJob {
                     name,
-                    environment,
-                    regexes,
-                    ResolveContext::for_dependency(context, lockfile, name),
-                ) {
-                    Ok(resolved) => {
-                        output.renderer.step_completed(
-                            output.operation,
-                            action,
-                            name,
-                            step,
-                            size,
-                            "Done",
-                        );
-                        resolved_dependencies.push(resolved)
-                    }
-                    Err(e) => {
-                        output.renderer.step_failed(
-                            output.operation,
-                            action,
-                            name,
-                            step,
-                            size,
-                            &format!("Could not {failure_action} {name}: {e}"),
-                        );
-                        failed_downloads.push((name.clone(), e));
-                        continue;
-                    }
-                };
+                    dependency,
+                    context: ResolveContext::for_dependency(context, lockfile, name),
+                });
+                steps.push(index + 1);
             }
             None => {
                 output
@@ -161,6 +177,55 @@ pub(crate) fn update_dependencies_with_context(
         }
     }
 
+    let results = resolve_concurrently(
+        &jobs,
+        project.info().concurrency(),
+        environment,
+        regexes,
+        |event| match event {
+            ResolveEvent::Started(job) => output.renderer.step_started(
+                output.operation,
+                action,
+                jobs[job].name,
+                steps[job],
+                size,
+            ),
+            ResolveEvent::Progress(job, bytes, total) => {
+                output
+                    .renderer
+                    .step_progress(output.operation, jobs[job].name, bytes, total)
+            }
+            ResolveEvent::Finished(job, Ok(resolved)) => {
+                output.renderer.step_completed(
+                    output.operation,
+                    action,
+                    jobs[job].name,
+                    steps[job],
+                    size,
+                    "Done",
+                );
+                for warning in &resolved.warnings {
+                    output.renderer.log(warning);
+                }
+            }
+            ResolveEvent::Finished(job, Err(e)) => output.renderer.step_failed(
+                output.operation,
+                action,
+                jobs[job].name,
+                steps[job],
+                size,
+                &format!("Could not {failure_action} {}: {e}", jobs[job].name),
+            ),
+        },
+    );
+
+    for (job, result) in jobs.iter().zip(results) {
+        match result {
+            Ok(resolved) => resolved_dependencies.push(resolved),
+            Err(e) => failed_downloads.push((job.name.to_string(), e)),
+        }
+    }
+
     if failed_downloads.is_empty() {
         output.renderer.operation_completed(
             output.operation,
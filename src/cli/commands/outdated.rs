@@ -0,0 +1,279 @@
+use std::{cmp::Ordering, process::exit};
+
+use serde_json::json;
+
+use crate::cli::args::StartupFlags;
+use crate::cli::commands::dependencies::{dependency_selection_or_exit, require_lockfile_or_exit};
+use crate::cli::commands::project_or_exit;
+use crate::dependency::Dependency;
+use crate::dependency::sources::{github, maven};
+use crate::dependency::version::{compare_versions, is_prerelease, major};
+use crate::model::{Lockfile, Project};
+use crate::output::{self, OutputMode};
+use crate::workspace::download;
+
+/// Current, newest same-major and newest overall versions of one locked dependency.
+#[derive(Debug, PartialEq, Eq)]
+struct VersionReport {
+    name: String,
+    current: String,
+    compatible: String,
+    latest: String,
+}
+
+impl VersionReport {
+    fn is_outdated(&self) -> bool {
+        self.current != self.latest
+    }
+}
+
+pub fn trigger_outdated(project: Result<Project, String>, args: &[String], flags: &StartupFlags) {
+    let project: Project = project_or_exit(project);
+
+    if download::offline() {
+        println!(
+            "Cannot check for newer dependency versions while offline, because available versions are only listed by remote repositories.\nFix: run `wisteria outdated` again once you are online, without `--offline` or `--frozen`."
+        );
+        exit(1)
+    }
+
+    let lockfile = require_lockfile_or_exit();
+    let selection = dependency_selection_or_exit(&project, args, "outdated", true);
+    let mut output = output::renderer(flags.output_mode);
+    let mut targets: Vec<(&String, &str)> = Vec::new();
+    for name in selection.names() {
+        if !matches!(
+            project.dependencies().get(name),
+            Some(Dependency::FetchFromMaven { .. } | Dependency::FetchFromGithub { .. })
+        ) {
+            continue;
+        }
+
+        match locked_version(&lockfile, name) {
+            Some(version) => targets.push((name, version)),
+            None => output.log(&format!(
+                "Skipping {name}, which has no locked version in wisteria.lock.\nFix: run `wisteria sync {name}` to lock it."
+            )),
+        }
+    }
+
+    let mut reports: Vec<VersionReport> = Vec::new();
+    let mut failed = false;
+    let size = targets.len();
+    output.operation_started("outdated", size);
+
+    for (index, (name, current)) in targets.iter().enumerate() {
+        output.step_started("outdated", "Checking", name, index + 1, size);
+        match available_versions(&project.dependencies()[*name]) {
+            Ok(versions) => {
+                let report = version_report(name, current, &versions);
+                let status = if report.is_outdated() {
+                    format!("{} available", report.latest)
+                } else {
+                    String::from("Up to date")
+                };
+                output.step_completed("outdated", "Checking", name, index + 1, size, &status);
+                reports.push(report);
+            }
+            Err(e) => {
+                failed = true;
+                output.step_failed(
+                    "outdated",
+                    "Checking",
+                    name,
+                    index + 1,
+                    size,
+                    &format!("Could not list versions of {name}: {e}"),
+                );
+            }
+        }
+    }
+
+    output.operation_completed(
+        "outdated",
+        if failed {
+            "Version check finished with errors."
+        } else {
+            "Checked dependency versions"
+        },
+    );
+
+    match flags.output_mode {
+        OutputMode::Json => {
+            for report in &reports {
+                println!(
+                    "{}",
+                    json!({
+                        "type": "dependency_versions",
+                        "dependency": report.name,
+                        "current": report.current,
+                        "compatible": report.compatible,
+                        "latest": report.latest,
+                        "outdated": report.is_outdated(),
+                    })
+                );
+            }
+        }
+        _ => {
+            for line in report_table(&reports) {
+                output.log(&line);
+            }
+        }
+    }
+
+    if failed {
+        exit(1)
+    }
+}
+
+fn locked_version<'a>(lockfile: &'a Lockfile, name: &str) -> Option<&'a str> {
+    lockfile.artifact_for_dependency(name)?.version()
+}
+
+fn available_versions(dependency: &Dependency) -> Result<Vec<String>, String> {
+    match dependency {
+        Dependency::FetchFromMaven {
+            repositories,
+            group_id,
+            artifact_id,
+            ..
+        } => maven::available_versions(repositories, group_id, artifact_id),
+        Dependency::FetchFromGithub {
+            username,
+            repository,
+            release_type,
+            ..
+        } => github::available_versions(username, repository, release_type),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Prereleases are only offered to dependencies which are already locked to one.
+fn version_report(name: &str, current: &str, versions: &[String]) -> VersionReport {
+    let candidates: Vec<&str> = versions
+        .iter()
+        .map(String::as_str)
+        .filter(|version| is_prerelease(current) || !is_prerelease(version))
+        .collect();
+    let newest = |matching: &dyn Fn(&str) -> bool| {
+        candidates
+            .iter()
+            .copied()
+            .filter(|version| matching(version))
+            .fold(current, |newest, version| {
+                match compare_versions(version, newest) {
+                    Ordering::Greater => version,
+                    _ => newest,
+                }
+            })
+            .to_string()
+    };
+
+    VersionReport {
+        name: name.to_string(),
+        current: current.to_string(),
+        compatible: newest(&|version| major(version) == major(current)),
+        latest: newest(&|_| true),
+    }
+}
+
+fn report_table(reports: &[VersionReport]) -> Vec<String> {
+    let outdated: Vec<&VersionReport> = reports.iter().filter(|r| r.is_outdated()).collect();
+    if outdated.is_empty() {
+        return vec![String::from("All checked dependencies are up to date.")];
+    }
+
+    let header = ["Dependency", "Current", "Compatible", "Latest"];
+    let rows: Vec<[&str; 4]> = outdated
+        .iter()
+        .map(|r| [&*r.name, &*r.current, &*r.compatible, &*r.latest])
+        .collect();
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            rows.iter()
+                .map(|row| row[column].len())
+                .chain([header[column].len()])
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    std::iter::once(header)
+        .chain(rows)
+        .map(|row| {
+            row.iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:<width$}"))
+                .collect::<Vec<String>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions(versions: &[&str]) -> Vec<String> {
+        versions.iter().map(|version| version.to_string()).collect()
+    }
+
+    #[test]
+    fn reports_newest_compatible_and_latest_versions() {
+        let report = version_report(
+            "library",
+            "1.2.0",
+            &versions(&[
+                "1.0.0",
+                "1.2.0",
+                "1.4.1",
+                "2.0.0-rc1",
+                "2.1.0",
+                "3.0.0-SNAPSHOT",
+            ]),
+        );
+
+        assert_eq!(report.compatible, "1.4.1");
+        assert_eq!(report.latest, "2.1.0");
+        assert!(report.is_outdated());
+    }
+
+    #[test]
+    fn offers_prereleases_only_to_prerelease_dependencies() {
+        let report = version_report(
+            "library",
+            "v2.0.0-beta1",
+            &versions(&["v1.9.0", "v2.0.0-rc1"]),
+        );
+
+        assert_eq!(report.compatible, "v2.0.0-rc1");
+        assert_eq!(report.latest, "v2.0.0-rc1");
+    }
+
+    #[test]
+    fn never_reports_versions_older_than_the_locked_one() {
+        let report = version_report("library", "1.2.0", &versions(&["1.0.0", "1.1.0"]));
+
+        assert_eq!(report.latest, "1.2.0");
+        assert!(!report.is_outdated());
+        assert_eq!(
+            report_table(&[report]),
+            vec!["All checked dependencies are up to date."]
+        );
+    }
+
+    #[test]
+    fn aligns_outdated_dependencies_into_columns() {
+        let report = version_report("gson", "2.10", &versions(&["2.10", "2.11.0"]));
+
+        assert_eq!(
+            report_table(&[report]),
+            vec![
+                "Dependency  Current  Compatible  Latest",
+                "gson        2.10     2.11.0      2.11.0",
+            ]
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use std::process::exit;
 
 use crate::cli::args::StartupFlags;
+use crate::cli::commands::dependencies::require_locked_project_or_exit;
 use crate::cli::commands::{configuration_or_exit, envvar_regexes, project_or_exit};
 use crate::model::{Metadata, Project};
 use crate::output;
@@ -9,6 +10,7 @@ use crate::workspace::refresh::refresh;
 /// `wisteria refresh`
 pub fn trigger_refresh(project: Result<Project, String>, flags: &StartupFlags) {
     let project: Project = project_or_exit(project);
+    require_locked_project_or_exit(&project);
     let mut output = output::renderer(flags.output_mode);
 
     let metadata = match Metadata::load() {
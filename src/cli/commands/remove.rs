@@ -0,0 +1,50 @@
+use std::process::exit;
+
+use crate::cli::args::StartupFlags;
+use crate::cli::commands::dependencies::duplicate_dependency_name;
+use crate::cli::commands::sync::trigger_sync;
+use crate::cli::commands::{align_versions, or_exit, project_file};
+use crate::model::Project;
+use crate::model::project_edit::ProjectEditor;
+
+pub fn trigger_remove(args: &[String], flags: &StartupFlags) {
+    if args.len() == 2 {
+        println!(
+            "Not enough arguments. Expected one or more dependency names.\nFix: run `wisteria remove <dependency>`."
+        );
+        exit(1)
+    }
+
+    if let Some(duplicate) = duplicate_dependency_name(&args[2..]) {
+        println!(
+            "Dependency `{duplicate}` was listed more than once.\nFix: list each dependency at most once."
+        );
+        exit(1)
+    }
+
+    let mut editor = or_exit(ProjectEditor::open(&project_file(flags)));
+    for name in &args[2..] {
+        let configurations = or_exit(editor.remove_dependency(name));
+        if configurations.is_empty() {
+            println!("Removed {name}.");
+        } else {
+            println!(
+                "Removed {name} and its references from [configuration.{}].",
+                configurations.join("], [configuration.")
+            );
+        }
+    }
+    or_exit(editor.save());
+
+    if flags.sync {
+        let args = [
+            String::from("wisteria"),
+            String::from("sync"),
+            String::from("all"),
+        ];
+        let project = Project::from_with_flags(flags.use_project.clone(), flags.clone());
+        trigger_sync(align_versions(project, true), &args, flags);
+    } else {
+        println!("Run `wisteria sync` to drop it from wisteria.lock.");
+    }
+}
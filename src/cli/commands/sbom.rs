@@ -0,0 +1,57 @@
+use std::process::exit;
+
+use crate::cli::args::StartupFlags;
+use crate::cli::commands::dependencies::read_lockfile_or_exit;
+use crate::cli::commands::{configuration_or_exit, envvar_regexes, project_or_exit};
+use crate::generators::sbom::{SbomFormat, generate_sbom, sbom_components};
+use crate::model::{Metadata, Project};
+use crate::output;
+
+/// Prints the SBOM to stdout, so it can be redirected into a file or piped into a scanner.
+pub fn trigger_sbom(project: Result<Project, String>, args: &[String], flags: &StartupFlags) {
+    let project: Project = project_or_exit(project);
+    let mut output = output::renderer(flags.output_mode);
+
+    let format = match &args[2..] {
+        [] => SbomFormat::default(),
+        [format] => match SbomFormat::load(format) {
+            Ok(format) => format,
+            Err(e) => {
+                output.log(&e);
+                exit(1)
+            }
+        },
+        _ => {
+            output.log(
+                "`wisteria sbom` describes the current configuration and only accepts a document format.\nFix: run `wisteria sbom [cyclonedx | spdx]`, and `wisteria switch <configuration>` to describe another configuration."
+            );
+            exit(1)
+        }
+    };
+
+    let metadata = match Metadata::load() {
+        Ok(m) => m,
+        Err(e) => {
+            output.log(&e);
+            exit(1)
+        }
+    };
+
+    let configuration = configuration_or_exit(&project, &metadata.configuration);
+    let lockfile = read_lockfile_or_exit();
+    let document = sbom_components(
+        &project,
+        configuration,
+        lockfile.as_ref(),
+        &envvar_regexes(),
+    )
+    .and_then(|components| generate_sbom(format, project.info(), &components));
+
+    match document {
+        Ok(document) => println!("{document}"),
+        Err(e) => {
+            output.log(&e);
+            exit(1)
+        }
+    }
+}
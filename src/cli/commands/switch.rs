@@ -1,6 +1,7 @@
 use std::{fs::write, process::exit};
 
 use crate::cli::args::StartupFlags;
+use crate::cli::commands::dependencies::require_locked_project_or_exit;
 use crate::cli::commands::{
     CommandOutput, configuration_or_exit, envvar_regexes, project_or_exit,
     update_dependencies_with_context,
@@ -15,6 +16,7 @@ use crate::workspace::refresh::refresh;
 
 pub fn trigger_switch(project: Result<Project, String>, args: &[String], flags: &StartupFlags) {
     let project: Project = project_or_exit(project);
+    require_locked_project_or_exit(&project);
     let mut output = output::renderer(flags.output_mode);
 
     let mut metadata = match Metadata::load() {
@@ -62,7 +64,7 @@ pub fn trigger_switch(project: Result<Project, String>, args: &[String], flags:
         let result = update_dependencies_with_context(
             CommandOutput::new(output.as_mut(), "switch"),
             &dependency_names,
-            project.dependencies(),
+            &project,
             configuration.environment(),
             &regexes,
             UpdateContext::SwitchConfiguration,
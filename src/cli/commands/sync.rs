@@ -2,12 +2,13 @@ use std::process::exit;
 
 use crate::cli::args::StartupFlags;
 use crate::cli::commands::dependencies::{
-    dependency_selection_or_exit, read_lockfile_or_exit, write_full_lockfile_artifacts_or_exit,
-    write_partial_lockfile_artifacts_or_exit,
+    dependency_selection_or_exit, merged_lockfile_artifacts, print_lockfile_changes,
+    read_lockfile_or_exit, write_full_lockfile_artifacts_or_exit,
 };
 use crate::cli::commands::{configuration_or_exit, envvar_regexes, project_or_exit};
 use crate::dependency::resolver::{ResolveContext, ResolvedDependency};
 use crate::dependency::{Dependency, UpdateContext};
+use crate::model::lockfile::diff_lockfile_artifacts;
 use crate::model::{Lockfile, LockfileArtifact, Metadata, Project};
 use crate::output::{self, OutputRenderer};
 use regex::Regex;
@@ -50,15 +51,21 @@ pub fn trigger_sync(project: Result<Project, String>, args: &[String], flags: &S
         exit(1)
     }
 
-    if selection.all_dependencies() {
-        write_full_lockfile_artifacts_or_exit(result.artifacts);
+    let artifacts = if selection.all_dependencies() {
+        result.artifacts
     } else {
-        write_partial_lockfile_artifacts_or_exit(
-            lockfile.as_ref(),
-            result.artifacts,
-            selection.names(),
-        );
-    }
+        merged_lockfile_artifacts(lockfile.as_ref(), result.artifacts, selection.names())
+    };
+    let changes = diff_lockfile_artifacts(
+        lockfile
+            .as_ref()
+            .map(Lockfile::artifacts)
+            .unwrap_or_default(),
+        &artifacts,
+    );
+
+    write_full_lockfile_artifacts_or_exit(artifacts);
+    print_lockfile_changes(output.as_mut(), flags.output_mode, &changes);
 }
 
 struct SyncResult {
@@ -157,12 +164,19 @@ fn sync_dependency_artifacts(
         ));
     }
 
-    let resolved = dependency.resolve(
-        name,
-        environment,
-        regexes,
-        ResolveContext::new(UpdateContext::ResolveOnly),
-    )?;
+    // A lock entry which only lacks attachments added with `attach` keeps its version, and the
+    // attachments are fetched beside it.
+    let relocked: Vec<&LockfileArtifact> = matching_artifacts
+        .iter()
+        .copied()
+        .filter(|artifact| dependency.matches_locked_coordinates(artifact))
+        .collect();
+    let context = match relocked.as_slice() {
+        [locked] => ResolveContext::with_locked_artifact(UpdateContext::ResolveOnly, locked),
+        _ => ResolveContext::new(UpdateContext::ResolveOnly),
+    };
+
+    let resolved = dependency.resolve(name, environment, regexes, context)?;
 
     let artifacts = lockfile_artifacts_from_resolved(resolved);
     if artifacts.is_empty() {
@@ -2,6 +2,7 @@ use std::process::exit;
 
 use crate::build::task::TaskOutput;
 use crate::cli::args::StartupFlags;
+use crate::cli::commands::dependencies::require_locked_project_or_exit;
 use crate::cli::commands::{configuration_or_exit, project_or_exit};
 use crate::model::{Configuration, Metadata, Project};
 use crate::output;
@@ -9,6 +10,7 @@ use crate::util::exit_code;
 
 pub fn trigger_task(project: Result<Project, String>, args: &[String], flags: &StartupFlags) {
     let project: Project = project_or_exit(project);
+    require_locked_project_or_exit(&project);
 
     let metadata = match Metadata::load() {
         Ok(m) => m,
@@ -56,6 +58,9 @@ fn task_summary(task: &str) -> String {
         "build" => String::from("Built project"),
         "javadocs" | "javadoc" => String::from("Generated javadocs"),
         "run" => String::from("Finished run task"),
+        "shell" => String::from("Closed shell"),
+        "image" => String::from("Built runtime image"),
+        "distribution" => String::from("Built distribution"),
         task => format!("Completed task \"{task}\""),
     }
 }
@@ -0,0 +1,482 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::exit,
+};
+
+use regex::Regex;
+use serde_json::json;
+
+use crate::cli::args::StartupFlags;
+use crate::cli::commands::dependencies::read_lockfile_or_exit;
+use crate::cli::commands::{configuration_or_exit, envvar_regexes, project_or_exit};
+use crate::dependency::resolver::ResolveContext;
+use crate::dependency::{Dependency, DependencyReference, UpdateContext};
+use crate::model::{Configuration, Lockfile, LockfileArtifact, Metadata, Project};
+use crate::output::{self, OutputMode, OutputRenderer};
+
+/// A configuration's reference to a dependency, with the jars it resolved to from the lockfile and
+/// cache.
+struct TreeNode<'a> {
+    reference: &'a DependencyReference,
+    version: Option<String>,
+    paths: Vec<PathBuf>,
+    error: Option<String>,
+}
+
+impl TreeNode<'_> {
+    fn label(&self) -> String {
+        let mut label = self.reference.name().to_string();
+        if let Some(version) = &self.version {
+            label.push_str(&format!(" {version}"));
+        }
+
+        label.push_str(&format!(" [{}", self.reference.scope()));
+        if let Some(packaging) = self.reference.packaging() {
+            label.push_str(&format!(", {packaging}"));
+        }
+        label.push(']');
+        label
+    }
+
+    /// The path of a jar from this node matching `artifact` by path or file name.
+    fn provided_path(&self, artifact: &str) -> Option<&PathBuf> {
+        self.paths.iter().find(|path| {
+            path.to_string_lossy() == artifact
+                || path.file_name().is_some_and(|name| name == artifact)
+        })
+    }
+}
+
+pub fn trigger_tree(project: Result<Project, String>, args: &[String], flags: &StartupFlags) {
+    let project: Project = project_or_exit(project);
+    let mut output = output::renderer(flags.output_mode);
+
+    if args.len() > 2 {
+        output.log(
+            "`wisteria tree` shows the current configuration and does not accept arguments.\nFix: run `wisteria switch <configuration>` to inspect another configuration, or `wisteria tree --why <dependency>` to explain one dependency."
+        );
+        exit(1)
+    }
+
+    let regexes = envvar_regexes();
+    let lockfile = read_lockfile_or_exit();
+
+    if let Some(artifact) = &flags.why {
+        explain_dependency(
+            output.as_mut(),
+            flags.output_mode,
+            &project,
+            artifact,
+            &regexes,
+            lockfile.as_ref(),
+        );
+        return;
+    }
+
+    let metadata = match Metadata::load() {
+        Ok(m) => m,
+        Err(e) => {
+            output.log(&e);
+            exit(1)
+        }
+    };
+
+    let configuration = configuration_or_exit(&project, &metadata.configuration);
+    let nodes = resolve_tree(&project, configuration, &regexes, lockfile.as_ref());
+
+    match flags.output_mode {
+        OutputMode::Json => {
+            for node in &nodes {
+                println!(
+                    "{}",
+                    json!({
+                        "type": "dependency_reference",
+                        "configuration": metadata.configuration,
+                        "dependency": node.reference.name(),
+                        "scope": node.reference.scope().to_string(),
+                        "packaging": node.reference.packaging().map(|p| p.to_string()),
+                        "version": node.version,
+                        "paths": node.paths,
+                        "error": node.error,
+                    })
+                );
+            }
+        }
+        _ => {
+            for line in tree_lines(&metadata.configuration, &nodes) {
+                output.log(&line);
+            }
+        }
+    }
+}
+
+/// Lists every reference, in any configuration, which pulls in `artifact`, given either as a
+/// dependency name or as the path or file name of one of its jars.
+fn explain_dependency(
+    output: &mut dyn OutputRenderer,
+    mode: OutputMode,
+    project: &Project,
+    artifact: &str,
+    regexes: &HashMap<&str, Regex>,
+    lockfile: Option<&Lockfile>,
+) {
+    let mut configuration_names: Vec<&String> = project.info().configurations().keys().collect();
+    configuration_names.sort_unstable();
+
+    let mut found = false;
+    for configuration_name in configuration_names {
+        let configuration = &project.info().configurations()[configuration_name];
+        for node in resolve_tree(project, configuration, regexes, lockfile) {
+            let provided_path = node.provided_path(artifact);
+            if node.reference.name() != artifact && provided_path.is_none() {
+                continue;
+            }
+
+            found = true;
+            let placements = placements(node.reference);
+            match mode {
+                OutputMode::Json => println!(
+                    "{}",
+                    json!({
+                        "type": "dependency_why",
+                        "configuration": configuration_name,
+                        "dependency": node.reference.name(),
+                        "artifact": provided_path,
+                        "scope": node.reference.scope().to_string(),
+                        "packaging": node.reference.packaging().map(|p| p.to_string()),
+                        "placements": placements,
+                    })
+                ),
+                _ => {
+                    let provides = match provided_path {
+                        Some(path) => format!(" provides {}", path.display()),
+                        None => String::new(),
+                    };
+                    output.log(&format!(
+                        "[configuration.{configuration_name}] {}{provides}: {}",
+                        node.label(),
+                        placements.join(", ")
+                    ));
+                }
+            }
+        }
+    }
+
+    if !found {
+        output.log(&format!(
+            "No configuration references \"{artifact}\".\nFix: pass a dependency name from project.toml, or the file name of one of its jars."
+        ));
+        exit(1)
+    }
+}
+
+/// Looks up each reference of the configuration without touching the network or the cache:
+/// lockable dependencies are read from the lockfile and their cache paths, and local files and
+/// folders are resolved in place.
+fn resolve_tree<'a>(
+    project: &Project,
+    configuration: &'a Configuration,
+    regexes: &HashMap<&str, Regex>,
+    lockfile: Option<&Lockfile>,
+) -> Vec<TreeNode<'a>> {
+    let Some(references) = configuration.dependencies() else {
+        return Vec::new();
+    };
+
+    references
+        .iter()
+        .map(|reference| {
+            let name = reference.name();
+            let Some(dependency) = project.dependencies().get(name) else {
+                return TreeNode {
+                    reference,
+                    version: lockfile
+                        .and_then(|lockfile| lockfile.artifact_for_dependency(name))
+                        .and_then(|artifact| artifact.version())
+                        .map(String::from),
+                    paths: Vec::new(),
+                    error: Some(String::from("dependency is not declared in [dependencies]")),
+                };
+            };
+
+            if dependency.is_bom() {
+                return TreeNode {
+                    reference,
+                    version: None,
+                    paths: Vec::new(),
+                    error: None,
+                };
+            }
+
+            if dependency.lockfile_source().is_some() {
+                return locked_node(reference, dependency, lockfile);
+            }
+
+            match dependency.resolve(
+                name,
+                configuration.environment(),
+                regexes,
+                ResolveContext::for_dependency(UpdateContext::ResolveOnly, lockfile, name),
+            ) {
+                Ok(resolved) => TreeNode {
+                    reference,
+                    version: None,
+                    paths: resolved.paths().cloned().collect(),
+                    error: None,
+                },
+                Err(error) => TreeNode {
+                    reference,
+                    version: None,
+                    paths: Vec::new(),
+                    error: Some(error),
+                },
+            }
+        })
+        .collect()
+}
+
+/// The lock entries of `dependency` and their cached jars.
+fn locked_node<'a>(
+    reference: &'a DependencyReference,
+    dependency: &Dependency,
+    lockfile: Option<&Lockfile>,
+) -> TreeNode<'a> {
+    let artifacts: Vec<&LockfileArtifact> = lockfile
+        .map(Lockfile::artifacts)
+        .unwrap_or_default()
+        .iter()
+        .filter(|artifact| {
+            artifact.name() == reference.name() && dependency.matches_locked_coordinates(artifact)
+        })
+        .collect();
+    let version = artifacts
+        .iter()
+        .find_map(|artifact| artifact.version())
+        .map(String::from);
+
+    let error = if artifacts.is_empty() {
+        Some(String::from(
+            "not locked in wisteria.lock\nFix: run `wisteria sync` to lock it.",
+        ))
+    } else {
+        artifacts
+            .iter()
+            .find(|artifact| !Path::new(artifact.cache_path()).exists())
+            .map(|artifact| {
+                format!(
+                    "not cached at {}\nFix: run `wisteria fetch` to download locked artifacts.",
+                    artifact.cache_path()
+                )
+            })
+    };
+
+    TreeNode {
+        reference,
+        version,
+        paths: artifacts
+            .iter()
+            .map(|artifact| PathBuf::from(artifact.cache_path()))
+            .collect(),
+        error,
+    }
+}
+
+/// Where a reference ends up, following the same rules as the build.
+fn placements(reference: &DependencyReference) -> Vec<&'static str> {
+    let scope = reference.scope();
+    if scope.is_test_only() {
+        return vec!["test only"];
+    }
+
+    let mut placements = Vec::new();
+    if scope.is_on_compile_classpath() {
+        placements.push("compile classpath");
+    }
+    if scope.is_on_runtime_classpath() && reference.packaging().is_none() {
+        placements.push("runtime classpath");
+    }
+    if reference.is_shaded() {
+        placements.push("shaded into the target jar");
+    }
+    if reference.is_nested() {
+        placements.push("nested in the target jar");
+    }
+    placements
+}
+
+fn tree_lines(configuration_name: &str, nodes: &[TreeNode<'_>]) -> Vec<String> {
+    let mut lines = vec![format!("[configuration.{configuration_name}]")];
+    if nodes.is_empty() {
+        lines.push(String::from("└── (no dependencies)"));
+        return lines;
+    }
+
+    for (index, node) in nodes.iter().enumerate() {
+        let last = index + 1 == nodes.len();
+        let (branch, indent) = if last {
+            ("└── ", "    ")
+        } else {
+            ("├── ", "│   ")
+        };
+        lines.push(format!("{branch}{}", node.label()));
+
+        let children: Vec<String> = match &node.error {
+            Some(error) => vec![format!(
+                "not resolved: {}",
+                error.lines().next().unwrap_or("")
+            )],
+            None => node
+                .paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect(),
+        };
+        for (child_index, child) in children.iter().enumerate() {
+            let child_branch = if child_index + 1 == children.len() {
+                "└── "
+            } else {
+                "├── "
+            };
+            lines.push(format!("{indent}{child_branch}{child}"));
+        }
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::dependency::{DependencyScope, PackagingType};
+    use crate::test_support::{TempDir, with_current_dir};
+
+    #[test]
+    fn places_references_like_the_build() {
+        let reference =
+            |scope, packaging| DependencyReference::new(String::from("a"), scope, packaging);
+
+        assert_eq!(
+            placements(&reference(DependencyScope::Compile, None)),
+            vec!["compile classpath", "runtime classpath"]
+        );
+        assert_eq!(
+            placements(&reference(
+                DependencyScope::Compile,
+                Some(PackagingType::Shade)
+            )),
+            vec!["compile classpath", "shaded into the target jar"]
+        );
+        assert_eq!(
+            placements(&reference(DependencyScope::Test, None)),
+            vec!["test only"]
+        );
+    }
+
+    #[test]
+    fn draws_local_folder_jars_as_children() {
+        let temp = TempDir::new("tree-folder");
+        fs::create_dir_all(temp.path().join("lib")).unwrap();
+        fs::write(temp.path().join("lib/a.jar"), "").unwrap();
+        let project_file = temp.path().join("project.toml");
+        fs::write(
+            &project_file,
+            r#"
+            [project]
+            name = "Demo"
+            version = "1.0.0"
+            description = "Demo project"
+
+            [dependencies.folder]
+            libs = { path = "lib/" }
+
+            [dependencies.archive]
+            missing-jar = { path = "missing.jar" }
+
+            [configuration.main]
+            sources = [ "src" ]
+            dependencies = [ { name = "libs", scope = "runtime" }, "missing-jar" ]
+            "#,
+        )
+        .unwrap();
+
+        with_current_dir(temp.path(), || {
+            let project = Project::from(Some(project_file.to_string_lossy().to_string())).unwrap();
+            let configuration = &project.info().configurations()["main"];
+            let nodes = resolve_tree(&project, configuration, &envvar_regexes(), None);
+            let lines = tree_lines("main", &nodes);
+
+            assert_eq!(lines[0], "[configuration.main]");
+            assert_eq!(lines[1], "├── libs [runtime]");
+            assert_eq!(lines[2], "│   └── lib/a.jar");
+            assert_eq!(lines[3], "└── missing-jar [compile]");
+            assert!(lines[4].starts_with("    └── not resolved: "));
+            assert_eq!(
+                nodes[0].provided_path("a.jar"),
+                Some(&PathBuf::from("lib/a.jar"))
+            );
+        });
+    }
+
+    #[test]
+    fn shows_locked_artifacts_without_resolving_them() {
+        let temp = TempDir::new("tree-locked");
+        let project_file = temp.path().join("project.toml");
+        fs::write(
+            &project_file,
+            r#"
+            [project]
+            name = "Demo"
+            version = "1.0.0"
+            description = "Demo project"
+
+            [dependencies.url]
+            cached = { url = "https://example.invalid/cached.jar" }
+            missing = { url = "https://example.invalid/missing.jar" }
+            unlocked = { url = "https://example.invalid/unlocked.jar" }
+
+            [configuration.main]
+            sources = [ "src" ]
+            dependencies = [ "cached", "missing", "unlocked" ]
+            "#,
+        )
+        .unwrap();
+        let artifact = |name: &str| {
+            LockfileArtifact::new(
+                String::from(name),
+                String::from("url"),
+                Some(String::from("1.0")),
+                format!("https://example.invalid/{name}.jar"),
+                format!(".wisteria/cache/{name}.jar"),
+                String::from("hash"),
+            )
+        };
+        let lockfile =
+            Lockfile::from_artifacts_for_test(vec![artifact("cached"), artifact("missing")]);
+
+        with_current_dir(temp.path(), || {
+            fs::create_dir_all(".wisteria/cache").unwrap();
+            fs::write(".wisteria/cache/cached.jar", "").unwrap();
+            let project = Project::from(Some(project_file.to_string_lossy().to_string())).unwrap();
+            let configuration = &project.info().configurations()["main"];
+            let nodes = resolve_tree(&project, configuration, &envvar_regexes(), Some(&lockfile));
+            let lines = tree_lines("main", &nodes);
+
+            assert_eq!(lines[1], "├── cached 1.0 [compile]");
+            assert_eq!(lines[2], "│   └── .wisteria/cache/cached.jar");
+            assert_eq!(lines[3], "├── missing 1.0 [compile]");
+            assert_eq!(
+                lines[4],
+                "│   └── not resolved: not cached at .wisteria/cache/missing.jar"
+            );
+            assert_eq!(lines[5], "└── unlocked [compile]");
+            assert_eq!(
+                lines[6],
+                "    └── not resolved: not locked in wisteria.lock"
+            );
+            assert!(!Path::new(".wisteria/cache/missing.jar").exists());
+        });
+    }
+}
@@ -2,21 +2,32 @@ use std::process::exit;
 
 use crate::cli::args::StartupFlags;
 use crate::cli::commands::dependencies::{
-    dependency_selection_or_exit, read_lockfile_or_exit, write_full_lockfile_or_exit,
-    write_partial_lockfile_or_exit,
+    DependencySelection, dependency_selection_or_exit, merged_lockfile_artifacts,
+    print_lockfile_changes, read_lockfile_or_exit, require_locked_project_or_exit,
+    write_full_lockfile_artifacts_or_exit,
 };
 use crate::cli::commands::{
     CommandOutput, configuration_or_exit, envvar_regexes, project_or_exit,
     update_dependencies_with_context,
 };
-use crate::dependency::UpdateContext;
-use crate::model::{Metadata, Project};
-use crate::output;
-use crate::workspace::refresh::refresh;
+use crate::dependency::resolver::ResolveContext;
+use crate::dependency::{UpdateContext, policy};
+use crate::model::lockfile::{LockfileChange, diff_lockfile_artifacts, lockable_artifacts};
+use crate::model::{Lockfile, LockfileArtifact, Metadata, Project};
+use crate::output::{self, OutputMode, OutputRenderer};
+use crate::workspace::{download, refresh::refresh};
 
 pub fn trigger_update(project: Result<Project, String>, args: &[String], flags: &StartupFlags) {
     let project: Project = project_or_exit(project);
 
+    if download::offline() {
+        println!(
+            "Cannot update dependencies while offline, because new versions can only be resolved over the network.\nFix: run `wisteria update` again once you are online, without `--offline` or `--frozen`."
+        );
+        exit(1)
+    }
+    require_locked_project_or_exit(&project);
+
     let metadata = match Metadata::load() {
         Ok(m) => m,
         Err(e) => {
@@ -32,10 +43,21 @@ pub fn trigger_update(project: Result<Project, String>, args: &[String], flags:
     let selection = dependency_selection_or_exit(&project, args, "update", false);
     let mut output = output::renderer(flags.output_mode);
 
+    if flags.dry_run {
+        print_version_changes(
+            &project,
+            &selection,
+            lockfile.as_ref(),
+            output.as_mut(),
+            flags.output_mode,
+        );
+        return;
+    }
+
     let result = update_dependencies_with_context(
         CommandOutput::new(output.as_mut(), "update"),
         selection.names(),
-        project.dependencies(),
+        &project,
         configuration.environment(),
         &regexes,
         UpdateContext::Update,
@@ -51,10 +73,32 @@ pub fn trigger_update(project: Result<Project, String>, args: &[String], flags:
         exit(1)
     }
 
-    if selection.all_dependencies() {
-        write_full_lockfile_or_exit(&result.resolved);
+    let resolved_artifacts = lockable_artifacts(&result.resolved);
+    let artifacts = if selection.all_dependencies() {
+        resolved_artifacts
+    } else {
+        merged_lockfile_artifacts(lockfile.as_ref(), resolved_artifacts, selection.names())
+    };
+    let changes = diff_lockfile_artifacts(
+        lockfile
+            .as_ref()
+            .map(Lockfile::artifacts)
+            .unwrap_or_default(),
+        &artifacts,
+    );
+
+    if policy::locked() {
+        if !changes.is_empty() {
+            let names: Vec<&str> = changes.iter().map(LockfileChange::name).collect();
+            output.log(&format!(
+                "Updating would change wisteria.lock for {}, which is not allowed with --locked.\nFix: run `wisteria update` without --locked and commit the new lockfile.",
+                names.join(", ")
+            ));
+            exit(1)
+        }
     } else {
-        write_partial_lockfile_or_exit(lockfile.as_ref(), &result.resolved, selection.names());
+        write_full_lockfile_artifacts_or_exit(artifacts);
+        print_lockfile_changes(output.as_mut(), flags.output_mode, &changes);
     }
 
     if !flags.no_refresh
@@ -76,3 +120,68 @@ pub fn trigger_update(project: Result<Project, String>, args: &[String], flags:
         exit(1)
     }
 }
+
+/// Compares locked versions with the versions an update would resolve, which only needs repository
+/// metadata, so nothing is downloaded and no cache is touched. Hashes are only known once an
+/// artifact is downloaded, so predicted entries carry no hash and only version changes are shown.
+fn print_version_changes(
+    project: &Project,
+    selection: &DependencySelection,
+    lockfile: Option<&Lockfile>,
+    output: &mut dyn OutputRenderer,
+    mode: OutputMode,
+) {
+    let locked = lockfile.map(Lockfile::artifacts).unwrap_or_default();
+    let mut predicted: Vec<LockfileArtifact> = Vec::new();
+    let mut failed: Vec<(&str, String)> = Vec::new();
+    for name in selection.names() {
+        let Some(dependency) = project.dependencies().get(name) else {
+            continue;
+        };
+        let Some(source) = dependency.lockfile_source() else {
+            continue;
+        };
+
+        let context = ResolveContext::for_dependency(UpdateContext::Update, lockfile, name);
+        let old = context.locked_artifact().map(LockfileArtifact::version);
+        match dependency.resolve_version(context) {
+            Ok(new) if old == Some(new.as_deref()) => predicted.extend(
+                locked
+                    .iter()
+                    .filter(|artifact| artifact.name() == name)
+                    .cloned(),
+            ),
+            Ok(new) => predicted.push(LockfileArtifact::new(
+                name.clone(),
+                String::from(source),
+                new,
+                String::new(),
+                String::new(),
+                String::new(),
+            )),
+            Err(error) => failed.push((name, error)),
+        }
+    }
+
+    if !failed.is_empty() {
+        output.log("Failed to resolve one or more dependencies:");
+        for (name, reason) in &failed {
+            output.log(&format!("\t{name}: {reason}"));
+        }
+        exit(1)
+    }
+
+    let artifacts = if selection.all_dependencies() {
+        predicted
+    } else {
+        merged_lockfile_artifacts(lockfile, predicted, selection.names())
+    };
+    let changes = diff_lockfile_artifacts(locked, &artifacts);
+
+    if changes.is_empty() {
+        output.log("Dry run: no locked version would change.");
+    } else {
+        output.log("Dry run: locked versions would change as follows:");
+    }
+    print_lockfile_changes(output, mode, &changes);
+}
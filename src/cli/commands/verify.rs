@@ -1,6 +1,6 @@
 use std::{
     collections::{BTreeMap, HashMap},
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::exit,
 };
 
@@ -11,7 +11,7 @@ use crate::dependency::Dependency;
 use crate::model::{Lockfile, LockfileArtifact, Project};
 use crate::output;
 use crate::util::consts;
-use crate::workspace::files;
+use crate::workspace::{files, signature};
 
 pub fn trigger_verify(project: Result<Project, String>, args: &[String], flags: &StartupFlags) {
     let mut output = output::renderer(flags.output_mode);
@@ -53,11 +53,29 @@ fn issue_label(count: usize) -> &'static str {
 }
 
 fn verify_project_lockfile(project: &Project, lockfile: &Lockfile) -> Vec<String> {
+    check_project_lockfile(project, lockfile, true)
+}
+
+/// Ways project.toml and the lockfile disagree, without looking at the cache.
+pub(crate) fn lockfile_disagreements(project: &Project, lockfile: &Lockfile) -> Vec<String> {
+    check_project_lockfile(project, lockfile, false)
+}
+
+fn check_project_lockfile(
+    project: &Project,
+    lockfile: &Lockfile,
+    check_cache: bool,
+) -> Vec<String> {
     let mut issues = Vec::new();
     let artifacts_by_name = artifacts_by_name(lockfile);
 
     verify_lockable_project_dependencies(project, &artifacts_by_name, &mut issues);
-    verify_locked_artifacts(project.dependencies(), &artifacts_by_name, &mut issues);
+    verify_locked_artifacts(
+        project.dependencies(),
+        &artifacts_by_name,
+        check_cache,
+        &mut issues,
+    );
 
     issues
 }
@@ -85,6 +103,7 @@ fn verify_lockable_project_dependencies(
 fn verify_locked_artifacts(
     dependencies: &HashMap<String, Dependency>,
     artifacts_by_name: &BTreeMap<&str, Vec<&LockfileArtifact>>,
+    check_cache: bool,
     issues: &mut Vec<String>,
 ) {
     for (name, artifacts) in artifacts_by_name {
@@ -115,23 +134,31 @@ fn verify_locked_artifacts(
         }
 
         for artifact in artifacts {
-            verify_artifact(name, dependency, artifact, issues);
+            if dependency.matches_locked_coordinates(artifact)
+                && !dependency.matches_lockfile_artifact(artifact)
+            {
+                issues.push(format!(
+                    "Lockfile artifact `{name}` does not record the attachments requested with `attach` in project.toml.\n  Fix: run `wisteria sync {name}` to fetch the attachments and lock them."
+                ));
+            } else if !dependency.matches_lockfile_artifact(artifact) {
+                issues.push(format!(
+                    "Lockfile artifact `{name}` no longer matches the dependency declared in project.toml.\n  Fix: run `wisteria sync {name}` if the matching artifact is already cached, or `wisteria update {name}` to resolve, download, and lock the current dependency."
+                ));
+            }
+
+            if check_cache {
+                verify_cached_artifact(name, dependency, artifact, issues);
+            }
         }
     }
 }
 
-fn verify_artifact(
+fn verify_cached_artifact(
     name: &str,
     dependency: &Dependency,
     artifact: &LockfileArtifact,
     issues: &mut Vec<String>,
 ) {
-    if !dependency.matches_lockfile_artifact(artifact) {
-        issues.push(format!(
-            "Lockfile artifact `{name}` no longer matches the dependency declared in project.toml.\n  Fix: run `wisteria sync {name}` if the matching artifact is already cached, or `wisteria update {name}` to resolve, download, and lock the current dependency."
-        ));
-    }
-
     let cache_path = PathBuf::from(artifact.cache_path());
     if !cache_path.exists() {
         issues.push(format!(
@@ -153,6 +180,46 @@ fn verify_artifact(
             artifact.cache_path()
         )),
     }
+
+    for attachment in artifact.attachments() {
+        if !Path::new(attachment.cache_path()).exists() {
+            issues.push(format!(
+                "The {} jar of locked dependency `{name}` is missing from the cache at `{}`.\n  Fix: run `wisteria fetch {name}` to download the locked attachments.",
+                attachment.kind(),
+                attachment.cache_path()
+            ));
+        }
+    }
+
+    verify_artifact_signature(name, dependency, artifact, issues);
+}
+
+fn verify_artifact_signature(
+    name: &str,
+    dependency: &Dependency,
+    artifact: &LockfileArtifact,
+    issues: &mut Vec<String>,
+) {
+    let Some(signing_key) = artifact.signing_key() else {
+        if dependency.requires_signature() {
+            issues.push(format!(
+                "Dependency `{name}` requires a verified signature, but `{}` does not record a signing key.\n  Fix: run `wisteria update {name}` to verify its signature and lock the signing key.",
+                consts::LOCKFILE
+            ));
+        }
+        return;
+    };
+
+    match signature::verify_cached_signature(artifact.cache_path()) {
+        Ok(fingerprint) if fingerprint == signing_key => {}
+        Ok(fingerprint) => issues.push(format!(
+            "Cached dependency `{name}` is signed by key {fingerprint}, but `{}` records key {signing_key}.\n  Fix: find out why the signer changed, then run `wisteria update {name}` to lock the new key.",
+            consts::LOCKFILE
+        )),
+        Err(error) => issues.push(format!(
+            "Could not verify the signature of cached dependency `{name}`: {error}"
+        )),
+    }
 }
 
 fn artifacts_by_name(lockfile: &Lockfile) -> BTreeMap<&str, Vec<&LockfileArtifact>> {
@@ -268,6 +335,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn lockfile_disagreements_ignore_the_cache() {
+        let temp = TempDir::new("verify-disagreements");
+
+        with_current_dir(temp.path(), || {
+            let project = url_project(&temp);
+            let matching = Lockfile::from_artifacts_for_test(vec![locked_artifact(
+                "library",
+                String::from("hash"),
+            )]);
+            let stale = Lockfile::from_artifacts_for_test(vec![locked_artifact(
+                "stale",
+                String::from("hash"),
+            )]);
+
+            assert!(lockfile_disagreements(&project, &matching).is_empty());
+            assert_eq!(lockfile_disagreements(&project, &stale).len(), 2);
+        });
+    }
+
     #[test]
     fn verify_rejects_cache_hash_mismatch() {
         let temp = TempDir::new("verify-hash-mismatch");
@@ -289,6 +376,43 @@ mod tests {
         });
     }
 
+    #[test]
+    fn verify_rejects_signed_dependency_locked_without_signing_key() {
+        let temp = TempDir::new("verify-unsigned-lock");
+
+        with_current_dir(temp.path(), || {
+            let project = project_from_toml(
+                &temp,
+                r#"
+                [project]
+                name = "Demo"
+                version = "1.0.0"
+                description = "Demo project"
+
+                [dependencies.maven]
+                library = { url = "https://repo.example", group_id = "com.example", artifact_id = "library", verify_signature = true }
+                "#,
+            );
+            let cache_path = ".wisteria/cache/com.example/library/1.0.0/library.jar";
+            fs::create_dir_all(".wisteria/cache/com.example/library/1.0.0").unwrap();
+            fs::write(cache_path, "cached").unwrap();
+            let hash = files::generate_sha2_for_file(&PathBuf::from(cache_path)).unwrap();
+            let lockfile = Lockfile::from_artifacts_for_test(vec![LockfileArtifact::new(
+                String::from("library"),
+                String::from("maven"),
+                Some(String::from("1.0.0")),
+                String::from("https://repo.example/com/example/library/1.0.0/library-1.0.0.jar"),
+                String::from(cache_path),
+                hash,
+            )]);
+
+            let issues = verify_project_lockfile(&project, &lockfile);
+
+            assert_eq!(issues.len(), 1);
+            assert!(issues[0].contains("requires a verified signature"));
+        });
+    }
+
     #[test]
     fn verify_rejects_lockfile_artifact_that_no_longer_matches_project_dependency() {
         let temp = TempDir::new("verify-dependency-drift");
@@ -1,6 +1,13 @@
 use std::process::exit;
 
+use crate::dependency::policy;
 use crate::model::Project;
+use crate::workspace::{
+    credentials,
+    download::{self, DownloadSettings},
+    global_cache,
+    settings::{self, UserSettings},
+};
 
 pub mod args;
 pub mod commands;
@@ -10,6 +17,21 @@ pub fn run() {
     let flags = args::load_arguments(&mut args);
     let command = args[1].to_lowercase();
 
+    if flags.dry_run && command != "update" {
+        println!(
+            "The --dry-run flag only applies to `wisteria update`, not `wisteria {command}`.\nFix: run `wisteria update --dry-run` to preview lockfile changes, or drop the flag."
+        );
+        exit(1)
+    }
+
+    match UserSettings::load() {
+        Ok(loaded) => settings::configure(loaded),
+        Err(e) => {
+            println!("{e}");
+            exit(1)
+        }
+    }
+
     match command.as_str() {
         "migrate" => commands::migrate::trigger_migrate(&args, &flags),
         "migrate2" => commands::migrate::trigger_migrate2(&flags),
@@ -18,12 +40,48 @@ pub fn run() {
 
     let project: Result<Project, String> =
         Project::from_with_flags(flags.use_project.clone(), flags.clone());
+    let download_settings = match &project {
+        Ok(project) => project.info().download_settings(),
+        Err(_) => DownloadSettings::default(),
+    };
+    policy::configure_locked(flags.locked);
+    download::configure(DownloadSettings {
+        offline: flags.offline || settings::settings().offline(),
+        ..download_settings
+    });
+
+    if let Ok(project) = &project {
+        global_cache::configure(project.info().cache_mode());
+
+        match credentials::load(project.repositories()) {
+            Ok(loaded) => credentials::configure(loaded),
+            Err(e) => {
+                println!("{e}");
+                exit(1)
+            }
+        }
+    }
+
+    // BOM versions are only needed by commands which resolve Maven versions. Commands which only
+    // look at the lockfile and cache read the cached BOMs without reaching the network.
+    let project = match command.as_str() {
+        "fetch" | "audit" | "licenses" | "add" | "remove" | "clean" | "new" | "create" => project,
+        "info" | "verify" | "sync" | "sbom" | "tree" => commands::align_versions(project, true),
+        _ => commands::align_versions(project, download::offline() || policy::locked()),
+    };
 
     match command.as_str() {
         "refresh" => commands::refresh::trigger_refresh(project, &flags),
         "sync" => commands::sync::trigger_sync(project, &args, &flags),
         "fetch" => commands::fetch::trigger_fetch(project, &args, &flags),
         "verify" => commands::verify::trigger_verify(project, &args, &flags),
+        "outdated" => commands::outdated::trigger_outdated(project, &args, &flags),
+        "tree" => commands::tree::trigger_tree(project, &args, &flags),
+        "audit" => commands::audit::trigger_audit(project, &args, &flags),
+        "licenses" => commands::licenses::trigger_licenses(project, &args, &flags),
+        "sbom" => commands::sbom::trigger_sbom(project, &args, &flags),
+        "add" => commands::add::trigger_add(&args, &flags),
+        "remove" => commands::remove::trigger_remove(&args, &flags),
         "update" if args.len() == 2 => {
             println!(
                 "Not enough arguments. Expected at least one argument, but none were supplied."
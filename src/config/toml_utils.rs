@@ -23,14 +23,21 @@ pub fn read_optional_string(
 }
 
 pub fn read_boolean(key: &str, toml: &Map<String, Value>) -> Result<bool, String> {
+    match read_optional_boolean(key, toml)? {
+        Some(value) => Ok(value),
+        None => Err(missing_key_message(key, value_hint(key, "a boolean"))),
+    }
+}
+
+pub fn read_optional_boolean(key: &str, toml: &Map<String, Value>) -> Result<Option<bool>, String> {
     match toml.get(key) {
-        Some(v) if v.is_bool() => Ok(v.as_bool().unwrap()),
+        Some(v) if v.is_bool() => Ok(Some(v.as_bool().unwrap())),
         Some(v) => Err(format!(
             "Mismatched type for \"{key}\", expected a boolean, found {}. {}",
             v.type_str(),
             value_hint(key, "a boolean")
         )),
-        None => Err(missing_key_message(key, value_hint(key, "a boolean"))),
+        None => Ok(None),
     }
 }
 
@@ -104,6 +111,21 @@ pub fn read_optional_string_array(
     }
 }
 
+pub fn read_optional_table<'a>(
+    key: &str,
+    toml: &'a Map<String, Value>,
+) -> Result<Option<&'a Map<String, Value>>, String> {
+    match toml.get(key) {
+        Some(v) if v.is_table() => Ok(v.as_table()),
+        Some(v) => Err(format!(
+            "Mismatched type for \"{key}\", expected a table, found {}. {}",
+            v.type_str(),
+            value_hint(key, "a table")
+        )),
+        None => Ok(None),
+    }
+}
+
 pub fn string_vec_to_string(data: &Vec<String>) -> String {
     let mut string: String = String::new();
 
@@ -148,6 +170,18 @@ fn value_hint(key: &str, expected: &str) -> String {
             String::from("Fix: use a numeric Java release, for example `java_version = 17`.")
         }
         "inherit" => String::from("Fix: use `inherit = \"base-configuration\"` or remove the key."),
+        "concurrency" => String::from(
+            "Fix: use a whole number such as `concurrency = 4`, or remove the key to use the default.",
+        ),
+        "connect_timeout" | "read_timeout" => {
+            format!("Fix: use a number of seconds such as `{key} = 30`, or remove the key.")
+        }
+        "retries" => String::from(
+            "Fix: use a whole number such as `retries = 3`, or `retries = 0` to disable retries.",
+        ),
+        "copy_libraries" => String::from(
+            "Fix: use `copy_libraries = true` to copy runtime jars into `lib/` beside each target, or remove the key.",
+        ),
         "update_policy" => String::from(
             "Fix: use a supported update policy such as `SwitchOrUpdate`, `TaskOrUpdate`, or `Never`.",
         ),
@@ -160,6 +194,9 @@ fn value_hint(key: &str, expected: &str) -> String {
         "repository" => String::from(
             "Fix: use a repository name, or for GitHub use `repository = \"Owner/Repository\"`.",
         ),
+        "include" | "exclude" => format!(
+            "Fix: use Maven coordinate patterns such as `{key} = [ \"com.example.*\", \"org.example:library\" ]`, or remove the key."
+        ),
         "path" => String::from("Fix: use a quoted path, for example `path = \"lib/library.jar\"`."),
         "url" => String::from(
             "Fix: use a quoted URL, for example `url = \"https://example.com/library.jar\"`.",
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use crate::dependency::Dependency;
+use crate::dependency::sources::maven::{self, ManagedVersion};
+
+/// Gives every Maven dependency without a `version` the version a declared BOM manages for it.
+/// Returns the name of the BOM which aligned each dependency. With `cached_only`, a BOM whose POMs
+/// are not all cached is skipped rather than downloaded.
+pub fn align_versions(
+    dependencies: &mut HashMap<String, Dependency>,
+    cached_only: bool,
+) -> Result<HashMap<String, String>, String> {
+    let mut bom_names: Vec<&String> = dependencies
+        .iter()
+        .filter(|(_, dependency)| dependency.is_bom())
+        .map(|(name, _)| name)
+        .collect();
+    if bom_names.is_empty() {
+        return Ok(HashMap::new());
+    }
+    bom_names.sort_unstable();
+
+    let mut boms: Vec<(String, Vec<ManagedVersion>)> = Vec::new();
+    for name in bom_names {
+        let Dependency::FetchFromMaven {
+            repositories,
+            group_id,
+            artifact_id,
+            version: Some(version),
+            ..
+        } = &dependencies[name]
+        else {
+            continue;
+        };
+
+        match maven::managed_versions(repositories, group_id, artifact_id, version, cached_only) {
+            Ok(Some(managed)) => boms.push((name.clone(), managed)),
+            // Only with `cached_only`: the BOM applies once its POMs are cached.
+            Ok(None) => continue,
+            Err(e) => {
+                return Err(format!(
+                    "Could not read the versions BOM `{name}` ({group_id}:{artifact_id}:{version}) manages: {e}\nFix: check its coordinates, and run Wisteria online once so its POM is cached."
+                ));
+            }
+        }
+    }
+
+    let mut aligned_by: HashMap<String, String> = HashMap::new();
+    for (name, dependency) in dependencies.iter_mut() {
+        let Dependency::FetchFromMaven {
+            group_id,
+            artifact_id,
+            version: version @ None,
+            bom: false,
+            ..
+        } = dependency
+        else {
+            continue;
+        };
+
+        let mut found: Option<(&String, &String)> = None;
+        for (bom, managed) in &boms {
+            let Some(entry) = managed
+                .iter()
+                .find(|entry| &entry.group_id == group_id && &entry.artifact_id == artifact_id)
+            else {
+                continue;
+            };
+
+            match found {
+                Some((other, other_version)) if *other_version != entry.version => {
+                    return Err(format!(
+                        "Maven dependency `{name}` is managed at {other_version} by BOM `{other}` and at {} by BOM `{bom}`.\nFix: set `version` on `{name}` to choose one.",
+                        entry.version
+                    ));
+                }
+                Some(_) => {}
+                None => found = Some((bom, &entry.version)),
+            }
+        }
+
+        if let Some((bom, managed_version)) = found {
+            *version = Some(managed_version.clone());
+            aligned_by.insert(name.clone(), bom.clone());
+        }
+    }
+
+    Ok(aligned_by)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::model::Project;
+    use crate::test_support::{TempDir, with_current_dir};
+
+    use super::*;
+
+    fn cache_pom(group_id: &str, artifact_id: &str, version: &str, contents: &str) {
+        let directory = format!(".wisteria/cache/{group_id}/{artifact_id}/{version}");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(format!("{directory}/{artifact_id}.pom"), contents).unwrap();
+    }
+
+    fn managed_dependency(group_id: &str, artifact_id: &str, version: &str) -> String {
+        format!(
+            "<dependency><groupId>{group_id}</groupId><artifactId>{artifact_id}</artifactId><version>{version}</version></dependency>"
+        )
+    }
+
+    #[test]
+    fn aligns_unversioned_dependencies_with_cached_boms() {
+        let temp = TempDir::new("bom-align");
+        let project_file = temp.path().join("project.toml");
+        fs::write(
+            &project_file,
+            r#"
+            [project]
+            name = "Demo"
+            version = "1.0.0"
+            description = "Demo project"
+
+            [dependencies.maven]
+            platform = { group_id = "com.example", artifact_id = "platform-bom", version = "1.0.0", bom = true }
+            library = { group_id = "com.example", artifact_id = "library" }
+            tool = { group_id = "com.example", artifact_id = "tool", version = "1.5" }
+            jupiter = { group_id = "org.junit.jupiter", artifact_id = "junit-jupiter" }
+            other = { group_id = "com.example", artifact_id = "other" }
+
+            [configuration.main]
+            sources = [ "src" ]
+            dependencies = [ "library", "tool", "jupiter", "other" ]
+            "#,
+        )
+        .unwrap();
+
+        with_current_dir(temp.path(), || {
+            let mut uncached =
+                Project::from(Some(project_file.to_string_lossy().to_string())).unwrap();
+            uncached.align_versions(true).unwrap();
+            assert_eq!(uncached.aligned_by("library"), None);
+
+            cache_pom(
+                "com.example",
+                "platform-parent",
+                "1.0.0",
+                "<project><properties><library.version>2.3.4</library.version></properties></project>",
+            );
+            cache_pom(
+                "com.example",
+                "platform-bom",
+                "1.0.0",
+                &format!(
+                    "<project><parent><groupId>com.example</groupId><artifactId>platform-parent</artifactId><version>1.0.0</version></parent><dependencyManagement><dependencies>{}{}<dependency><groupId>org.junit</groupId><artifactId>junit-bom</artifactId><version>5.10.2</version><type>pom</type><scope>import</scope></dependency></dependencies></dependencyManagement></project>",
+                    managed_dependency("com.example", "library", "${library.version}"),
+                    managed_dependency("com.example", "tool", "${project.version}"),
+                ),
+            );
+            cache_pom(
+                "org.junit",
+                "junit-bom",
+                "5.10.2",
+                &format!(
+                    "<project><dependencyManagement><dependencies>{}{}</dependencies></dependencyManagement></project>",
+                    managed_dependency("org.junit.jupiter", "junit-jupiter", "${project.version}"),
+                    managed_dependency("com.example", "library", "9.9"),
+                ),
+            );
+
+            let mut project =
+                Project::from(Some(project_file.to_string_lossy().to_string())).unwrap();
+            project.align_versions(true).unwrap();
+            let version = |name: &str| match &project.dependencies()[name] {
+                Dependency::FetchFromMaven { version, .. } => version.clone(),
+                _ => panic!("expected Maven dependency"),
+            };
+
+            assert_eq!(version("library").as_deref(), Some("2.3.4"));
+            assert_eq!(version("tool").as_deref(), Some("1.5"));
+            assert_eq!(version("jupiter").as_deref(), Some("5.10.2"));
+            assert_eq!(version("other"), None);
+            assert_eq!(project.aligned_by("library"), Some("platform"));
+            assert_eq!(project.aligned_by("tool"), None);
+            assert_eq!(project.dependencies()["platform"].lockfile_source(), None);
+
+            fs::write(
+                &project_file,
+                fs::read_to_string(&project_file)
+                    .unwrap()
+                    .replace(r#""library", "tool""#, r#""platform", "tool""#),
+            )
+            .unwrap();
+            let error = match Project::from(Some(project_file.to_string_lossy().to_string())) {
+                Ok(_) => panic!("expected a BOM reference to fail"),
+                Err(error) => error,
+            };
+            assert!(error.contains("`platform` is a BOM"));
+        });
+    }
+
+    #[test]
+    fn cached_only_alignment_reports_malformed_cached_boms() {
+        let temp = TempDir::new("bom-malformed");
+        let project_file = temp.path().join("project.toml");
+        fs::write(
+            &project_file,
+            r#"
+            [project]
+            name = "Demo"
+            version = "1.0.0"
+            description = "Demo project"
+
+            [dependencies.maven]
+            platform = { group_id = "com.example", artifact_id = "platform-bom", version = "1.0.0", bom = true }
+            library = { group_id = "com.example", artifact_id = "library" }
+
+            [configuration.main]
+            sources = [ "src" ]
+            dependencies = [ "library" ]
+            "#,
+        )
+        .unwrap();
+
+        with_current_dir(temp.path(), || {
+            cache_pom(
+                "com.example",
+                "platform-bom",
+                "1.0.0",
+                "<project><unclosed>",
+            );
+
+            let mut project =
+                Project::from(Some(project_file.to_string_lossy().to_string())).unwrap();
+            let error = project.align_versions(true).unwrap_err();
+            assert!(error.contains("Could not read the versions BOM `platform`"));
+        });
+    }
+}
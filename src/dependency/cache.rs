@@ -24,6 +24,25 @@ pub fn maven_cache_path(
     )
 }
 
+/// Where the POM of a released artifact is cached, beside its jar.
+pub fn maven_pom_cache_path(group_id: &str, artifact_id: &str, version: &str) -> String {
+    format!(
+        "{}/{group_id}/{artifact_id}/{version}/{artifact_id}.pom",
+        consts::CACHE_PATH
+    )
+}
+
+/// A file published or cached beside a jar under its name, such as its POM or sources jar. The
+/// classifier of the jar is dropped first, since these files belong to the main artifact.
+pub fn jar_sibling(jar: &str, classifier: Option<&String>, suffix: &str) -> String {
+    let stem = jar.strip_suffix(".jar").unwrap_or(jar);
+    let stem = match classifier {
+        Some(classifier) => stem.strip_suffix(&format!("-{classifier}")).unwrap_or(stem),
+        None => stem,
+    };
+    format!("{stem}{suffix}")
+}
+
 pub fn github_cache_path(username: &str, repository: &str, tag: &str) -> String {
     format!(
         "{}/{username}/{repository}/{tag}/{repository}.jar",
@@ -65,6 +84,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn builds_jar_siblings_without_the_classifier() {
+        let shaded = String::from("shaded");
+        assert_eq!(
+            jar_sibling(
+                ".wisteria/cache/com.example/library/1.0.0/library-shaded.jar",
+                Some(&shaded),
+                "-sources.jar"
+            ),
+            ".wisteria/cache/com.example/library/1.0.0/library-sources.jar"
+        );
+        assert_eq!(
+            jar_sibling("https://repo.example/library-1.0.0.jar", None, ".pom"),
+            "https://repo.example/library-1.0.0.pom"
+        );
+    }
+
     #[test]
     fn builds_github_cache_path() {
         assert_eq!(
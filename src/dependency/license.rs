@@ -0,0 +1,341 @@
+use std::collections::HashMap;
+
+use toml::Value;
+
+use crate::config::toml_utils;
+use crate::maven::pom::PomLicense;
+
+/// Phrases, after normalizing with `normalize`, which identify a license by name or URL. Earlier
+/// entries win, so more specific licenses come before the ones whose phrases they contain.
+const KNOWN_LICENSES: &[(&str, &[&str])] = &[
+    (
+        "Apache-2.0",
+        &["apache 2", "apache org 2", "asl 2", "apache2"],
+    ),
+    ("MIT", &["mit", "expat"]),
+    (
+        "BSD-3-Clause",
+        &[
+            "bsd 3 clause",
+            "3 clause bsd",
+            "new bsd",
+            "revised bsd",
+            "modified bsd",
+            "bsd 3",
+            "eclipse distribution 1 0",
+            "edl 1 0",
+        ],
+    ),
+    (
+        "BSD-2-Clause",
+        &[
+            "bsd 2 clause",
+            "2 clause bsd",
+            "simplified bsd",
+            "freebsd",
+            "bsd 2",
+        ],
+    ),
+    ("EPL-2.0", &["eclipse public 2", "epl 2"]),
+    ("EPL-1.0", &["eclipse public 1", "epl 1"]),
+    (
+        "GPL-2.0-only WITH Classpath-exception-2.0",
+        &["classpath exception", "gpl2 w cpe"],
+    ),
+    ("AGPL-3.0-only", &["agpl 3", "affero general public 3"]),
+    (
+        "LGPL-2.1-only",
+        &["lgpl 2 1", "lesser general public 2 1", "lgplv2 1"],
+    ),
+    (
+        "LGPL-3.0-only",
+        &["lgpl 3", "lesser general public 3", "lgplv3"],
+    ),
+    ("GPL-2.0-only", &["gpl 2", "gnu general public 2", "gplv2"]),
+    ("GPL-3.0-only", &["gpl 3", "gnu general public 3", "gplv3"]),
+    ("MPL-2.0", &["mozilla public 2", "mpl 2"]),
+    (
+        "CDDL-1.1",
+        &["cddl 1 1", "common development and distribution 1 1"],
+    ),
+    (
+        "CDDL-1.0",
+        &["cddl 1", "common development and distribution 1", "cddl"],
+    ),
+    ("BSL-1.0", &["boost 1 0", "bsl 1 0"]),
+    ("ISC", &["isc"]),
+    ("Unlicense", &["unlicense"]),
+    ("CC0-1.0", &["cc0", "creative commons zero"]),
+];
+
+/// Words which license names and URLs use inconsistently, and are dropped before matching.
+const FILLER_WORDS: &[&str] = &[
+    "the", "version", "v", "license", "licence", "licenses", "software", "https", "http", "www",
+    "txt", "html", "php",
+];
+
+/// The SPDX identifier for a POM license, or its name as written when it is not recognized.
+/// Returns `None` when the license has neither a name nor a URL.
+pub fn spdx_identifier(license: &PomLicense) -> Option<String> {
+    let name = license.name.as_deref().map(str::trim);
+    if let Some(name) = name
+        && let Some((identifier, _)) = KNOWN_LICENSES
+            .iter()
+            .find(|(identifier, _)| identifier.eq_ignore_ascii_case(name))
+    {
+        return Some(identifier.to_string());
+    }
+
+    let text = [name, license.url.as_deref()]
+        .into_iter()
+        .flatten()
+        .map(normalize)
+        .collect::<Vec<String>>()
+        .join(" ");
+    let padded = format!(" {text} ");
+    let known = KNOWN_LICENSES.iter().find(|(_, phrases)| {
+        phrases
+            .iter()
+            .any(|phrase| padded.contains(&format!(" {phrase} ")))
+    });
+
+    match known {
+        Some((identifier, _))
+            if padded.contains(" or later ") || padded.contains(" any later ") =>
+        {
+            Some(identifier.replacen("-only", "-or-later", 1))
+        }
+        Some((identifier, _)) => Some(identifier.to_string()),
+        None => name
+            .filter(|name| !name.is_empty())
+            .or(license.url.as_deref())
+            .map(String::from),
+    }
+}
+
+/// Lowercases, splits at anything but letters and digits, drops filler words, and turns `v2` into
+/// `2`.
+fn normalize(value: &str) -> String {
+    value
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty() && !FILLER_WORDS.contains(word))
+        .map(|word| match word.strip_prefix('v') {
+            Some(number) if number.starts_with(|c: char| c.is_ascii_digit()) => number,
+            _ => word,
+        })
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+/// `[licenses]` in project.toml: which licenses shipped dependencies may carry, and the licenses
+/// of dependencies which have no POM to read them from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LicensePolicy {
+    allow: Vec<String>,
+    deny: Vec<String>,
+    declared: HashMap<String, Vec<String>>,
+}
+
+impl LicensePolicy {
+    pub fn load(value: Option<&Value>) -> Result<Self, String> {
+        let Some(value) = value else {
+            return Ok(Self::default());
+        };
+        let Some(table) = value.as_table() else {
+            return Err(format!(
+                "Invalid [licenses] section: expected a table, found {}.\nFix: use `[licenses]` followed by `allow = [ \"Apache-2.0\", \"MIT\" ]` or `deny = [ ... ]`.",
+                value.type_str()
+            ));
+        };
+
+        let read_list = |key: &str| {
+            toml_utils::read_optional_string_array(key, table)
+                .map(Option::unwrap_or_default)
+                .map_err(|error| format!("Invalid [licenses].{key}: {error}"))
+        };
+        let allow = read_list("allow")?;
+        let deny = read_list("deny")?;
+        if let Some(both) = allow.iter().find(|license| {
+            deny.iter()
+                .any(|denied| denied.eq_ignore_ascii_case(license))
+        }) {
+            return Err(format!(
+                "License \"{both}\" is both allowed and denied in [licenses].\nFix: remove it from either `allow` or `deny`."
+            ));
+        }
+
+        let mut declared = HashMap::new();
+        if let Some(table) = toml_utils::read_optional_table("declared", table)
+            .map_err(|error| format!("Invalid [licenses].declared: {error}"))?
+        {
+            for name in table.keys() {
+                let licenses = toml_utils::read_string_array(name, table)
+                    .map_err(|error| format!("Invalid [licenses.declared].{name}: {error}"))?;
+                declared.insert(name.clone(), licenses);
+            }
+        }
+
+        Ok(Self {
+            allow,
+            deny,
+            declared,
+        })
+    }
+
+    /// Whether `allow` or `deny` restrict anything.
+    pub fn is_enforced(&self) -> bool {
+        !self.allow.is_empty() || !self.deny.is_empty()
+    }
+
+    /// Licenses given for a dependency in `[licenses.declared]`, which take precedence over its POM.
+    pub fn declared(&self, name: &str) -> Option<&[String]> {
+        self.declared.get(name).map(Vec::as_slice)
+    }
+
+    pub fn declared_names(&self) -> impl Iterator<Item = &String> {
+        self.declared.keys()
+    }
+
+    /// Why a dependency with these licenses may not ship, if it may not. Several licenses are
+    /// treated as alternatives, so one acceptable license is enough.
+    pub fn violation(&self, licenses: &[String]) -> Option<String> {
+        if !self.is_enforced() {
+            return None;
+        }
+        if licenses.is_empty() {
+            return if self.allow.is_empty() {
+                None
+            } else {
+                Some(String::from("no license is known"))
+            };
+        }
+
+        let matches = |list: &[String], license: &String| {
+            list.iter()
+                .any(|listed| listed.eq_ignore_ascii_case(license))
+        };
+        let acceptable = licenses.iter().any(|license| {
+            !matches(&self.deny, license)
+                && (self.allow.is_empty() || matches(&self.allow, license))
+        });
+        if acceptable {
+            return None;
+        }
+
+        match licenses.iter().find(|license| matches(&self.deny, license)) {
+            Some(denied) => Some(format!("{denied} is denied")),
+            None => Some(format!("{} is not allowed", licenses.join(", "))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identifier(name: Option<&str>, url: Option<&str>) -> Option<String> {
+        spdx_identifier(&PomLicense {
+            name: name.map(String::from),
+            url: url.map(String::from),
+        })
+    }
+
+    #[test]
+    fn recognizes_common_pom_license_names_and_urls() {
+        let apache = Some(String::from("Apache-2.0"));
+        assert_eq!(
+            identifier(Some("The Apache Software License, Version 2.0"), None),
+            apache
+        );
+        assert_eq!(
+            identifier(
+                None,
+                Some("https://www.apache.org/licenses/LICENSE-2.0.txt")
+            ),
+            apache
+        );
+        assert_eq!(identifier(Some("mit"), None), Some(String::from("MIT")));
+        assert_eq!(
+            identifier(Some("Eclipse Public License - v 2.0"), None),
+            Some(String::from("EPL-2.0"))
+        );
+        assert_eq!(
+            identifier(Some("GNU Lesser General Public License, version 2.1"), None),
+            Some(String::from("LGPL-2.1-only"))
+        );
+        assert_eq!(
+            identifier(Some("GNU General Public License v3.0 or later"), None),
+            Some(String::from("GPL-3.0-or-later"))
+        );
+        assert_eq!(
+            identifier(Some("Bouncy Castle Licence"), None),
+            Some(String::from("Bouncy Castle Licence"))
+        );
+        assert_eq!(identifier(None, None), None);
+    }
+
+    #[test]
+    fn treats_several_licenses_as_alternatives() {
+        let policy = LicensePolicy::load(Some(&Value::Table(
+            r#"
+            allow = [ "Apache-2.0", "MIT" ]
+            deny = [ "GPL-3.0-only" ]
+            "#
+            .parse()
+            .unwrap(),
+        )))
+        .unwrap();
+        let licenses = |licenses: &[&str]| -> Vec<String> {
+            licenses.iter().map(|license| license.to_string()).collect()
+        };
+
+        assert_eq!(policy.violation(&licenses(&["mit"])), None);
+        assert_eq!(policy.violation(&licenses(&["GPL-3.0-only", "MIT"])), None);
+        assert_eq!(
+            policy.violation(&licenses(&["GPL-3.0-only"])),
+            Some(String::from("GPL-3.0-only is denied"))
+        );
+        assert_eq!(
+            policy.violation(&licenses(&["EPL-2.0"])),
+            Some(String::from("EPL-2.0 is not allowed"))
+        );
+        assert_eq!(
+            policy.violation(&[]),
+            Some(String::from("no license is known"))
+        );
+    }
+
+    #[test]
+    fn loads_declared_licenses_and_rejects_conflicting_lists() {
+        let policy = LicensePolicy::load(Some(&Value::Table(
+            r#"
+            deny = [ "AGPL-3.0-only" ]
+
+            [declared]
+            local-lib = "MIT"
+            dual = [ "MIT", "Apache-2.0" ]
+            "#
+            .parse()
+            .unwrap(),
+        )))
+        .unwrap();
+
+        assert_eq!(
+            policy.declared("local-lib"),
+            Some(&[String::from("MIT")][..])
+        );
+        assert_eq!(policy.declared("dual").map(<[String]>::len), Some(2));
+        assert_eq!(policy.violation(&[]), None);
+
+        let conflicting = LicensePolicy::load(Some(&Value::Table(
+            r#"
+            allow = [ "MIT" ]
+            deny = [ "mit" ]
+            "#
+            .parse()
+            .unwrap(),
+        )));
+        assert!(conflicting.unwrap_err().contains("both allowed and denied"));
+    }
+}
@@ -1,12 +1,18 @@
+pub mod bom;
 pub mod cache;
+pub mod license;
 pub mod model;
+pub mod osv;
 pub mod parse;
 pub mod policy;
 pub mod reference;
+pub mod repositories;
 pub mod resolver;
 pub mod sources;
+pub mod version;
 
-pub use model::{Dependency, GithubReleaseType};
+pub use model::{AttachmentKind, Dependency, GithubReleaseType};
 pub use parse::{load_dependency_map, migrate_legacy_dependency_table};
-pub use policy::{UpdateContext, UpdatePolicy};
+pub use policy::{ChecksumPolicy, UpdateContext, UpdatePolicy};
 pub use reference::{DependencyReference, DependencyScope, PackagingType};
+pub use repositories::{MavenRepository, assign_repositories, load_repositories};
@@ -1,5 +1,11 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    dependency::{UpdatePolicy, cache},
+    dependency::{
+        ChecksumPolicy, UpdatePolicy, cache, repositories::MavenRepository, version::VersionRange,
+    },
     model::LockfileArtifact,
     util::consts,
 };
@@ -28,13 +34,23 @@ pub enum Dependency {
         javadoc: Option<String>,
     },
     FetchFromMaven {
-        url: String,
+        /// Repository pinned with `repository = "name"`.
+        repository: Option<String>,
+        /// Repositories to try in order, assigned from `url` or the project's `[repositories]`.
+        repositories: Vec<MavenRepository>,
         group_id: String,
         artifact_id: String,
         version: Option<String>,
         classifier: Option<String>,
         update_policy: UpdatePolicy,
+        /// Overrides the `checksum_policy` of the repository the artifact is downloaded from.
+        checksum_policy: Option<ChecksumPolicy>,
+        verify_signature: bool,
         javadoc: Option<String>,
+        /// Extra jars fetched beside the artifact for IDEs, from `attach = [ "sources", "javadoc" ]`.
+        attachments: Vec<AttachmentKind>,
+        /// Set with `bom = true`: only the POM is read, for the versions it manages.
+        bom: bool,
     },
     FetchFromGithub {
         username: String,
@@ -43,7 +59,9 @@ pub enum Dependency {
         tag: Option<String>,
         release_type: GithubReleaseType,
         update_policy: UpdatePolicy,
+        checksum_policy: ChecksumPolicy,
         javadoc: Option<String>,
+        attachments: Vec<AttachmentKind>,
     },
 
     BuildFromScript {
@@ -67,6 +85,17 @@ impl Dependency {
         }
     }
 
+    /// Whether locked artifacts of this dependency must carry a verified signature.
+    pub fn requires_signature(&self) -> bool {
+        matches!(
+            self,
+            Dependency::FetchFromMaven {
+                verify_signature: true,
+                ..
+            }
+        )
+    }
+
     pub fn javadoc(&self) -> Option<&String> {
         match self {
             Dependency::LocalFile { javadoc, .. } => javadoc.as_ref(),
@@ -79,8 +108,23 @@ impl Dependency {
         }
     }
 
+    /// The extra jars requested with `attach`, which only Maven and GitHub dependencies support.
+    pub fn attachments(&self) -> &[AttachmentKind] {
+        match self {
+            Dependency::FetchFromMaven { attachments, .. } => attachments,
+            Dependency::FetchFromGithub { attachments, .. } => attachments,
+            _ => &[],
+        }
+    }
+
+    /// Whether this is a BOM, which supplies versions to other Maven dependencies and has no jar.
+    pub fn is_bom(&self) -> bool {
+        matches!(self, Dependency::FetchFromMaven { bom: true, .. })
+    }
+
     pub fn lockfile_source(&self) -> Option<&'static str> {
         match self {
+            _ if self.is_bom() => None,
             Dependency::FetchFromUrl { .. } => Some("url"),
             Dependency::FetchFromMaven { .. } => Some("maven"),
             Dependency::FetchFromGithub { .. } => Some("github"),
@@ -88,7 +132,22 @@ impl Dependency {
         }
     }
 
+    /// Whether the lock entry was resolved from this declaration, and records exactly the
+    /// attachments it asks for.
     pub fn matches_lockfile_artifact(&self, artifact: &LockfileArtifact) -> bool {
+        self.matches_locked_coordinates(artifact) && self.matches_locked_attachments(artifact)
+    }
+
+    fn matches_locked_attachments(&self, artifact: &LockfileArtifact) -> bool {
+        artifact.attachments().len() == self.attachments().len()
+            && self
+                .attachments()
+                .iter()
+                .all(|kind| artifact.attachment(*kind).is_some())
+    }
+
+    /// Whether the lock entry was resolved from this declaration, whatever attachments it records.
+    pub fn matches_locked_coordinates(&self, artifact: &LockfileArtifact) -> bool {
         let Some(source) = self.lockfile_source() else {
             return false;
         };
@@ -100,22 +159,24 @@ impl Dependency {
         match self {
             Dependency::FetchFromUrl { url, .. } => artifact.fetch_url() == url,
             Dependency::FetchFromMaven {
-                url,
+                repositories,
                 group_id,
                 artifact_id,
                 version,
                 classifier,
                 ..
-            } => maven_dependency_matches_lockfile(
-                MavenLockfileMatch {
-                    url,
-                    group_id,
-                    artifact_id,
-                    version: version.as_deref(),
-                    classifier: classifier.as_ref(),
-                },
-                artifact,
-            ),
+            } => repositories.iter().any(|repository| {
+                maven_dependency_matches_lockfile(
+                    MavenLockfileMatch {
+                        url: repository.url(),
+                        group_id,
+                        artifact_id,
+                        version: version.as_deref(),
+                        classifier: classifier.as_ref(),
+                    },
+                    artifact,
+                )
+            }),
             Dependency::FetchFromGithub {
                 username,
                 repository,
@@ -300,6 +361,11 @@ fn configured_version_matches_lockfile(
 ) -> bool {
     match configured_version {
         Some("latest" | "release") | None => locked_version.is_some(),
+        Some(version) if VersionRange::is_range(version) => {
+            locked_version.is_some_and(|locked_version| {
+                VersionRange::parse(version).is_ok_and(|range| range.contains(locked_version))
+            })
+        }
         Some(version) => locked_version == Some(version),
     }
 }
@@ -324,19 +390,59 @@ impl GithubReleaseType {
     }
 }
 
+/// A jar published beside an artifact which IDEs attach to it, named with the kind as classifier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AttachmentKind {
+    Sources,
+    Javadoc,
+}
+
+impl AttachmentKind {
+    pub fn load(value: &str) -> Result<Self, String> {
+        match value {
+            "sources" => Ok(Self::Sources),
+            "javadoc" => Ok(Self::Javadoc),
+            _ => Err(format!(
+                "Unexpected attachment \"{value}\". Fix: use any of [sources, javadoc] in `attach`."
+            )),
+        }
+    }
+
+    /// The classifier Maven repositories publish the jar under, also used for GitHub assets.
+    pub fn classifier(&self) -> &'static str {
+        match self {
+            Self::Sources => "sources",
+            Self::Javadoc => "javadoc",
+        }
+    }
+}
+
+impl fmt::Display for AttachmentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.classifier())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::LockfileAttachment;
 
     fn maven_dependency(version: Option<&str>, artifact_id: &str) -> Dependency {
         Dependency::FetchFromMaven {
-            url: String::from("https://repo.example"),
+            repository: None,
+            repositories: vec![MavenRepository::new("example", "https://repo.example")],
             group_id: String::from("com.example"),
             artifact_id: String::from(artifact_id),
             version: version.map(String::from),
             classifier: None,
             update_policy: UpdatePolicy::Never,
+            checksum_policy: None,
+            verify_signature: false,
             javadoc: None,
+            attachments: Vec::new(),
+            bom: false,
         }
     }
 
@@ -359,7 +465,9 @@ mod tests {
             tag: tag.map(String::from),
             release_type: GithubReleaseType::Release,
             update_policy: UpdatePolicy::Never,
+            checksum_policy: ChecksumPolicy::Fail,
             javadoc: None,
+            attachments: Vec::new(),
         }
     }
 
@@ -398,6 +506,48 @@ mod tests {
         assert!(!dependency.matches_lockfile_artifact(&artifact));
     }
 
+    #[test]
+    fn maven_dependency_rejects_lockfile_artifact_without_requested_attachments() {
+        let mut dependency = maven_dependency(Some("latest"), "library");
+        if let Dependency::FetchFromMaven { attachments, .. } = &mut dependency {
+            *attachments = vec![AttachmentKind::Sources];
+        }
+        let artifact = maven_artifact(
+            "1.0.0",
+            "https://repo.example/com/example/library/1.0.0/library-1.0.0.jar",
+            ".wisteria/cache/com.example/library/1.0.0/library.jar",
+        );
+        let attached = artifact
+            .clone()
+            .with_attachments(vec![LockfileAttachment::new(
+                AttachmentKind::Sources,
+                String::from(
+                    "https://repo.example/com/example/library/1.0.0/library-1.0.0-sources.jar",
+                ),
+                String::from(".wisteria/cache/com.example/library/1.0.0/library-sources.jar"),
+                String::from("hash"),
+            )]);
+
+        assert!(dependency.matches_locked_coordinates(&artifact));
+        assert!(!dependency.matches_lockfile_artifact(&artifact));
+        assert!(dependency.matches_lockfile_artifact(&attached));
+    }
+
+    #[test]
+    fn maven_dependency_matches_lockfile_artifact_inside_its_version_range() {
+        let artifact = maven_artifact(
+            "1.5.2",
+            "https://repo.example/com/example/library/1.5.2/library-1.5.2.jar",
+            ".wisteria/cache/com.example/library/1.5.2/library.jar",
+        );
+
+        assert!(maven_dependency(Some("^1.4"), "library").matches_lockfile_artifact(&artifact));
+        assert!(
+            maven_dependency(Some("[1.0,2.0)"), "library").matches_lockfile_artifact(&artifact)
+        );
+        assert!(!maven_dependency(Some("~1.4.0"), "library").matches_lockfile_artifact(&artifact));
+    }
+
     #[test]
     fn maven_dependency_matches_snapshot_lockfile_artifact_with_timestamp_value() {
         let dependency = maven_dependency(Some("1.0-SNAPSHOT"), "library");
@@ -0,0 +1,508 @@
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display},
+    fs::{self, File},
+    io::Read,
+    path::Path,
+};
+
+use serde::Deserialize;
+use serde_json::Value;
+use zip::ZipArchive;
+
+use crate::dependency::version::compare_versions;
+use crate::workspace::files;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl Severity {
+    pub fn load(value: &str) -> Result<Self, String> {
+        match value.to_lowercase().as_str() {
+            "low" => Ok(Self::Low),
+            "medium" | "moderate" => Ok(Self::Medium),
+            "high" => Ok(Self::High),
+            "critical" => Ok(Self::Critical),
+            _ => Err(format!(
+                "Unknown severity \"{value}\". Expected one of [low, medium, high, critical]."
+            )),
+        }
+    }
+
+    fn from_score(score: f64) -> Option<Self> {
+        match score {
+            score if score >= 9.0 => Some(Self::Critical),
+            score if score >= 7.0 => Some(Self::High),
+            score if score >= 4.0 => Some(Self::Medium),
+            score if score > 0.0 => Some(Self::Low),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+            Self::Critical => "critical",
+        })
+    }
+}
+
+/// An advisory affecting one locked Maven artifact.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Finding {
+    pub id: String,
+    pub aliases: Vec<String>,
+    pub summary: Option<String>,
+    pub severity: Option<Severity>,
+    pub fixed: Vec<String>,
+}
+
+/// The subset of the OSV schema which is needed to match Maven advisories.
+#[derive(Deserialize)]
+struct Advisory {
+    id: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    summary: Option<String>,
+    withdrawn: Option<String>,
+    #[serde(default)]
+    severity: Vec<SeverityScore>,
+    #[serde(default)]
+    affected: Vec<Affected>,
+    database_specific: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct SeverityScore {
+    #[serde(rename = "type")]
+    kind: String,
+    score: String,
+}
+
+#[derive(Deserialize)]
+struct Affected {
+    package: Option<Package>,
+    #[serde(default)]
+    ranges: Vec<Range>,
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    ecosystem: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Range {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    events: Vec<Event>,
+}
+
+#[derive(Deserialize)]
+struct Event {
+    introduced: Option<String>,
+    fixed: Option<String>,
+    last_affected: Option<String>,
+    limit: Option<String>,
+}
+
+impl Event {
+    fn version(&self) -> &str {
+        [
+            &self.introduced,
+            &self.fixed,
+            &self.last_affected,
+            &self.limit,
+        ]
+        .into_iter()
+        .find_map(Option::as_deref)
+        .unwrap_or("0")
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OsvDocument {
+    One(Box<Advisory>),
+    Many(Vec<Advisory>),
+}
+
+/// Maven advisories read from a local OSV export: a directory of JSON files, the `all.zip` archive
+/// published for each ecosystem, or a single JSON file holding one advisory or an array of them.
+pub struct OsvDatabase {
+    advisories: Vec<Advisory>,
+}
+
+impl OsvDatabase {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Err(format!(
+                "OSV database {} does not exist.\nFix: download the Maven export from https://osv-vulnerabilities.storage.googleapis.com/Maven/all.zip and pass its path with `--database`.",
+                path.display()
+            ));
+        }
+
+        let mut advisories = Vec::new();
+        if path.is_dir() {
+            for file in files::collect_files_with_extension(path, "json")? {
+                let contents = fs::read_to_string(&file)
+                    .map_err(|e| format!("Could not read {}: {e}", file.display()))?;
+                read_document(&contents, &file.display().to_string(), &mut advisories)?;
+            }
+        } else if path.extension().is_some_and(|extension| extension == "zip") {
+            let archive =
+                File::open(path).map_err(|e| format!("Could not open {}: {e}", path.display()))?;
+            let mut archive = ZipArchive::new(archive)
+                .map_err(|e| format!("Could not open {}: {e}", path.display()))?;
+            for index in 0..archive.len() {
+                let mut entry = archive
+                    .by_index(index)
+                    .map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+                if !entry.name().ends_with(".json") {
+                    continue;
+                }
+
+                let name = format!("{}!{}", path.display(), entry.name());
+                let mut contents = String::new();
+                entry
+                    .read_to_string(&mut contents)
+                    .map_err(|e| format!("Could not read {name}: {e}"))?;
+                read_document(&contents, &name, &mut advisories)?;
+            }
+        } else {
+            let contents = fs::read_to_string(path)
+                .map_err(|e| format!("Could not read {}: {e}", path.display()))?;
+            read_document(&contents, &path.display().to_string(), &mut advisories)?;
+        }
+
+        Ok(Self { advisories })
+    }
+
+    /// Advisories which list `group_id:artifact_id` at `version` as affected.
+    pub fn findings(&self, group_id: &str, artifact_id: &str, version: &str) -> Vec<Finding> {
+        let package = format!("{group_id}:{artifact_id}");
+        self.advisories
+            .iter()
+            .filter_map(|advisory| {
+                let affected: Vec<&Affected> = advisory
+                    .affected
+                    .iter()
+                    .filter(|affected| {
+                        affected
+                            .package
+                            .as_ref()
+                            .is_some_and(|p| p.ecosystem == "Maven" && p.name == package)
+                            && affected.affects(version)
+                    })
+                    .collect();
+                if affected.is_empty() {
+                    return None;
+                }
+
+                let mut fixed: Vec<String> = affected
+                    .iter()
+                    .flat_map(|affected| &affected.ranges)
+                    .flat_map(|range| &range.events)
+                    .filter_map(|event| event.fixed.clone())
+                    .collect();
+                fixed.sort_by(|a, b| compare_versions(a, b));
+                fixed.dedup();
+
+                Some(Finding {
+                    id: advisory.id.clone(),
+                    aliases: advisory.aliases.clone(),
+                    summary: advisory.summary.clone(),
+                    severity: advisory.severity(),
+                    fixed,
+                })
+            })
+            .collect()
+    }
+}
+
+fn read_document(contents: &str, name: &str, advisories: &mut Vec<Advisory>) -> Result<(), String> {
+    let document: OsvDocument = serde_json::from_str(contents).map_err(|e| {
+        format!(
+            "Could not read OSV advisory {name}: {e}\nFix: point `--database` at an OSV export, such as the Maven all.zip from osv.dev."
+        )
+    })?;
+
+    let found = match document {
+        OsvDocument::One(advisory) => vec![*advisory],
+        OsvDocument::Many(found) => found,
+    };
+    advisories.extend(found.into_iter().filter(|advisory| {
+        advisory.withdrawn.is_none()
+            && advisory.affected.iter().any(|affected| {
+                affected
+                    .package
+                    .as_ref()
+                    .is_some_and(|package| package.ecosystem == "Maven")
+            })
+    }));
+    Ok(())
+}
+
+impl Advisory {
+    /// The severity the database assigned, such as GitHub's, or else the rating of a CVSS v3 vector.
+    fn severity(&self) -> Option<Severity> {
+        let assigned = self
+            .database_specific
+            .as_ref()
+            .and_then(|specific| specific.get("severity"))
+            .and_then(Value::as_str)
+            .and_then(|severity| Severity::load(severity).ok());
+
+        assigned.or_else(|| {
+            self.severity
+                .iter()
+                .filter(|score| score.kind == "CVSS_V3")
+                .find_map(|score| cvss3_base_score(&score.score))
+                .and_then(Severity::from_score)
+        })
+    }
+}
+
+impl Affected {
+    fn affects(&self, version: &str) -> bool {
+        self.versions.iter().any(|listed| listed == version)
+            || self
+                .ranges
+                .iter()
+                .filter(|range| range.kind == "ECOSYSTEM")
+                .any(|range| range.affects(version))
+    }
+}
+
+impl Range {
+    /// Walks the events in version order, as the OSV schema describes.
+    fn affects(&self, version: &str) -> bool {
+        let mut events: Vec<&Event> = self.events.iter().collect();
+        events.sort_by(|a, b| compare_versions(a.version(), b.version()));
+
+        let mut affected = false;
+        for event in events {
+            let ordering = compare_versions(version, event.version());
+            let passed = if event.last_affected.is_some() {
+                ordering == Ordering::Greater
+            } else {
+                ordering != Ordering::Less
+            };
+            if passed {
+                affected = event.introduced.is_some();
+            }
+        }
+        affected
+    }
+}
+
+/// Base score of a `CVSS:3.x/...` vector, following the CVSS v3.1 specification.
+fn cvss3_base_score(vector: &str) -> Option<f64> {
+    let mut metrics = vector.split('/');
+    if !metrics.next()?.starts_with("CVSS:3") {
+        return None;
+    }
+
+    let metrics: Vec<(&str, &str)> = metrics.filter_map(|m| m.split_once(':')).collect();
+    let metric = |name: &str| {
+        metrics
+            .iter()
+            .find(|(metric, _)| *metric == name)
+            .map(|(_, value)| *value)
+    };
+    let changed = metric("S")? == "C";
+
+    let attack_vector = match metric("AV")? {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        "P" => 0.2,
+        _ => return None,
+    };
+    let attack_complexity = match metric("AC")? {
+        "L" => 0.77,
+        "H" => 0.44,
+        _ => return None,
+    };
+    let privileges = match (metric("PR")?, changed) {
+        ("N", _) => 0.85,
+        ("L", false) => 0.62,
+        ("L", true) => 0.68,
+        ("H", false) => 0.27,
+        ("H", true) => 0.5,
+        _ => return None,
+    };
+    let user_interaction = match metric("UI")? {
+        "N" => 0.85,
+        "R" => 0.62,
+        _ => return None,
+    };
+    let impact_of = |name: &str| match metric(name)? {
+        "H" => Some(0.56),
+        "L" => Some(0.22),
+        "N" => Some(0.0),
+        _ => None,
+    };
+
+    let base = 1.0 - (1.0 - impact_of("C")?) * (1.0 - impact_of("I")?) * (1.0 - impact_of("A")?);
+    let impact = if changed {
+        7.52 * (base - 0.029) - 3.25 * (base - 0.02_f64).powi(15)
+    } else {
+        6.42 * base
+    };
+    if impact <= 0.0 {
+        return Some(0.0);
+    }
+
+    let exploitability = 8.22 * attack_vector * attack_complexity * privileges * user_interaction;
+    let score = if changed {
+        1.08 * (impact + exploitability)
+    } else {
+        impact + exploitability
+    };
+    Some(round_up(score.min(10.0)))
+}
+
+/// Rounds up to one decimal place, avoiding floating point artifacts like the specification does.
+fn round_up(value: f64) -> f64 {
+    let scaled = (value * 100_000.0).round() as u64;
+    match scaled % 10_000 {
+        0 => scaled as f64 / 100_000.0,
+        _ => (scaled / 10_000 + 1) as f64 / 10.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    const ADVISORY: &str = r#"{
+        "id": "GHSA-4jrv-ppp4-jm57",
+        "aliases": ["CVE-2022-25647"],
+        "summary": "Deserialization of Untrusted Data in Gson",
+        "affected": [{
+            "package": { "ecosystem": "Maven", "name": "com.google.code.gson:gson" },
+            "ranges": [{
+                "type": "ECOSYSTEM",
+                "events": [{ "introduced": "0" }, { "fixed": "2.8.9" }]
+            }]
+        }],
+        "severity": [{ "type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:H" }],
+        "database_specific": { "severity": "HIGH" }
+    }"#;
+
+    fn database(advisories: &[&str]) -> OsvDatabase {
+        let mut loaded = Vec::new();
+        for advisory in advisories {
+            read_document(advisory, "test", &mut loaded).unwrap();
+        }
+        OsvDatabase { advisories: loaded }
+    }
+
+    #[test]
+    fn matches_versions_inside_affected_ranges() {
+        let database = database(&[ADVISORY]);
+
+        let findings = database.findings("com.google.code.gson", "gson", "2.8.5");
+        assert_eq!(
+            findings,
+            vec![Finding {
+                id: String::from("GHSA-4jrv-ppp4-jm57"),
+                aliases: vec![String::from("CVE-2022-25647")],
+                summary: Some(String::from("Deserialization of Untrusted Data in Gson")),
+                severity: Some(Severity::High),
+                fixed: vec![String::from("2.8.9")],
+            }]
+        );
+        assert!(
+            database
+                .findings("com.google.code.gson", "gson", "2.8.9")
+                .is_empty()
+        );
+        assert!(
+            database
+                .findings("com.google.code.gson", "other", "2.8.5")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn honours_last_affected_and_listed_versions() {
+        let database = database(&[r#"[{
+            "id": "OSV-1",
+            "affected": [{
+                "package": { "ecosystem": "Maven", "name": "org.example:library" },
+                "ranges": [{
+                    "type": "ECOSYSTEM",
+                    "events": [{ "introduced": "1.2" }, { "last_affected": "1.4" }]
+                }],
+                "versions": ["0.9"]
+            }]
+        }]"#]);
+
+        let affected = |version| {
+            !database
+                .findings("org.example", "library", version)
+                .is_empty()
+        };
+        assert!(affected("0.9"));
+        assert!(!affected("1.1"));
+        assert!(affected("1.2.0"));
+        assert!(affected("1.4"));
+        assert!(!affected("1.4.1"));
+    }
+
+    #[test]
+    fn rates_cvss_vectors_when_no_severity_is_assigned() {
+        assert_eq!(
+            cvss3_base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"),
+            Some(9.8)
+        );
+        assert_eq!(
+            cvss3_base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N"),
+            Some(6.1)
+        );
+        assert_eq!(
+            cvss3_base_score("CVSS:3.1/AV:L/AC:H/PR:H/UI:R/S:U/C:N/I:N/A:N"),
+            Some(0.0)
+        );
+        assert_eq!(cvss3_base_score("CVSS:4.0/AV:N"), None);
+        assert_eq!(Severity::from_score(6.1), Some(Severity::Medium));
+    }
+
+    #[test]
+    fn loads_json_files_from_directories_and_skips_withdrawn_advisories() {
+        let temp = TempDir::new("osv-directory");
+        fs::create_dir_all(temp.path().join("Maven")).unwrap();
+        fs::write(temp.path().join("Maven/GHSA-4jrv-ppp4-jm57.json"), ADVISORY).unwrap();
+        fs::write(
+            temp.path().join("Maven/withdrawn.json"),
+            ADVISORY.replace(
+                r#""id": "GHSA-4jrv-ppp4-jm57","#,
+                r#""id": "OSV-2", "withdrawn": "2024-01-01T00:00:00Z","#,
+            ),
+        )
+        .unwrap();
+
+        let database = OsvDatabase::load(temp.path()).unwrap();
+        let findings = database.findings("com.google.code.gson", "gson", "2.8.0");
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].id, "GHSA-4jrv-ppp4-jm57");
+        assert!(OsvDatabase::load(&temp.path().join("missing.zip")).is_err());
+    }
+}
@@ -3,7 +3,10 @@ use std::collections::HashMap;
 use toml::{Table, Value};
 
 use crate::config::toml_utils;
-use crate::dependency::{Dependency, GithubReleaseType, UpdatePolicy};
+use crate::dependency::version::VersionRange;
+use crate::dependency::{
+    AttachmentKind, ChecksumPolicy, Dependency, GithubReleaseType, MavenRepository, UpdatePolicy,
+};
 
 impl Dependency {
     pub fn load(toml: &Table) -> Result<Dependency, String> {
@@ -57,22 +60,53 @@ impl Dependency {
                         })
                     }
                     "fetchFromMaven" => {
-                        let url: String = toml_utils::read_optional_string("url", toml)?
-                            .unwrap_or(String::from("https://repo1.maven.org/maven2/"));
+                        let url = toml_utils::read_optional_string("url", toml)?;
+                        let repository = toml_utils::read_optional_string("repository", toml)?;
+                        if url.is_some() && repository.is_some() {
+                            return Err(String::from(
+                                "A Maven dependency cannot set both `url` and `repository`.\nFix: keep `repository` to pin a repository from [repositories], or `url` to use a repository only for this dependency.",
+                            ));
+                        }
+                        // Dependencies without their own URL are given the project repositories later.
+                        let repositories = url
+                            .map(|url| vec![MavenRepository::new(&url, &url)])
+                            .unwrap_or_default();
                         let group_id: String = toml_utils::read_string("group_id", toml)?;
                         let artifact_id: String = toml_utils::read_string("artifact_id", toml)?;
                         let version = toml_utils::read_optional_string("version", toml)?;
+                        if let Some(version) = &version
+                            && VersionRange::is_range(version)
+                        {
+                            VersionRange::parse(version)?;
+                        }
                         let classifier: Option<String> =
                             toml_utils::read_optional_string("classifier", toml)?;
+                        let checksum_policy = read_checksum_policy(toml)?;
+                        let verify_signature =
+                            toml_utils::read_optional_boolean("verify_signature", toml)?
+                                .unwrap_or(false);
+                        let attachments = read_attachments(toml)?;
+                        let bom = toml_utils::read_optional_boolean("bom", toml)?.unwrap_or(false);
+                        if bom && !is_exact_version(version.as_deref()) {
+                            return Err(format!(
+                                "A BOM needs an exact `version`, found {}.\nFix: set `version` to a released version of {group_id}:{artifact_id}, such as `version = \"1.0.0\"`.",
+                                version.as_deref().unwrap_or("none")
+                            ));
+                        }
 
                         Ok(Dependency::FetchFromMaven {
-                            url,
+                            repository,
+                            repositories,
                             group_id,
                             artifact_id,
                             version,
                             classifier,
                             update_policy,
+                            checksum_policy,
+                            verify_signature,
                             javadoc,
+                            attachments,
+                            bom,
                         })
                     }
                     "fetchFromGithub" => {
@@ -88,6 +122,8 @@ impl Dependency {
 
                         let asset: String = toml_utils::read_optional_string("asset", toml)?
                             .unwrap_or(repository.to_string());
+                        let checksum_policy = read_checksum_policy(toml)?.unwrap_or_default();
+                        let attachments = read_attachments(toml)?;
 
                         Ok(Dependency::FetchFromGithub {
                             username,
@@ -96,7 +132,9 @@ impl Dependency {
                             tag,
                             release_type,
                             update_policy,
+                            checksum_policy,
                             javadoc,
+                            attachments,
                         })
                     }
                     "buildFromScript" => {
@@ -269,9 +307,7 @@ fn insert_dependency(
     dependency: Dependency,
 ) -> Result<(), String> {
     if dependencies.insert(name.to_string(), dependency).is_some() {
-        return Err(format!(
-            "Duplicate dependency name \"{name}\".\nFix: dependency names must be unique across all dependency groups; rename one of them or remove the duplicate."
-        ));
+        return Err(duplicate_dependency_error(name));
     }
 
     Ok(())
@@ -323,14 +359,19 @@ fn insert_grouped_dependency(
         .insert(dependency_name.clone(), dependency_value)
         .is_some()
     {
-        return Err(format!(
-            "Duplicate dependency name \"{dependency_name}\".\nFix: dependency names must be unique across all dependency groups; rename one of them or remove the duplicate."
-        ));
+        return Err(duplicate_dependency_error(&dependency_name));
     }
 
     Ok(())
 }
 
+/// Dependency names are unique across every dependency group.
+pub(crate) fn duplicate_dependency_error(name: &str) -> String {
+    format!(
+        "Duplicate dependency name \"{name}\".\nFix: dependency names must be unique across all dependency groups; rename one of them or remove the duplicate."
+    )
+}
+
 fn dependency_type_for_group(group: &str) -> Result<&'static str, String> {
     match group {
         "archive" | "loadArchive" => Ok("loadArchive"),
@@ -374,6 +415,31 @@ fn contextual_dependency_error(
     format!("Invalid dependency {location}: {error}")
 }
 
+fn read_checksum_policy(toml: &Table) -> Result<Option<ChecksumPolicy>, String> {
+    toml_utils::read_optional_string("checksum_policy", toml)?
+        .map(|policy| ChecksumPolicy::load(&policy))
+        .transpose()
+}
+
+/// BOM POMs are read before any version is resolved, so their own version must be exact.
+fn is_exact_version(version: Option<&str>) -> bool {
+    match version {
+        None | Some("latest" | "release") => false,
+        Some(version) => !version.ends_with("-SNAPSHOT") && !VersionRange::is_range(version),
+    }
+}
+
+fn read_attachments(toml: &Table) -> Result<Vec<AttachmentKind>, String> {
+    let mut attachments: Vec<AttachmentKind> = Vec::new();
+    for value in toml_utils::read_optional_string_array("attach", toml)?.unwrap_or_default() {
+        let kind = AttachmentKind::load(&value)?;
+        if !attachments.contains(&kind) {
+            attachments.push(kind);
+        }
+    }
+    Ok(attachments)
+}
+
 fn github_owner_and_repository(
     username: Option<String>,
     repository: String,
@@ -709,25 +775,78 @@ mod tests {
     }
 
     #[test]
-    fn fetch_from_maven_uses_default_repository_url() {
+    fn fetch_from_maven_loads_checksum_policy() {
         let dependency = load_dependency(
             r#"
             type = "fetchFromMaven"
             group_id = "com.example"
             artifact_id = "library"
+            checksum_policy = "warn"
             "#,
         );
 
         match dependency {
             Dependency::FetchFromMaven {
-                url,
+                checksum_policy, ..
+            } => assert_eq!(checksum_policy, Some(ChecksumPolicy::Warn)),
+            _ => panic!("expected Maven dependency"),
+        }
+    }
+
+    #[test]
+    fn fetch_from_maven_loads_attachments_once_each() {
+        let dependency = load_dependency(
+            r#"
+            type = "fetchFromMaven"
+            group_id = "com.example"
+            artifact_id = "library"
+            attach = [ "sources", "javadoc", "sources" ]
+            "#,
+        );
+
+        assert_eq!(
+            dependency.attachments(),
+            [AttachmentKind::Sources, AttachmentKind::Javadoc]
+        );
+        let error = match Dependency::load(
+            &r#"
+            type = "fetchFromMaven"
+            group_id = "com.example"
+            artifact_id = "library"
+            attach = [ "tests" ]
+            "#
+            .parse::<Table>()
+            .unwrap(),
+        ) {
+            Ok(_) => panic!("expected unknown attachment to fail"),
+            Err(error) => error,
+        };
+
+        assert!(error.contains("Unexpected attachment \"tests\""));
+    }
+
+    #[test]
+    fn fetch_from_maven_leaves_repositories_to_the_project_without_url() {
+        let dependency = load_dependency(
+            r#"
+            type = "fetchFromMaven"
+            group_id = "com.example"
+            artifact_id = "library"
+            "#,
+        );
+
+        match dependency {
+            Dependency::FetchFromMaven {
+                repository,
+                repositories,
                 group_id,
                 artifact_id,
                 version,
                 classifier,
                 ..
             } => {
-                assert_eq!(url, "https://repo1.maven.org/maven2/");
+                assert!(repository.is_none());
+                assert!(repositories.is_empty());
                 assert_eq!(group_id, "com.example");
                 assert_eq!(artifact_id, "library");
                 assert!(version.is_none());
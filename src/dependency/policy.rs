@@ -1,3 +1,17 @@
+use std::sync::OnceLock;
+
+static LOCKED: OnceLock<bool> = OnceLock::new();
+
+/// Sets whether dependencies may only use the artifacts recorded in `wisteria.lock`, as with
+/// `--locked` and `--frozen`. Only the first call has any effect.
+pub fn configure_locked(locked: bool) {
+    let _ = LOCKED.set(locked);
+}
+
+pub fn locked() -> bool {
+    LOCKED.get().copied().unwrap_or_default()
+}
+
 #[derive(Clone, Default)]
 pub enum UpdatePolicy {
     Always,
@@ -11,6 +25,28 @@ pub enum UpdatePolicy {
     Never,
 }
 
+/// What happens when a download does not match the checksum published by its repository.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ChecksumPolicy {
+    #[default]
+    Fail,
+    Warn,
+    Ignore,
+}
+
+impl ChecksumPolicy {
+    pub fn load(value: &str) -> Result<Self, String> {
+        match value {
+            "fail" => Ok(ChecksumPolicy::Fail),
+            "warn" => Ok(ChecksumPolicy::Warn),
+            "ignore" => Ok(ChecksumPolicy::Ignore),
+            _ => Err(format!(
+                "Unexpected checksum policy \"{value}\". Fix: use one of [fail, warn, ignore]."
+            )),
+        }
+    }
+}
+
 #[derive(Clone, Copy)]
 pub enum UpdateContext {
     Update,
@@ -130,6 +166,18 @@ mod tests {
         assert!(error.contains("Unexpected update policy"));
     }
 
+    #[test]
+    fn loads_checksum_policies() {
+        assert_eq!(ChecksumPolicy::load("fail"), Ok(ChecksumPolicy::Fail));
+        assert_eq!(ChecksumPolicy::load("warn"), Ok(ChecksumPolicy::Warn));
+        assert_eq!(ChecksumPolicy::load("ignore"), Ok(ChecksumPolicy::Ignore));
+        assert!(
+            ChecksumPolicy::load("strict")
+                .unwrap_err()
+                .contains("[fail, warn, ignore]")
+        );
+    }
+
     #[test]
     fn update_policy_matches_expected_context_matrix() {
         let contexts = [
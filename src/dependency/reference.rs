@@ -31,6 +31,10 @@ impl DependencyReference {
     pub fn is_shaded(&self) -> bool {
         self.packaging == Some(PackagingType::Shade)
     }
+
+    pub fn is_nested(&self) -> bool {
+        self.packaging == Some(PackagingType::Nest)
+    }
 }
 
 impl fmt::Display for DependencyReference {
@@ -43,6 +47,7 @@ impl fmt::Display for DependencyReference {
 pub enum PackagingType {
     #[default]
     Shade,
+    Nest,
 }
 
 #[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
@@ -92,6 +97,7 @@ impl Display for PackagingType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             PackagingType::Shade => write!(f, "shade"),
+            PackagingType::Nest => write!(f, "nest"),
         }
     }
 }
@@ -102,6 +108,7 @@ impl TryFrom<String> for PackagingType {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         match value.to_lowercase().as_str() {
             "shade" => Ok(PackagingType::Shade),
+            "nest" => Ok(PackagingType::Nest),
             _ => Err(format!("No such packaging type \"{value}\"")),
         }
     }
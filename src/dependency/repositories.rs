@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+
+use toml::{Table, Value};
+
+use crate::config::toml_utils;
+use crate::dependency::{ChecksumPolicy, Dependency};
+
+pub const MAVEN_CENTRAL_URL: &str = "https://repo1.maven.org/maven2/";
+
+/// A Maven repository which dependencies can be resolved from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MavenRepository {
+    name: String,
+    url: String,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    /// Used by dependencies downloaded from this repository which set no `checksum_policy` of their
+    /// own.
+    checksum_policy: Option<ChecksumPolicy>,
+}
+
+impl MavenRepository {
+    pub fn new(name: &str, url: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            url: url.to_string(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            checksum_policy: None,
+        }
+    }
+
+    pub fn central() -> Self {
+        Self::new("central", MAVEN_CENTRAL_URL)
+    }
+
+    /// Loads `name = "url"` or `name = { url = ..., include = [...], exclude = [...],
+    /// checksum_policy = ... }`.
+    pub fn load(name: &str, value: &Value) -> Result<Self, String> {
+        if let Some(url) = value.as_str() {
+            return Ok(Self::new(name, url));
+        }
+
+        let Some(table) = value.as_table() else {
+            return Err(format!(
+                "Invalid [repositories].{name}: expected a URL string or a table, found {}.\nFix: write the repository as `{name} = \"https://repo.example/maven\"` or `{name} = {{ url = \"https://repo.example/maven\" }}`.",
+                value.type_str()
+            ));
+        };
+
+        Self::load_table(name, table)
+            .map_err(|error| format!("Invalid [repositories].{name}: {error}"))
+    }
+
+    fn load_table(name: &str, table: &Table) -> Result<Self, String> {
+        let url = toml_utils::read_string("url", table)?;
+        let include = toml_utils::read_optional_string_array("include", table)?.unwrap_or_default();
+        let exclude = toml_utils::read_optional_string_array("exclude", table)?.unwrap_or_default();
+        let checksum_policy = toml_utils::read_optional_string("checksum_policy", table)?
+            .map(|policy| ChecksumPolicy::load(&policy))
+            .transpose()?;
+
+        Ok(Self {
+            name: name.to_string(),
+            url,
+            include,
+            exclude,
+            checksum_policy,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn checksum_policy(&self) -> Option<ChecksumPolicy> {
+        self.checksum_policy
+    }
+
+    /// Whether `group_id:artifact_id` may be requested from this repository. Exclude filters win
+    /// over include filters, and a repository without include filters accepts everything else.
+    pub fn accepts(&self, group_id: &str, artifact_id: &str) -> bool {
+        let matches = |pattern: &String| coordinate_matches(pattern, group_id, artifact_id);
+
+        !self.exclude.iter().any(matches)
+            && (self.include.is_empty() || self.include.iter().any(matches))
+    }
+}
+
+/// Loads the `[repositories]` table in declaration order. Maven Central is used when the table is
+/// missing.
+pub fn load_repositories(value: Option<&Value>) -> Result<Vec<MavenRepository>, String> {
+    let Some(value) = value else {
+        return Ok(vec![MavenRepository::central()]);
+    };
+
+    let Some(table) = value.as_table() else {
+        return Err(format!(
+            "Invalid [repositories] section: expected a table, found {}.\nFix: name each repository, for example `[repositories]` followed by `central = \"{MAVEN_CENTRAL_URL}\"`.",
+            value.type_str()
+        ));
+    };
+
+    if table.is_empty() {
+        return Err(String::from(
+            "Invalid [repositories] section: no repositories are declared.\nFix: declare at least one repository, or remove the table to use Maven Central.",
+        ));
+    }
+
+    table
+        .iter()
+        .map(|(name, value)| MavenRepository::load(name, value))
+        .collect()
+}
+
+/// Gives every Maven dependency without its own `url` the repositories it resolves from: the one
+/// it pins with `repository = "name"`, or every project repository that accepts its coordinates.
+pub fn assign_repositories(
+    dependencies: &mut HashMap<String, Dependency>,
+    repositories: &[MavenRepository],
+) -> Result<(), String> {
+    for (name, dependency) in dependencies.iter_mut() {
+        let Dependency::FetchFromMaven {
+            group_id,
+            artifact_id,
+            repository,
+            repositories: candidates,
+            ..
+        } = dependency
+        else {
+            continue;
+        };
+
+        if !candidates.is_empty() {
+            continue;
+        }
+
+        *candidates = match repository {
+            Some(pinned) => {
+                let Some(found) = repositories.iter().find(|known| known.name() == pinned) else {
+                    return Err(format!(
+                        "Maven dependency `{name}` pins repository `{pinned}`, which is not declared in [repositories].\nFix: declare `{pinned}` under [repositories], or pin one of [{}].",
+                        repository_names(repositories)
+                    ));
+                };
+
+                if !found.accepts(group_id, artifact_id) {
+                    return Err(format!(
+                        "Maven dependency `{name}` pins repository `{pinned}`, but its include/exclude filters reject {group_id}:{artifact_id}.\nFix: adjust the filters of `{pinned}`, or pin a repository that accepts these coordinates."
+                    ));
+                }
+
+                vec![found.clone()]
+            }
+            None => repositories
+                .iter()
+                .filter(|known| known.accepts(group_id, artifact_id))
+                .cloned()
+                .collect(),
+        };
+
+        if candidates.is_empty() {
+            return Err(format!(
+                "No repository in [repositories] accepts Maven dependency `{name}` ({group_id}:{artifact_id}).\nFix: add an include filter for these coordinates to one of [{}], or declare a repository for them.",
+                repository_names(repositories)
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn repository_names(repositories: &[MavenRepository]) -> String {
+    repositories
+        .iter()
+        .map(MavenRepository::name)
+        .collect::<Vec<&str>>()
+        .join(", ")
+}
+
+/// Patterns are `group` or `group:artifact`, where `*` matches any run of characters.
+fn coordinate_matches(pattern: &str, group_id: &str, artifact_id: &str) -> bool {
+    match pattern.split_once(':') {
+        Some((group, artifact)) => {
+            wildcard_matches(group, group_id) && wildcard_matches(artifact, artifact_id)
+        }
+        None => wildcard_matches(pattern, group_id),
+    }
+}
+
+fn wildcard_matches(pattern: &str, value: &str) -> bool {
+    let Some((prefix, rest)) = pattern.split_once('*') else {
+        return pattern == value;
+    };
+
+    let Some(remaining) = value.strip_prefix(prefix) else {
+        return false;
+    };
+
+    (0..=remaining.len())
+        .filter(|index| remaining.is_char_boundary(*index))
+        .any(|index| wildcard_matches(rest, &remaining[index..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repositories(toml: &str) -> Result<Vec<MavenRepository>, String> {
+        load_repositories(Some(&Value::Table(toml.parse::<Table>().unwrap())))
+    }
+
+    fn maven_dependency(group_id: &str, repository: Option<&str>) -> Dependency {
+        Dependency::FetchFromMaven {
+            repository: repository.map(String::from),
+            repositories: Vec::new(),
+            group_id: String::from(group_id),
+            artifact_id: String::from("library"),
+            version: None,
+            classifier: None,
+            update_policy: Default::default(),
+            checksum_policy: None,
+            verify_signature: false,
+            javadoc: None,
+            attachments: Vec::new(),
+            bom: false,
+        }
+    }
+
+    fn assigned(
+        dependency: Dependency,
+        repositories: &[MavenRepository],
+    ) -> Result<Vec<String>, String> {
+        let mut dependencies = HashMap::from([(String::from("library"), dependency)]);
+        assign_repositories(&mut dependencies, repositories)?;
+
+        match dependencies.remove("library").unwrap() {
+            Dependency::FetchFromMaven { repositories, .. } => Ok(repositories
+                .iter()
+                .map(|repository| repository.name().to_string())
+                .collect()),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn filters_match_groups_and_artifacts_with_wildcards() {
+        let repository = MavenRepository {
+            name: String::from("internal"),
+            url: String::from("https://repo.corp"),
+            include: vec![
+                String::from("com.corp.*"),
+                String::from("org.example:corp-*"),
+            ],
+            exclude: vec![String::from("com.corp.public")],
+            checksum_policy: None,
+        };
+
+        assert!(repository.accepts("com.corp.tools", "anything"));
+        assert!(repository.accepts("org.example", "corp-client"));
+        assert!(!repository.accepts("org.example", "client"));
+        assert!(!repository.accepts("com.corp.public", "api"));
+        assert!(!repository.accepts("com.corp", "api"));
+    }
+
+    #[test]
+    fn loads_repositories_in_declaration_order() {
+        let repositories = repositories(
+            r#"
+            zeta = "https://zeta.example/maven"
+            alpha = { url = "https://alpha.example/maven", exclude = ["com.corp.*"], checksum_policy = "warn" }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(repositories[0].name(), "zeta");
+        assert_eq!(repositories[0].checksum_policy(), None);
+        assert_eq!(repositories[1].url(), "https://alpha.example/maven");
+        assert_eq!(
+            repositories[1].checksum_policy(),
+            Some(ChecksumPolicy::Warn)
+        );
+        assert_eq!(
+            load_repositories(None).unwrap(),
+            vec![MavenRepository::central()]
+        );
+    }
+
+    #[test]
+    fn assigns_accepting_repositories_or_the_pinned_one() {
+        let repositories = repositories(
+            r#"
+            internal = { url = "https://repo.corp", include = ["com.corp.*"] }
+            central = { url = "https://repo1.maven.org/maven2/", exclude = ["com.corp.*"] }
+            mirror = "https://mirror.example/maven"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            assigned(maven_dependency("com.corp.tools", None), &repositories).unwrap(),
+            vec!["internal", "mirror"]
+        );
+        assert_eq!(
+            assigned(maven_dependency("org.example", None), &repositories).unwrap(),
+            vec!["central", "mirror"]
+        );
+        assert_eq!(
+            assigned(
+                maven_dependency("org.example", Some("mirror")),
+                &repositories
+            )
+            .unwrap(),
+            vec!["mirror"]
+        );
+        assert!(
+            assigned(
+                maven_dependency("com.corp.tools", Some("central")),
+                &repositories
+            )
+            .unwrap_err()
+            .contains("filters reject com.corp.tools:library")
+        );
+        assert!(
+            assigned(
+                maven_dependency("org.example", Some("missing")),
+                &repositories
+            )
+            .unwrap_err()
+            .contains("not declared in [repositories]")
+        );
+    }
+}
@@ -1,18 +1,107 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::HashMap,
+    iter,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
 use regex::Regex;
 
 use crate::dependency::sources;
-use crate::dependency::{Dependency, UpdateContext, UpdatePolicy};
+use crate::dependency::{Dependency, UpdateContext, UpdatePolicy, policy};
 use crate::model::{Lockfile, LockfileArtifact};
+use crate::workspace::{
+    download::{self, ProgressReporter},
+    global_cache,
+};
 
 impl Dependency {
+    /// Resolves the dependency through its source. A locked artifact missing from the project cache
+    /// is first restored from the global cache, and locked results are added to it afterwards.
     pub fn resolve(
         &self,
         name: &str,
         environment: &HashMap<String, String>,
         regexes: &HashMap<&str, Regex>,
         context: ResolveContext<'_>,
+    ) -> Result<ResolvedDependency, String> {
+        let mut warnings: Vec<String> = Vec::new();
+        if let Some(locked) = context.locked_artifact() {
+            for (cache_path, hash) in cached_files(locked) {
+                let cache_path = Path::new(cache_path);
+                if !cache_path.exists()
+                    && let Err(error) = global_cache::restore(hash, cache_path)
+                {
+                    warnings.push(error);
+                }
+            }
+        }
+
+        let resolved = self.resolve_from_source(name, environment, regexes, context)?;
+        for lock in resolved.artifacts.iter().filter_map(|a| a.lock.as_ref()) {
+            for (cache_path, hash) in cached_files(lock) {
+                if let Err(error) = global_cache::store(Path::new(cache_path), hash) {
+                    warnings.push(error);
+                }
+            }
+        }
+
+        Ok(resolved.with_warnings(warnings))
+    }
+
+    /// The version an update would lock, asked of the dependency's repository without downloading
+    /// anything. Dependencies which are not versioned, or are not updated, keep their locked version.
+    pub fn resolve_version(&self, context: ResolveContext<'_>) -> Result<Option<String>, String> {
+        let locked_version = || {
+            context
+                .locked_artifact()
+                .and_then(LockfileArtifact::version)
+                .map(String::from)
+        };
+
+        match self {
+            Dependency::FetchFromMaven { bom: true, .. } => Ok(None),
+            Dependency::FetchFromMaven {
+                repositories,
+                group_id,
+                artifact_id,
+                version,
+                classifier,
+                update_policy,
+                ..
+            } if context.should_update(update_policy) => sources::maven::latest_version(
+                repositories,
+                group_id,
+                artifact_id,
+                version.as_ref(),
+                classifier.as_ref(),
+            )
+            .map(Some),
+            Dependency::FetchFromGithub {
+                username,
+                repository,
+                tag,
+                release_type,
+                update_policy,
+                ..
+            } if context.should_update(update_policy) => {
+                sources::github::latest_tag(username, repository, tag.as_ref(), release_type)
+                    .map(Some)
+            }
+            _ => Ok(locked_version()),
+        }
+    }
+
+    fn resolve_from_source(
+        &self,
+        name: &str,
+        environment: &HashMap<String, String>,
+        regexes: &HashMap<&str, Regex>,
+        context: ResolveContext<'_>,
     ) -> Result<ResolvedDependency, String> {
         match self {
             Dependency::LocalFile { path, .. } => {
@@ -25,23 +114,33 @@ impl Dependency {
             Dependency::FetchFromUrl {
                 url, update_policy, ..
             } => sources::url::resolve(name, url, update_policy, &context),
+            // The POM of a BOM is read to align versions before resolving, and it has no jar.
+            Dependency::FetchFromMaven { bom: true, .. } => {
+                Ok(ResolvedDependency::new(String::from(name), Vec::new()))
+            }
             Dependency::FetchFromMaven {
-                url,
+                repositories,
                 group_id,
                 artifact_id,
                 version,
                 classifier,
                 update_policy,
+                checksum_policy,
+                verify_signature,
+                attachments,
                 ..
             } => sources::maven::resolve(
                 sources::maven::MavenResolveRequest {
                     name,
-                    url,
+                    repositories,
                     group_id,
                     artifact_id,
                     version: version.as_ref(),
                     classifier: classifier.as_ref(),
                     update_policy,
+                    checksum_policy: *checksum_policy,
+                    verify_signature: *verify_signature,
+                    attachments,
                 },
                 &context,
             ),
@@ -52,6 +151,8 @@ impl Dependency {
                 tag,
                 release_type,
                 update_policy,
+                checksum_policy,
+                attachments,
                 ..
             } => sources::github::resolve(
                 sources::github::GithubResolveRequest {
@@ -62,6 +163,8 @@ impl Dependency {
                     tag: tag.as_ref(),
                     release_type,
                     update_policy,
+                    checksum_policy: *checksum_policy,
+                    attachments,
                 },
                 &context,
             ),
@@ -70,9 +173,20 @@ impl Dependency {
     }
 }
 
+/// The cache path and hash of a locked artifact and of each of its attachments.
+fn cached_files(lock: &LockfileArtifact) -> impl Iterator<Item = (&str, &str)> {
+    iter::once((lock.cache_path(), lock.hash())).chain(
+        lock.attachments()
+            .iter()
+            .map(|attachment| (attachment.cache_path(), attachment.hash())),
+    )
+}
+
+#[derive(Clone, Copy)]
 pub struct ResolveContext<'a> {
     update: UpdateContext,
     locked_artifact: Option<&'a LockfileArtifact>,
+    progress: Option<&'a ProgressReporter<'a>>,
 }
 
 impl<'a> ResolveContext<'a> {
@@ -80,6 +194,7 @@ impl<'a> ResolveContext<'a> {
         Self {
             update,
             locked_artifact: None,
+            progress: None,
         }
     }
 
@@ -90,6 +205,7 @@ impl<'a> ResolveContext<'a> {
         Self {
             update,
             locked_artifact: Some(locked_artifact),
+            progress: None,
         }
     }
 
@@ -104,13 +220,109 @@ impl<'a> ResolveContext<'a> {
         }
     }
 
+    /// Offline, or when locked outside of `wisteria update`, nothing is updated and dependencies
+    /// resolve from the lockfile and cache.
     pub fn should_update(&self, update_policy: &UpdatePolicy) -> bool {
-        update_policy.should_update(&self.update)
+        let locked = policy::locked() && !matches!(self.update, UpdateContext::Update);
+        !download::offline() && !locked && update_policy.should_update(&self.update)
     }
 
     pub fn locked_artifact(&self) -> Option<&'a LockfileArtifact> {
         self.locked_artifact
     }
+
+    pub fn with_progress(self, progress: &'a ProgressReporter<'a>) -> Self {
+        Self {
+            progress: Some(progress),
+            ..self
+        }
+    }
+
+    pub fn progress(&self) -> Option<&'a ProgressReporter<'a>> {
+        self.progress
+    }
+}
+
+/// A single dependency queued for [`resolve_concurrently`].
+pub struct ResolveJob<'a> {
+    pub name: &'a str,
+    pub dependency: &'a Dependency,
+    pub context: ResolveContext<'a>,
+}
+
+/// Progress of a job, identified by its index in the submitted jobs.
+pub enum ResolveEvent<'a> {
+    Started(usize),
+    /// Bytes downloaded so far, and the expected total when known.
+    Progress(usize, u64, Option<u64>),
+    Finished(usize, &'a Result<ResolvedDependency, String>),
+}
+
+enum WorkerMessage {
+    Started(usize),
+    Progress(usize, u64, Option<u64>),
+    Finished(usize, Result<ResolvedDependency, String>),
+}
+
+/// Resolves jobs on up to `limit` worker threads. Events are reported on the calling thread, and
+/// results keep the order of `jobs`.
+pub fn resolve_concurrently(
+    jobs: &[ResolveJob<'_>],
+    limit: usize,
+    environment: &HashMap<String, String>,
+    regexes: &HashMap<&str, Regex>,
+    mut on_event: impl FnMut(ResolveEvent<'_>),
+) -> Vec<Result<ResolvedDependency, String>> {
+    let mut results: Vec<Option<Result<ResolvedDependency, String>>> =
+        jobs.iter().map(|_| None).collect();
+    let next_job = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..limit.clamp(1, jobs.len().max(1)) {
+            let sender = sender.clone();
+            let next_job = &next_job;
+            scope.spawn(move || {
+                loop {
+                    let index = next_job.fetch_add(1, Ordering::Relaxed);
+                    let Some(job) = jobs.get(index) else {
+                        break;
+                    };
+
+                    let _ = sender.send(WorkerMessage::Started(index));
+                    let progress = |bytes: u64, total: Option<u64>| {
+                        let _ = sender.send(WorkerMessage::Progress(index, bytes, total));
+                    };
+                    let result = job.dependency.resolve(
+                        job.name,
+                        environment,
+                        regexes,
+                        job.context.with_progress(&progress),
+                    );
+                    let _ = sender.send(WorkerMessage::Finished(index, result));
+                }
+            });
+        }
+        drop(sender);
+
+        for message in receiver {
+            match message {
+                WorkerMessage::Started(index) => on_event(ResolveEvent::Started(index)),
+                WorkerMessage::Progress(index, bytes, total) => {
+                    on_event(ResolveEvent::Progress(index, bytes, total))
+                }
+                WorkerMessage::Finished(index, result) => {
+                    on_event(ResolveEvent::Finished(index, &result));
+                    results[index] = Some(result);
+                }
+            }
+        }
+    });
+
+    results
+        .into_iter()
+        .map(|result| result.expect("every resolve job reports a result"))
+        .collect()
 }
 
 /// A dependency which has one or more files that exists on disk.
@@ -118,21 +330,32 @@ impl<'a> ResolveContext<'a> {
 pub struct ResolvedDependency {
     pub name: String,
     pub artifacts: Vec<ResolvedArtifact>,
+    /// Problems that did not stop resolution, such as an unverified download.
+    pub warnings: Vec<String>,
 }
 
 impl ResolvedDependency {
     pub fn new(name: String, artifacts: Vec<ResolvedArtifact>) -> Self {
-        Self { name, artifacts }
+        Self {
+            name,
+            artifacts,
+            warnings: Vec::new(),
+        }
     }
 
     pub fn from_paths(name: String, paths: Vec<PathBuf>) -> Self {
-        Self {
+        Self::new(
             name,
-            artifacts: paths
+            paths
                 .into_iter()
                 .map(|path| ResolvedArtifact::new(path, None))
                 .collect(),
-        }
+        )
+    }
+
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        self.warnings.extend(warnings);
+        self
     }
 
     pub fn paths(&self) -> impl Iterator<Item = &PathBuf> {
@@ -153,3 +376,73 @@ impl ResolvedArtifact {
         Self { path, lock }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::cli::commands::envvar_regexes;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn resolve_concurrently_keeps_job_order_and_reports_each_job() {
+        let temp = TempDir::new("resolver-concurrent");
+        let dependencies: Vec<(String, Dependency)> = (0..6)
+            .map(|index| {
+                let path = temp.path().join(format!("library-{index}.jar"));
+                if index != 3 {
+                    fs::write(&path, "jar").unwrap();
+                }
+
+                (
+                    format!("library-{index}"),
+                    Dependency::LocalFile {
+                        path: path.to_string_lossy().to_string(),
+                        javadoc: None,
+                    },
+                )
+            })
+            .collect();
+        let jobs: Vec<ResolveJob<'_>> = dependencies
+            .iter()
+            .map(|(name, dependency)| ResolveJob {
+                name,
+                dependency,
+                context: ResolveContext::new(UpdateContext::ResolveOnly),
+            })
+            .collect();
+
+        let mut started: Vec<usize> = Vec::new();
+        let mut finished: Vec<usize> = Vec::new();
+        let results =
+            resolve_concurrently(
+                &jobs,
+                3,
+                &HashMap::new(),
+                &envvar_regexes(),
+                |event| match event {
+                    ResolveEvent::Started(index) => started.push(index),
+                    ResolveEvent::Progress(..) => {}
+                    ResolveEvent::Finished(index, _) => {
+                        assert!(started.contains(&index));
+                        finished.push(index);
+                    }
+                },
+            );
+
+        started.sort_unstable();
+        finished.sort_unstable();
+        assert_eq!(started, (0..6).collect::<Vec<_>>());
+        assert_eq!(finished, (0..6).collect::<Vec<_>>());
+        for (index, result) in results.iter().enumerate() {
+            match result {
+                Ok(resolved) => assert_eq!(resolved.name, format!("library-{index}")),
+                Err(error) => {
+                    assert_eq!(index, 3);
+                    assert!(error.contains("does not exist"));
+                }
+            }
+        }
+    }
+}
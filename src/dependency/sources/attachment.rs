@@ -0,0 +1,98 @@
+use std::path::Path;
+
+use crate::dependency::resolver::{ResolveContext, ResolvedDependency};
+use crate::dependency::sources::checksum;
+use crate::dependency::{AttachmentKind, ChecksumPolicy, cache};
+use crate::model::{LockfileArtifact, LockfileAttachment};
+use crate::workspace::checksum::Checksum;
+use crate::workspace::files;
+
+pub struct AttachmentRequest<'a> {
+    pub name: &'a str,
+    pub kinds: &'a [AttachmentKind],
+    /// Classifier of the main artifact, which attachments replace with their own.
+    pub classifier: Option<&'a String>,
+    pub checksum_policy: ChecksumPolicy,
+}
+
+/// Downloads the requested attachments of each locked artifact which are not cached yet, and
+/// records them on its lock. Locks which already record exactly the requested, cached attachments
+/// are left alone. Attachments only help IDEs, so one which cannot be fetched is reported as a
+/// warning rather than failing the dependency.
+pub fn attach(
+    mut resolved: ResolvedDependency,
+    request: AttachmentRequest<'_>,
+    mut published: impl FnMut(&str) -> Option<Checksum>,
+    context: &ResolveContext<'_>,
+) -> ResolvedDependency {
+    let mut warnings: Vec<String> = Vec::new();
+    for artifact in &mut resolved.artifacts {
+        let Some(lock) = artifact.lock.take() else {
+            continue;
+        };
+
+        if attachments_cached(&lock, request.kinds) {
+            artifact.lock = Some(lock);
+            continue;
+        }
+
+        let mut attachments: Vec<LockfileAttachment> = Vec::new();
+        for kind in request.kinds {
+            match fetch_attachment(&lock, *kind, &request, &mut published, context) {
+                Ok((attachment, download_warnings)) => {
+                    attachments.push(attachment);
+                    warnings.extend(download_warnings);
+                }
+                Err(warning) => warnings.push(warning),
+            }
+        }
+        artifact.lock = Some(lock.with_attachments(attachments));
+    }
+
+    resolved.with_warnings(warnings)
+}
+
+fn attachments_cached(lock: &LockfileArtifact, kinds: &[AttachmentKind]) -> bool {
+    lock.attachments().len() == kinds.len()
+        && kinds.iter().all(|kind| {
+            lock.attachment(*kind)
+                .is_some_and(|attachment| Path::new(attachment.cache_path()).exists())
+        })
+}
+
+fn fetch_attachment(
+    lock: &LockfileArtifact,
+    kind: AttachmentKind,
+    request: &AttachmentRequest<'_>,
+    published: &mut impl FnMut(&str) -> Option<Checksum>,
+    context: &ResolveContext<'_>,
+) -> Result<(LockfileAttachment, Vec<String>), String> {
+    let suffix = format!("-{}.jar", kind.classifier());
+    let url = cache::jar_sibling(lock.fetch_url(), request.classifier, &suffix);
+    let filepath = cache::jar_sibling(lock.cache_path(), request.classifier, &suffix);
+
+    let mut warnings: Vec<String> = Vec::new();
+    if !Path::new(&filepath).exists() {
+        let checksum = match request.checksum_policy {
+            ChecksumPolicy::Ignore => None,
+            _ => published(&url),
+        };
+        warnings = checksum::download_verified(
+            &format!("{} {kind}", request.name),
+            &url,
+            &filepath,
+            checksum,
+            request.checksum_policy,
+            context,
+        )
+        .map_err(|e| {
+            format!(
+                "The {kind} jar of \"{}\" was not attached: {e}",
+                request.name
+            )
+        })?;
+    }
+
+    let hash = files::generate_sha2_for_file(Path::new(&filepath))?;
+    Ok((LockfileAttachment::new(kind, url, filepath, hash), warnings))
+}
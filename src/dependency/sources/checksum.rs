@@ -0,0 +1,46 @@
+use std::path::Path;
+
+use crate::dependency::ChecksumPolicy;
+use crate::dependency::resolver::ResolveContext;
+use crate::workspace::checksum::Checksum;
+use crate::workspace::download;
+
+/// Downloads `url` to `filepath` and checks it against the checksum its repository published.
+/// Returns warnings for anything the policy lets through.
+pub fn download_verified(
+    name: &str,
+    url: &str,
+    filepath: &str,
+    published: Option<Checksum>,
+    policy: ChecksumPolicy,
+    context: &ResolveContext<'_>,
+) -> Result<Vec<String>, String> {
+    let download = |checksum: Option<&Checksum>| {
+        download::download_silent(
+            name.to_string(),
+            url.to_string(),
+            filepath.to_string(),
+            checksum,
+            context.progress(),
+        )
+    };
+
+    match (policy, published) {
+        (ChecksumPolicy::Ignore, _) => download(None).map(|_| Vec::new()),
+        (_, None) => {
+            download(None)?;
+            Ok(vec![format!(
+                "No checksum is published for {url}, so the download of \"{name}\" was not verified."
+            )])
+        }
+        (ChecksumPolicy::Fail, Some(checksum)) => download(Some(&checksum)).map(|_| Vec::new()),
+        (ChecksumPolicy::Warn, Some(checksum)) => {
+            download(None)?;
+            Ok(checksum
+                .verify(Path::new(filepath))
+                .err()
+                .into_iter()
+                .collect())
+        }
+    }
+}
@@ -1,12 +1,14 @@
 use std::path::PathBuf;
 
-use reqwest::blocking::Client;
-use serde::Deserialize;
+use serde::{Deserialize, de::DeserializeOwned};
 
 use crate::dependency::cache;
 use crate::dependency::resolver::{ResolveContext, ResolvedArtifact, ResolvedDependency};
-use crate::dependency::{GithubReleaseType, UpdatePolicy};
+use crate::dependency::sources::attachment::{self, AttachmentRequest};
+use crate::dependency::sources::checksum;
+use crate::dependency::{AttachmentKind, ChecksumPolicy, GithubReleaseType, UpdatePolicy};
 use crate::model::LockfileArtifact;
+use crate::workspace::checksum::Checksum;
 use crate::workspace::{download, files, paths};
 
 const LOCKFILE_SOURCE: &str = "github";
@@ -19,6 +21,8 @@ pub struct GithubResolveRequest<'a> {
     pub tag: Option<&'a String>,
     pub release_type: &'a GithubReleaseType,
     pub update_policy: &'a UpdatePolicy,
+    pub checksum_policy: ChecksumPolicy,
+    pub attachments: &'a [AttachmentKind],
 }
 
 pub fn resolve(
@@ -26,30 +30,78 @@ pub fn resolve(
     context: &ResolveContext<'_>,
 ) -> Result<ResolvedDependency, String> {
     if !context.should_update(request.update_policy) {
-        if let Some(locked_artifact) = context.locked_artifact() {
-            return resolve_locked_artifact(request.name, locked_artifact);
-        }
-
-        return resolve_without_update(
-            request.name,
-            request.username,
-            request.repository,
-            request.asset,
-            request.tag,
-        );
+        let resolved = match context.locked_artifact() {
+            Some(locked_artifact) => resolve_locked_artifact(request.name, locked_artifact)?,
+            None => resolve_without_update(
+                request.name,
+                request.username,
+                request.repository,
+                request.asset,
+                request.tag,
+            )?,
+        };
+        return Ok(attach_to_cached_artifact(resolved, &request, context));
     }
 
-    let resolved_tag = match request.tag {
-        Some(tag) => tag.clone(),
-        None => resolve_latest_tag(request.username, request.repository, request.release_type)?,
+    let (resolved_tag, release) = match request.tag {
+        Some(tag) => (tag.clone(), None),
+        None => {
+            let release =
+                resolve_latest_release(request.username, request.repository, request.release_type)?;
+            (release.tag_name.clone(), Some(release))
+        }
     };
 
-    resolve_updated_artifact(
-        request.name,
-        request.username,
-        request.repository,
-        request.asset,
-        &resolved_tag,
+    resolve_updated_artifact(&request, &resolved_tag, release, context)
+}
+
+/// The tag an update would lock, without downloading the asset.
+pub fn latest_tag(
+    username: &str,
+    repository: &str,
+    tag: Option<&String>,
+    release_type: &GithubReleaseType,
+) -> Result<String, String> {
+    match tag {
+        Some(tag) => Ok(tag.clone()),
+        None => resolve_latest_release(username, repository, release_type)
+            .map(|release| release.tag_name),
+    }
+}
+
+/// Fetches attachments requested since the asset was locked or cached, checked against the
+/// digests of the release it came from.
+fn attach_to_cached_artifact(
+    resolved: ResolvedDependency,
+    request: &GithubResolveRequest<'_>,
+    context: &ResolveContext<'_>,
+) -> ResolvedDependency {
+    let tag: Option<String> = resolved
+        .artifacts
+        .iter()
+        .filter_map(|artifact| artifact.lock.as_ref())
+        .find_map(LockfileArtifact::version)
+        .map(String::from);
+
+    let mut release: Option<GithubRelease> = None;
+    attachment::attach(
+        resolved,
+        AttachmentRequest {
+            name: request.name,
+            kinds: request.attachments,
+            classifier: None,
+            checksum_policy: request.checksum_policy,
+        },
+        |url| {
+            if release.is_none() {
+                release =
+                    release_for_tag(request.username, request.repository, tag.as_deref()?).ok();
+            }
+            release
+                .as_ref()
+                .and_then(|release| release.asset_checksum(url.rsplit('/').next().unwrap_or(url)))
+        },
+        context,
     )
 }
 
@@ -81,25 +133,58 @@ fn resolve_without_update(
 }
 
 fn resolve_updated_artifact(
-    name: &str,
-    username: &str,
-    repository: &str,
-    asset: &str,
+    request: &GithubResolveRequest<'_>,
     resolved_tag: &str,
+    release: Option<GithubRelease>,
+    context: &ResolveContext<'_>,
 ) -> Result<ResolvedDependency, String> {
-    let filepath = cache::github_cache_path(username, repository, resolved_tag);
+    let filepath = cache::github_cache_path(request.username, request.repository, resolved_tag);
     let path = PathBuf::from(&filepath);
     paths::ensure_parents(&filepath)?;
 
-    let asset = release_asset_filename(asset);
-    let full_url = github_release_url(username, repository, resolved_tag, &asset);
+    let asset = release_asset_filename(request.asset);
+    let full_url = github_release_url(request.username, request.repository, resolved_tag, &asset);
 
-    if path.exists() {
-        return resolve_cached_artifact(name, path, resolved_tag, full_url, filepath);
-    }
+    // A release that cannot be looked up is treated as one without published digests.
+    let mut release = release;
+    let mut published = |asset: &str| {
+        if release.is_none() {
+            release = release_for_tag(request.username, request.repository, resolved_tag).ok();
+        }
+        release
+            .as_ref()
+            .and_then(|release| release.asset_checksum(asset))
+    };
 
-    download::download_silent(repository.to_string(), full_url.clone(), filepath.clone())?;
-    resolve_cached_artifact(name, path, resolved_tag, full_url, filepath)
+    let warnings = if path.exists() {
+        Vec::new()
+    } else {
+        let checksum = match request.checksum_policy {
+            ChecksumPolicy::Ignore => None,
+            _ => published(&asset),
+        };
+        checksum::download_verified(
+            request.repository,
+            &full_url,
+            &filepath,
+            checksum,
+            request.checksum_policy,
+            context,
+        )?
+    };
+
+    let resolved = resolve_cached_artifact(request.name, path, resolved_tag, full_url, filepath)?;
+    Ok(attachment::attach(
+        resolved.with_warnings(warnings),
+        AttachmentRequest {
+            name: request.name,
+            kinds: request.attachments,
+            classifier: None,
+            checksum_policy: request.checksum_policy,
+        },
+        |url| published(url.rsplit('/').next().unwrap_or(url)),
+        context,
+    ))
 }
 
 fn resolve_locked_artifact(
@@ -183,21 +268,74 @@ struct GithubRelease {
     tag_name: String,
     prerelease: bool,
     draft: bool,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
 }
 
-fn resolve_latest_tag(
+#[derive(Debug, Deserialize)]
+struct GithubAsset {
+    name: String,
+    digest: Option<String>,
+}
+
+impl GithubRelease {
+    fn asset_checksum(&self, asset: &str) -> Option<Checksum> {
+        self.assets
+            .iter()
+            .find(|candidate| candidate.name == asset)
+            .and_then(|candidate| candidate.digest.as_deref())
+            .and_then(Checksum::from_prefixed)
+    }
+}
+
+fn resolve_latest_release(
     username: &str,
     repository: &str,
     release_type: &GithubReleaseType,
-) -> Result<String, String> {
-    let url = format!("https://api.github.com/repos/{username}/{repository}/releases?per_page=100");
-    let client = Client::builder()
-        .user_agent(download::USER_AGENT)
-        .build()
-        .map_err(|e| format!("Could not create GitHub API client: {e}"))?;
-
-    let response = client
-        .get(&url)
+) -> Result<GithubRelease, String> {
+    let releases: Vec<GithubRelease> = github_api_get(
+        &format!("https://api.github.com/repos/{username}/{repository}/releases?per_page=100"),
+        username,
+        repository,
+    )?;
+
+    select_latest_release(releases, release_type)
+        .ok_or_else(|| missing_release_message(username, repository, release_type))
+}
+
+/// Lists the tags of every release of the given type, newest first, skipping drafts.
+pub fn available_versions(
+    username: &str,
+    repository: &str,
+    release_type: &GithubReleaseType,
+) -> Result<Vec<String>, String> {
+    let releases: Vec<GithubRelease> = github_api_get(
+        &format!("https://api.github.com/repos/{username}/{repository}/releases?per_page=100"),
+        username,
+        repository,
+    )?;
+
+    Ok(releases
+        .into_iter()
+        .filter(|release| release_type_matches(release, release_type))
+        .map(|release| release.tag_name)
+        .collect())
+}
+
+fn release_for_tag(username: &str, repository: &str, tag: &str) -> Result<GithubRelease, String> {
+    github_api_get(
+        &format!("https://api.github.com/repos/{username}/{repository}/releases/tags/{tag}"),
+        username,
+        repository,
+    )
+}
+
+fn github_api_get<T: DeserializeOwned>(
+    url: &str,
+    username: &str,
+    repository: &str,
+) -> Result<T, String> {
+    let response = download::request(url)?
         .send()
         .map_err(|e| format!("Could not reach GitHub API at URL {url}: {e}"))?;
 
@@ -211,27 +349,26 @@ fn resolve_latest_tag(
     let text = response.text().map_err(|e| {
         format!("Failed to read GitHub API response for {username}/{repository}: {e}")
     })?;
-    let releases: Vec<GithubRelease> = serde_json::from_str(&text).map_err(|e| {
-        format!("Could not decode GitHub releases for {username}/{repository}: {e}")
-    })?;
-
-    select_latest_tag(&releases, release_type)
-        .ok_or_else(|| missing_release_message(username, repository, release_type))
+    serde_json::from_str(&text)
+        .map_err(|e| format!("Could not decode GitHub releases for {username}/{repository}: {e}"))
 }
 
-fn select_latest_tag(
-    releases: &[GithubRelease],
+fn select_latest_release(
+    releases: Vec<GithubRelease>,
     release_type: &GithubReleaseType,
-) -> Option<String> {
+) -> Option<GithubRelease> {
     releases
-        .iter()
-        .filter(|release| !release.draft)
-        .find(|release| match release_type {
+        .into_iter()
+        .find(|release| release_type_matches(release, release_type))
+}
+
+fn release_type_matches(release: &GithubRelease, release_type: &GithubReleaseType) -> bool {
+    !release.draft
+        && match release_type {
             GithubReleaseType::Release => !release.prerelease,
             GithubReleaseType::Prerelease => release.prerelease,
             GithubReleaseType::Any => true,
-        })
-        .map(|release| release.tag_name.clone())
+        }
 }
 
 fn missing_release_message(
@@ -262,6 +399,7 @@ fn release_asset_filename(asset: &str) -> String {
 mod tests {
     use std::fs;
 
+    use crate::workspace::checksum::ChecksumAlgorithm;
     use crate::workspace::files;
     use crate::{
         dependency::UpdateContext,
@@ -279,6 +417,7 @@ mod tests {
             tag_name: tag_name.to_string(),
             prerelease,
             draft,
+            assets: Vec::new(),
         }
     }
 
@@ -290,7 +429,9 @@ mod tests {
         ];
 
         assert_eq!(
-            select_latest_tag(&releases, &GithubReleaseType::Release).as_deref(),
+            select_latest_release(releases, &GithubReleaseType::Release)
+                .map(|release| release.tag_name)
+                .as_deref(),
             Some("v1.9.0")
         );
     }
@@ -303,7 +444,9 @@ mod tests {
         ];
 
         assert_eq!(
-            select_latest_tag(&releases, &GithubReleaseType::Prerelease).as_deref(),
+            select_latest_release(releases, &GithubReleaseType::Prerelease)
+                .map(|release| release.tag_name)
+                .as_deref(),
             Some("v2.0.0-beta.1")
         );
     }
@@ -316,11 +459,38 @@ mod tests {
         ];
 
         assert_eq!(
-            select_latest_tag(&releases, &GithubReleaseType::Any).as_deref(),
+            select_latest_release(releases, &GithubReleaseType::Any)
+                .map(|release| release.tag_name)
+                .as_deref(),
             Some("v1.9.0")
         );
     }
 
+    #[test]
+    fn asset_checksum_reads_the_matching_asset_digest() {
+        let release: GithubRelease = serde_json::from_str(
+            r#"{
+                "tag_name": "v1.0.0",
+                "prerelease": false,
+                "draft": false,
+                "assets": [
+                    { "name": "Other.jar", "digest": null },
+                    { "name": "Repository.jar", "digest": "sha256:ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad" }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            release
+                .asset_checksum("Repository.jar")
+                .map(|checksum| checksum.algorithm()),
+            Some(ChecksumAlgorithm::Sha256)
+        );
+        assert_eq!(release.asset_checksum("Other.jar"), None);
+        assert_eq!(release.asset_checksum("Missing.jar"), None);
+    }
+
     #[test]
     fn appends_jar_extension_only_when_missing() {
         assert_eq!(release_asset_filename("library"), "library.jar");
@@ -350,6 +520,8 @@ mod tests {
                     tag: Some(&tag),
                     release_type: &GithubReleaseType::Release,
                     update_policy: &UpdatePolicy::Never,
+                    checksum_policy: ChecksumPolicy::Fail,
+                    attachments: &[],
                 },
                 &ResolveContext::new(UpdateContext::ResolveOnly),
             )
@@ -401,6 +573,8 @@ mod tests {
                     tag: None,
                     release_type: &GithubReleaseType::Release,
                     update_policy: &UpdatePolicy::Never,
+                    checksum_policy: ChecksumPolicy::Fail,
+                    attachments: &[],
                 },
                 &ResolveContext::with_locked_artifact(UpdateContext::ResolveOnly, &lock),
             )
@@ -437,6 +611,8 @@ mod tests {
                     tag: None,
                     release_type: &GithubReleaseType::Release,
                     update_policy: &UpdatePolicy::Never,
+                    checksum_policy: ChecksumPolicy::Fail,
+                    attachments: &[],
                 },
                 &ResolveContext::with_locked_artifact(UpdateContext::ResolveOnly, &lock),
             )
@@ -457,6 +633,8 @@ mod tests {
                 tag: None,
                 release_type: &GithubReleaseType::Release,
                 update_policy: &UpdatePolicy::Never,
+                checksum_policy: ChecksumPolicy::Fail,
+                attachments: &[],
             },
             &ResolveContext::new(UpdateContext::ResolveOnly),
         )
@@ -1,24 +1,33 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, fs, path::PathBuf};
 
 use reqwest::blocking::Client;
 
-use crate::dependency::UpdatePolicy;
 use crate::dependency::cache;
 use crate::dependency::resolver::{ResolveContext, ResolvedArtifact, ResolvedDependency};
+use crate::dependency::sources::attachment::{self, AttachmentRequest};
+use crate::dependency::sources::checksum;
+use crate::dependency::version::VersionRange;
+use crate::dependency::{AttachmentKind, ChecksumPolicy, MavenRepository, UpdatePolicy};
+use crate::maven::pom::{Pom, PomLicense};
 use crate::maven::repository::{self, ArtifactVersion};
 use crate::model::LockfileArtifact;
-use crate::workspace::{download, files, paths};
+use crate::workspace::checksum::{Checksum, ChecksumAlgorithm};
+use crate::workspace::{credentials, download, files, paths, settings, signature};
 
 const LOCKFILE_SOURCE: &str = "maven";
 
 pub struct MavenResolveRequest<'a> {
     pub name: &'a str,
-    pub url: &'a str,
+    pub repositories: &'a [MavenRepository],
     pub group_id: &'a str,
     pub artifact_id: &'a str,
     pub version: Option<&'a String>,
     pub classifier: Option<&'a String>,
     pub update_policy: &'a UpdatePolicy,
+    /// Set by the dependency itself; otherwise the policy of the repository it is downloaded from.
+    pub checksum_policy: Option<ChecksumPolicy>,
+    pub verify_signature: bool,
+    pub attachments: &'a [AttachmentKind],
 }
 
 pub fn resolve(
@@ -26,44 +35,29 @@ pub fn resolve(
     context: &ResolveContext<'_>,
 ) -> Result<ResolvedDependency, String> {
     if !context.should_update(request.update_policy) {
-        if let Some(locked_artifact) = context.locked_artifact() {
-            return resolve_locked_artifact(request.name, locked_artifact);
-        }
-
-        return resolve_without_update(request);
+        let resolved = match context.locked_artifact() {
+            Some(locked_artifact) => resolve_locked_artifact(request.name, locked_artifact)?,
+            None => resolve_without_update(&request)?,
+        };
+        return Ok(attach_to_cached_artifact(resolved, &request, context));
     }
 
-    let target_version = match request.version {
-        Some(version) => match version.as_str() {
-            "latest" => ArtifactVersion::Latest,
-            "release" => ArtifactVersion::Release,
-            _ => ArtifactVersion::Version {
-                version: version.to_string(),
-            },
-        },
-        None => ArtifactVersion::Latest,
-    };
+    let target_version = ArtifactVersion::load(request.version)?;
 
-    let client: Client = Client::builder()
-        .user_agent(download::USER_AGENT)
-        .build()
-        .map_err(|e| format!("Could not create Maven repository client: {e}"))?;
+    let client = download::client()?;
 
-    let version = match repository::get_version(
-        &client,
-        request.url,
+    let (found, version) = find_version(
+        client,
+        request.repositories,
         request.group_id,
         request.artifact_id,
         request.classifier,
         &target_version,
-    ) {
-        Ok(t) => t,
-        Err(e) => {
-            return Err(format!(
-                "Failed to get Maven repository version information: {e}"
-            ));
-        }
-    };
+    )?;
+    let checksum_policy = request
+        .checksum_policy
+        .or(found.checksum_policy())
+        .unwrap_or_default();
 
     let filepath = cache::maven_cache_path(
         request.group_id,
@@ -77,8 +71,8 @@ pub fn resolve(
     paths::ensure_parents(&filepath)?;
 
     let target_url = match repository::get_artifact(
-        &client,
-        request.url,
+        client,
+        found.url(),
         request.group_id,
         request.artifact_id,
         request.classifier,
@@ -88,25 +82,404 @@ pub fn resolve(
         Err(e) => return Err(format!("Failed to get Maven repository artifact: {e}")),
     };
 
-    if path.exists() {
-        return resolve_cached_artifact(
-            request.name,
-            path,
-            version.0.as_str(),
-            target_url,
-            filepath,
-        );
+    let warnings = if path.exists() {
+        Vec::new()
+    } else {
+        let published = match checksum_policy {
+            ChecksumPolicy::Ignore => None,
+            _ => published_checksum(client, &target_url),
+        };
+        checksum::download_verified(
+            request.artifact_id,
+            &target_url,
+            &filepath,
+            published,
+            checksum_policy,
+            context,
+        )?
+    };
+
+    let signing_key = request
+        .verify_signature
+        .then(|| verify_artifact_signature(request.name, &target_url, &filepath))
+        .transpose()?;
+    let resolved = resolve_cached_artifact(
+        request.name,
+        path,
+        version.0.as_str(),
+        target_url,
+        filepath,
+        signing_key,
+    )?;
+    Ok(attachment::attach(
+        resolved.with_warnings(warnings),
+        AttachmentRequest {
+            name: request.name,
+            kinds: request.attachments,
+            classifier: request.classifier,
+            checksum_policy,
+        },
+        |url| published_checksum(client, url),
+        context,
+    ))
+}
+
+/// Asks each candidate repository in order for the version, and returns the first repository that
+/// has it along with the resolved version.
+fn find_version<'a>(
+    client: &Client,
+    repositories: &'a [MavenRepository],
+    group_id: &str,
+    artifact_id: &str,
+    classifier: Option<&String>,
+    target_version: &ArtifactVersion,
+) -> Result<(&'a MavenRepository, (String, Option<String>)), String> {
+    let mut failures: Vec<String> = Vec::new();
+    for candidate in repositories {
+        match repository::get_version(
+            client,
+            candidate.url(),
+            group_id,
+            artifact_id,
+            classifier,
+            target_version,
+        ) {
+            Ok(version) => return Ok((candidate, version)),
+            Err(e) => failures.push(format!("{} ({}): {e}", candidate.name(), candidate.url())),
+        }
     }
 
-    download::download_silent(
-        request.artifact_id.to_string(),
-        target_url.clone(),
-        filepath.clone(),
+    Err(format!(
+        "Failed to get Maven repository version information for {group_id}:{artifact_id} from any repository:\n- {}",
+        failures.join("\n- ")
+    ))
+}
+
+/// The version an update would lock, read from repository metadata without downloading the
+/// artifact.
+pub fn latest_version(
+    repositories: &[MavenRepository],
+    group_id: &str,
+    artifact_id: &str,
+    version: Option<&String>,
+    classifier: Option<&String>,
+) -> Result<String, String> {
+    let target_version = ArtifactVersion::load(version)?;
+    let (_, (version, _)) = find_version(
+        download::client()?,
+        repositories,
+        group_id,
+        artifact_id,
+        classifier,
+        &target_version,
     )?;
-    resolve_cached_artifact(request.name, path, version.0.as_str(), target_url, filepath)
+    Ok(version)
+}
+
+/// Lists the published versions of an artifact from the first candidate repository that has them.
+pub fn available_versions(
+    repositories: &[MavenRepository],
+    group_id: &str,
+    artifact_id: &str,
+) -> Result<Vec<String>, String> {
+    let client = download::client()?;
+    let mut failures: Vec<String> = Vec::new();
+    for candidate in repositories {
+        match repository::get_versions(client, candidate.url(), group_id, artifact_id) {
+            Ok(versions) => return Ok(versions),
+            Err(e) => failures.push(format!("{} ({}): {e}", candidate.name(), candidate.url())),
+        }
+    }
+
+    Err(format!(
+        "Failed to get Maven repository version information for {group_id}:{artifact_id} from any repository:\n- {}",
+        failures.join("\n- ")
+    ))
+}
+
+/// Parent POMs followed when looking for licenses, before giving up.
+const MAX_PARENT_DEPTH: usize = 8;
+
+/// Licenses declared by the POM of a locked artifact, or inherited from its nearest parent POM
+/// which declares any. POMs are cached beside their jars, so this only reaches the network once.
+pub fn locked_licenses(
+    repositories: &[MavenRepository],
+    classifier: Option<&String>,
+    locked_artifact: &LockfileArtifact,
+) -> Result<Vec<PomLicense>, String> {
+    let mut pom = read_pom(
+        &cache::jar_sibling(locked_artifact.cache_path(), classifier, ".pom"),
+        &[cache::jar_sibling(
+            locked_artifact.fetch_url(),
+            classifier,
+            ".pom",
+        )],
+    )?;
+
+    for _ in 0..MAX_PARENT_DEPTH {
+        if !pom.licenses().is_empty() {
+            break;
+        }
+        let Some(parent) = pom.parent() else {
+            break;
+        };
+
+        let urls: Vec<String> = repositories
+            .iter()
+            .map(|repository| {
+                repository::pom_url(
+                    repository.url(),
+                    &parent.group_id,
+                    &parent.artifact_id,
+                    &parent.version,
+                )
+            })
+            .collect();
+        pom = read_pom(
+            &cache::maven_pom_cache_path(&parent.group_id, &parent.artifact_id, &parent.version),
+            &urls,
+        )?;
+    }
+
+    Ok(pom.licenses().to_vec())
+}
+
+/// BOMs imported through each other, followed before giving up.
+const MAX_IMPORT_DEPTH: usize = 8;
+
+/// Property references replaced in one POM value, before giving up.
+const MAX_INTERPOLATIONS: usize = 32;
+
+/// A version a BOM manages for one artifact.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManagedVersion {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: String,
+}
+
+/// The versions the `<dependencyManagement>` of a BOM and its parent POMs manage, including those
+/// of the BOMs it imports. Entries come in the order Maven gives them precedence: the BOM's own
+/// first, then its parents', then imported ones. With `cached_only`, nothing is downloaded, and
+/// `None` is returned when one of the POMs is missing from the cache.
+pub fn managed_versions(
+    repositories: &[MavenRepository],
+    group_id: &str,
+    artifact_id: &str,
+    version: &str,
+    cached_only: bool,
+) -> Result<Option<Vec<ManagedVersion>>, String> {
+    managed_versions_at_depth(repositories, group_id, artifact_id, version, cached_only, 0)
+}
+
+fn managed_versions_at_depth(
+    repositories: &[MavenRepository],
+    group_id: &str,
+    artifact_id: &str,
+    version: &str,
+    cached_only: bool,
+    depth: usize,
+) -> Result<Option<Vec<ManagedVersion>>, String> {
+    if depth > MAX_IMPORT_DEPTH {
+        return Err(format!(
+            "BOM {group_id}:{artifact_id}:{version} is imported more than {MAX_IMPORT_DEPTH} levels deep"
+        ));
+    }
+
+    let read = |group_id: &str, artifact_id: &str, version: &str| {
+        let cache_path = cache::maven_pom_cache_path(group_id, artifact_id, version);
+        if cached_only && !PathBuf::from(&cache_path).is_file() {
+            return Ok(None);
+        }
+
+        let urls: Vec<String> = if cached_only {
+            Vec::new()
+        } else {
+            repositories
+                .iter()
+                .map(|repository| {
+                    repository::pom_url(repository.url(), group_id, artifact_id, version)
+                })
+                .collect()
+        };
+        read_pom(&cache_path, &urls).map(Some)
+    };
+
+    let Some(pom) = read(group_id, artifact_id, version)? else {
+        return Ok(None);
+    };
+    let mut chain = vec![pom];
+    while chain.len() <= MAX_PARENT_DEPTH
+        && let Some(parent) = chain.last().and_then(Pom::parent)
+    {
+        let Some(parent) = read(&parent.group_id, &parent.artifact_id, &parent.version)? else {
+            return Ok(None);
+        };
+        chain.push(parent);
+    }
+
+    // Closer POMs override the properties of their parents.
+    let mut properties: HashMap<String, String> = HashMap::new();
+    for pom in chain.iter().rev() {
+        properties.extend(pom.properties().clone());
+    }
+    for (key, value) in [
+        ("project.groupId", group_id),
+        ("project.artifactId", artifact_id),
+        ("project.version", version),
+    ] {
+        properties.insert(String::from(key), String::from(value));
+    }
+
+    let mut managed: Vec<ManagedVersion> = Vec::new();
+    let mut imports: Vec<ManagedVersion> = Vec::new();
+    for dependency in chain.iter().flat_map(Pom::managed_dependencies) {
+        // Entries whose version cannot be worked out are left unmanaged.
+        let (Some(group_id), Some(artifact_id), Some(version)) = (
+            interpolate(&dependency.group_id, &properties),
+            interpolate(&dependency.artifact_id, &properties),
+            dependency
+                .version
+                .as_deref()
+                .and_then(|version| interpolate(version, &properties)),
+        ) else {
+            continue;
+        };
+
+        let entry = ManagedVersion {
+            group_id,
+            artifact_id,
+            version,
+        };
+        if dependency.is_import() {
+            imports.push(entry);
+        } else {
+            managed.push(entry);
+        }
+    }
+
+    for import in imports {
+        let Some(imported) = managed_versions_at_depth(
+            repositories,
+            &import.group_id,
+            &import.artifact_id,
+            &import.version,
+            cached_only,
+            depth + 1,
+        )?
+        else {
+            return Ok(None);
+        };
+        managed.extend(imported);
+    }
+    Ok(Some(managed))
+}
+
+/// Replaces `${name}` with POM properties, which may refer to further properties. Returns `None`
+/// when a property is not defined, or when properties keep referring to each other.
+fn interpolate(value: &str, properties: &HashMap<String, String>) -> Option<String> {
+    let mut value = value.to_string();
+    for _ in 0..MAX_INTERPOLATIONS {
+        let Some(start) = value.find("${") else {
+            return Some(value);
+        };
+        let end = start + value[start..].find('}')?;
+        let replacement = properties.get(&value[start + 2..end])?;
+        value.replace_range(start..=end, replacement);
+    }
+    None
+}
+
+fn read_pom(cache_path: &str, urls: &[String]) -> Result<Pom, String> {
+    if let Ok(text) = fs::read_to_string(cache_path) {
+        return Pom::parse(&text);
+    }
+    if urls.is_empty() {
+        return Err(format!("The POM is not cached at {cache_path}"));
+    }
+
+    let client = download::client()?;
+    let mut failures: Vec<String> = Vec::new();
+    for url in urls {
+        match repository::get_text_at_url(url, client) {
+            Ok(text) => {
+                let pom = Pom::parse(&text)?;
+                paths::ensure_parents(cache_path)?;
+                fs::write(cache_path, &text)
+                    .map_err(|e| format!("Could not cache POM at {cache_path}: {e}"))?;
+                return Ok(pom);
+            }
+            Err(e) => failures.push(format!("{}: {e}", credentials::redact(url))),
+        }
+    }
+
+    Err(format!(
+        "Could not download POM:\n- {}",
+        failures.join("\n- ")
+    ))
 }
 
-fn resolve_without_update(request: MavenResolveRequest<'_>) -> Result<ResolvedDependency, String> {
+/// Checks an artifact against its published signature. Both are dropped from the cache on failure,
+/// so an unverified artifact is never picked up later.
+fn verify_artifact_signature(
+    name: &str,
+    artifact_url: &str,
+    filepath: &str,
+) -> Result<String, String> {
+    signature::verify_published_signature(name, artifact_url, filepath).inspect_err(|_| {
+        let _ = fs::remove_file(filepath);
+        let _ = fs::remove_file(signature::signature_path(filepath));
+    })
+}
+
+/// Looks for a checksum sidecar beside the artifact, preferring the strongest algorithm.
+fn published_checksum(client: &Client, artifact_url: &str) -> Option<Checksum> {
+    ChecksumAlgorithm::SIDECARS.iter().find_map(|algorithm| {
+        let sidecar_url = format!("{artifact_url}.{}", algorithm.extension());
+        let contents = repository::get_text_at_url(&sidecar_url, client).ok()?;
+        Checksum::from_sidecar(*algorithm, &contents)
+    })
+}
+
+/// Fetches attachments requested since the artifact was locked or cached. Their checksum policy is
+/// that of the repository the artifact was locked from, as no version is resolved here.
+fn attach_to_cached_artifact(
+    resolved: ResolvedDependency,
+    request: &MavenResolveRequest<'_>,
+    context: &ResolveContext<'_>,
+) -> ResolvedDependency {
+    let locked_from = resolved
+        .artifacts
+        .iter()
+        .filter_map(|artifact| artifact.lock.as_ref())
+        .find_map(|lock| {
+            request.repositories.iter().find(|repository| {
+                settings::url_prefix(lock.fetch_url(), repository.url()).is_some()
+            })
+        });
+    let checksum_policy = request
+        .checksum_policy
+        .or(locked_from.and_then(MavenRepository::checksum_policy))
+        .unwrap_or_default();
+
+    attachment::attach(
+        resolved,
+        AttachmentRequest {
+            name: request.name,
+            kinds: request.attachments,
+            classifier: request.classifier,
+            checksum_policy,
+        },
+        |url| {
+            download::client()
+                .ok()
+                .and_then(|client| published_checksum(client, url))
+        },
+        context,
+    )
+}
+
+fn resolve_without_update(request: &MavenResolveRequest<'_>) -> Result<ResolvedDependency, String> {
     let Some(version) = explicit_static_version(request.version) else {
         return Err(format!(
             "Maven dependency \"{}\" does not have a locked artifact or explicit non-SNAPSHOT version, and no update was requested.\nFix: run `wisteria update {}` to resolve the Maven version, download it, and write it to wisteria.lock.",
@@ -130,8 +503,14 @@ fn resolve_without_update(request: MavenResolveRequest<'_>) -> Result<ResolvedDe
         ));
     }
 
+    let Some(repository) = request.repositories.first() else {
+        return Err(format!(
+            "Maven dependency \"{}\" has no repository to resolve from.\nFix: declare a repository under [repositories], or set `url` on the dependency.",
+            request.name
+        ));
+    };
     let target_url = maven_artifact_url(
-        request.url,
+        repository.url(),
         request.group_id,
         request.artifact_id,
         version,
@@ -139,14 +518,25 @@ fn resolve_without_update(request: MavenResolveRequest<'_>) -> Result<ResolvedDe
         request.classifier,
     );
 
-    resolve_cached_artifact(request.name, path, version, target_url, filepath)
+    let signing_key = request
+        .verify_signature
+        .then(|| verify_artifact_signature(request.name, &target_url, &filepath))
+        .transpose()?;
+    resolve_cached_artifact(
+        request.name,
+        path,
+        version,
+        target_url,
+        filepath,
+        signing_key,
+    )
 }
 
 fn explicit_static_version(version: Option<&String>) -> Option<&str> {
     let version = version?;
     match version.as_str() {
         "latest" | "release" => None,
-        version if version.ends_with("-SNAPSHOT") => None,
+        version if version.ends_with("-SNAPSHOT") || VersionRange::is_range(version) => None,
         version => Some(version),
     }
 }
@@ -206,6 +596,7 @@ fn resolve_cached_artifact(
     version: &str,
     fetch_url: String,
     filepath: String,
+    signing_key: Option<String>,
 ) -> Result<ResolvedDependency, String> {
     let hash = files::generate_sha2_for_file(&path)?;
     let lock = LockfileArtifact::new(
@@ -215,7 +606,8 @@ fn resolve_cached_artifact(
         fetch_url,
         filepath,
         hash,
-    );
+    )
+    .with_signing_key(signing_key);
 
     Ok(ResolvedDependency::new(
         String::from(name),
@@ -249,7 +641,10 @@ fn maven_artifact_url(
 
 #[cfg(test)]
 mod tests {
-    use std::{fs, path::PathBuf};
+    use std::{
+        fs,
+        path::{Path, PathBuf},
+    };
 
     use super::*;
     use crate::dependency::UpdateContext;
@@ -259,18 +654,26 @@ mod tests {
     const CACHE_PATH: &str = ".wisteria/cache/com.example/library/1.0.0/library.jar";
     const FETCH_URL: &str = "https://repo.example/com/example/library/1.0.0/library-1.0.0.jar";
 
+    fn repositories() -> Vec<MavenRepository> {
+        vec![MavenRepository::new("example", "https://repo.example")]
+    }
+
     fn request<'a>(
+        repositories: &'a [MavenRepository],
         version: Option<&'a String>,
         update_policy: &'a UpdatePolicy,
     ) -> MavenResolveRequest<'a> {
         MavenResolveRequest {
             name: "library",
-            url: "https://repo.example",
+            repositories,
             group_id: "com.example",
             artifact_id: "library",
             version,
             classifier: None,
             update_policy,
+            checksum_policy: None,
+            verify_signature: false,
+            attachments: &[],
         }
     }
 
@@ -289,7 +692,7 @@ mod tests {
             create_cached_artifact("cached");
 
             let resolved = resolve(
-                request(Some(&version), &UpdatePolicy::Never),
+                request(&repositories(), Some(&version), &UpdatePolicy::Never),
                 &ResolveContext::new(UpdateContext::ResolveOnly),
             )
             .unwrap();
@@ -332,7 +735,7 @@ mod tests {
             );
 
             let resolved = resolve(
-                request(None, &UpdatePolicy::Never),
+                request(&repositories(), None, &UpdatePolicy::Never),
                 &ResolveContext::with_locked_artifact(UpdateContext::ResolveOnly, &lock),
             )
             .unwrap();
@@ -344,6 +747,50 @@ mod tests {
         });
     }
 
+    #[test]
+    fn resolve_with_lockfile_artifact_fetches_newly_requested_attachments() {
+        let temp = TempDir::new("maven-locked-attach");
+
+        with_current_dir(temp.path(), || {
+            let jar = publish_artifact(&temp.path().join("repository"));
+            fs::write(
+                jar.with_file_name("library-1.0.0-sources.jar"),
+                "published sources",
+            )
+            .unwrap();
+            create_cached_artifact("published contents");
+            let hash = files::generate_sha2_for_file(&PathBuf::from(CACHE_PATH)).unwrap();
+            let lock = LockfileArtifact::new(
+                String::from("library"),
+                String::from("maven"),
+                Some(String::from("1.0.0")),
+                format!("file://{}", jar.display()),
+                String::from(CACHE_PATH),
+                hash,
+            );
+
+            let resolved = resolve(
+                MavenResolveRequest {
+                    attachments: &[AttachmentKind::Sources],
+                    ..request(&repositories(), None, &UpdatePolicy::Never)
+                },
+                &ResolveContext::with_locked_artifact(UpdateContext::ResolveOnly, &lock),
+            )
+            .unwrap();
+
+            let attachment = resolved.artifacts[0]
+                .lock
+                .as_ref()
+                .unwrap()
+                .attachment(AttachmentKind::Sources)
+                .unwrap();
+            assert_eq!(
+                fs::read_to_string(attachment.cache_path()).unwrap(),
+                "published sources"
+            );
+        });
+    }
+
     #[test]
     fn resolve_with_lockfile_artifact_rejects_hash_mismatch() {
         let temp = TempDir::new("maven-locked-hash-mismatch");
@@ -360,7 +807,7 @@ mod tests {
             );
 
             let error = resolve(
-                request(None, &UpdatePolicy::Never),
+                request(&repositories(), None, &UpdatePolicy::Never),
                 &ResolveContext::with_locked_artifact(UpdateContext::ResolveOnly, &lock),
             )
             .unwrap_err();
@@ -372,7 +819,7 @@ mod tests {
     #[test]
     fn resolve_without_update_rejects_dynamic_version_without_lockfile_artifact() {
         let error = resolve(
-            request(None, &UpdatePolicy::Never),
+            request(&repositories(), None, &UpdatePolicy::Never),
             &ResolveContext::new(UpdateContext::ResolveOnly),
         )
         .unwrap_err();
@@ -384,7 +831,7 @@ mod tests {
     fn resolve_without_update_rejects_snapshot_without_lockfile_artifact() {
         let version = String::from("1.0-SNAPSHOT");
         let error = resolve(
-            request(Some(&version), &UpdatePolicy::Never),
+            request(&repositories(), Some(&version), &UpdatePolicy::Never),
             &ResolveContext::new(UpdateContext::ResolveOnly),
         )
         .unwrap_err();
@@ -392,6 +839,158 @@ mod tests {
         assert!(error.contains("explicit non-SNAPSHOT version"));
     }
 
+    fn gpg(home: &Path, args: &[&str]) {
+        let status = std::process::Command::new("gpg")
+            .arg("--homedir")
+            .arg(home)
+            .args([
+                "--batch",
+                "--quiet",
+                "--no-permission-warning",
+                "--pinentry-mode",
+                "loopback",
+                "--passphrase",
+                "",
+            ])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "gpg {args:?} failed");
+    }
+
+    /// Publishes library 1.0.0 to a file-based repository and returns the jar path.
+    fn publish_artifact(repository: &Path) -> PathBuf {
+        let library = repository.join("com/example/library");
+        let jar = library.join("1.0.0/library-1.0.0.jar");
+        fs::create_dir_all(jar.parent().unwrap()).unwrap();
+        fs::write(
+            library.join("maven-metadata.xml"),
+            "<metadata><versioning><latest>1.0.0</latest><release>1.0.0</release><versions><version>1.0.0</version></versions></versioning></metadata>",
+        )
+        .unwrap();
+        fs::write(&jar, "published contents").unwrap();
+        jar
+    }
+
+    /// Publishes library 1.0.0 signed by a fresh key, which is exported into the project's trusted
+    /// keys. Returns the repository URL.
+    fn publish_signed_artifact(temp: &TempDir) -> String {
+        let repository = temp.path().join("repository");
+        let jar = publish_artifact(&repository);
+
+        let home = temp.path().join("gnupg");
+        fs::create_dir_all(&home).unwrap();
+        gpg(
+            &home,
+            &[
+                "--quick-gen-key",
+                "Tester <tester@example.com>",
+                "ed25519",
+                "sign",
+                "never",
+            ],
+        );
+        gpg(&home, &["--armor", "--detach-sign", jar.to_str().unwrap()]);
+        fs::create_dir_all(crate::util::consts::TRUSTED_KEYS_PATH).unwrap();
+        gpg(
+            &home,
+            &[
+                "--armor",
+                "--output",
+                ".wisteria/trusted-keys/tester.asc",
+                "--export",
+            ],
+        );
+        let _ = std::process::Command::new("gpgconf")
+            .arg("--homedir")
+            .arg(&home)
+            .args(["--kill", "gpg-agent"])
+            .status();
+
+        format!("file://{}", repository.display())
+    }
+
+    #[test]
+    fn resolve_falls_back_to_the_next_repository() {
+        let temp = TempDir::new("maven-fallback");
+        let version = String::from("1.0.0");
+
+        with_current_dir(temp.path(), || {
+            publish_artifact(&temp.path().join("second"));
+            let repositories = [
+                MavenRepository::new("first", &format!("file://{}/first", temp.path().display())),
+                MavenRepository::new(
+                    "second",
+                    &format!("file://{}/second", temp.path().display()),
+                ),
+            ];
+
+            let resolved = resolve(
+                request(&repositories, Some(&version), &UpdatePolicy::Always),
+                &ResolveContext::new(UpdateContext::Update),
+            )
+            .unwrap();
+
+            let lock = resolved.artifacts[0].lock.as_ref().unwrap();
+            assert!(lock.fetch_url().starts_with(repositories[1].url()));
+            assert_eq!(
+                fs::read_to_string(CACHE_PATH).unwrap(),
+                "published contents"
+            );
+        });
+    }
+
+    #[test]
+    fn latest_version_reads_metadata_without_downloading() {
+        let temp = TempDir::new("maven-latest-version");
+
+        with_current_dir(temp.path(), || {
+            publish_artifact(&temp.path().join("repository"));
+            let repositories = [MavenRepository::new(
+                "local",
+                &format!("file://{}/repository", temp.path().display()),
+            )];
+
+            let version =
+                latest_version(&repositories, "com.example", "library", None, None).unwrap();
+
+            assert_eq!(version, "1.0.0");
+            assert!(!Path::new(CACHE_PATH).exists());
+        });
+    }
+
+    #[test]
+    fn resolve_from_file_repository_locks_verified_signing_key() {
+        let temp = TempDir::new("maven-signature");
+        let version = String::from("1.0.0");
+
+        with_current_dir(temp.path(), || {
+            let url = publish_signed_artifact(&temp);
+            let repositories = [MavenRepository::new("local", &url)];
+            let request = MavenResolveRequest {
+                verify_signature: true,
+                ..request(&repositories, Some(&version), &UpdatePolicy::Always)
+            };
+
+            let resolved = resolve(request, &ResolveContext::new(UpdateContext::Update)).unwrap();
+
+            let lock = resolved.artifacts[0].lock.as_ref().unwrap();
+            let signing_key = lock.signing_key().unwrap();
+            assert_eq!(signing_key.len(), 40);
+            assert_eq!(
+                signature::verify_cached_signature(lock.cache_path()).as_deref(),
+                Ok(signing_key)
+            );
+
+            fs::write(lock.cache_path(), "tampered contents").unwrap();
+            assert!(
+                signature::verify_cached_signature(lock.cache_path())
+                    .unwrap_err()
+                    .contains("does not match its signature")
+            );
+        });
+    }
+
     #[test]
     fn builds_maven_artifact_url_with_classifier() {
         let classifier = String::from("shaded");
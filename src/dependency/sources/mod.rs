@@ -1,3 +1,5 @@
+pub mod attachment;
+pub mod checksum;
 pub mod github;
 pub mod local;
 pub mod maven;
@@ -0,0 +1,457 @@
+use std::{cmp::Ordering, fmt};
+
+/// One piece of a version string, split at separators and digit/letter boundaries.
+#[derive(Debug, PartialEq, Eq)]
+enum Token {
+    Number(u64),
+    Qualifier(String),
+}
+
+/// Rank of a plain release, used when the other version has run out of tokens.
+const RELEASE_RANK: u8 = 5;
+
+/// Compares two versions the way Maven orders them: numbers numerically, and known qualifiers as
+/// `alpha < beta < milestone < rc < snapshot < release < sp`, with unknown qualifiers after those.
+/// A leading `v`, as used by many GitHub tags, is ignored.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let a = tokenize(a);
+    let b = tokenize(b);
+
+    for index in 0..a.len().max(b.len()) {
+        let ordering = match (a.get(index), b.get(index)) {
+            (Some(a), Some(b)) => compare_tokens(a, b),
+            (Some(a), None) => compare_to_end(a),
+            (None, Some(b)) => compare_to_end(b).reverse(),
+            (None, None) => Ordering::Equal,
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    Ordering::Equal
+}
+
+/// Whether the version is an alpha, beta, milestone, release candidate or snapshot.
+pub fn is_prerelease(version: &str) -> bool {
+    tokenize(version).iter().any(|token| match token {
+        Token::Qualifier(qualifier) => qualifier_rank(qualifier) < RELEASE_RANK,
+        Token::Number(_) => false,
+    })
+}
+
+/// The leading number of a version, if it starts with one.
+pub fn major(version: &str) -> Option<u64> {
+    match tokenize(version).first() {
+        Some(Token::Number(major)) => Some(*major),
+        _ => None,
+    }
+}
+
+fn tokenize(version: &str) -> Vec<Token> {
+    let version = match version.strip_prefix(['v', 'V']) {
+        Some(rest) if rest.starts_with(|c: char| c.is_ascii_digit()) => rest,
+        _ => version,
+    };
+
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    for c in version.chars() {
+        if matches!(c, '.' | '-' | '_' | '+') {
+            push_token(&mut tokens, &mut current);
+            continue;
+        }
+
+        if let Some(last) = current.chars().last()
+            && last.is_ascii_digit() != c.is_ascii_digit()
+        {
+            push_token(&mut tokens, &mut current);
+        }
+        current.push(c);
+    }
+    push_token(&mut tokens, &mut current);
+
+    tokens
+}
+
+fn push_token(tokens: &mut Vec<Token>, current: &mut String) {
+    if current.is_empty() {
+        return;
+    }
+
+    let token = match current.parse::<u64>() {
+        Ok(number) => Token::Number(number),
+        Err(_) => Token::Qualifier(current.to_lowercase()),
+    };
+    tokens.push(token);
+    current.clear();
+}
+
+fn qualifier_rank(qualifier: &str) -> u8 {
+    match qualifier {
+        "alpha" | "a" => 0,
+        "beta" | "b" => 1,
+        "milestone" | "m" => 2,
+        "rc" | "cr" => 3,
+        "snapshot" => 4,
+        "ga" | "final" | "release" => RELEASE_RANK,
+        "sp" => 6,
+        _ => 7,
+    }
+}
+
+fn compare_tokens(a: &Token, b: &Token) -> Ordering {
+    match (a, b) {
+        (Token::Number(a), Token::Number(b)) => a.cmp(b),
+        (Token::Number(_), Token::Qualifier(_)) => Ordering::Greater,
+        (Token::Qualifier(_), Token::Number(_)) => Ordering::Less,
+        (Token::Qualifier(a), Token::Qualifier(b)) => qualifier_rank(a)
+            .cmp(&qualifier_rank(b))
+            .then_with(|| a.cmp(b)),
+    }
+}
+
+/// Compares a token against a version that has no more tokens, so `1.0.0 == 1` and `1-rc1 < 1`.
+fn compare_to_end(token: &Token) -> Ordering {
+    match token {
+        Token::Number(number) => number.cmp(&0),
+        Token::Qualifier(qualifier) => qualifier_rank(qualifier).cmp(&RELEASE_RANK),
+    }
+}
+
+/// A set of versions, written with Maven range syntax such as `[1.2,2.0)` or `[1.0,2.0),[3.0,)`,
+/// or with the shorthands `^1.4` (same leading non-zero number) and `~1.4.2` (same minor version).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VersionRange {
+    text: String,
+    intervals: Vec<Interval>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Interval {
+    lower: Option<Bound>,
+    upper: Option<Bound>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Bound {
+    version: String,
+    inclusive: bool,
+}
+
+impl VersionRange {
+    /// Whether a configured version uses range syntax rather than naming one version.
+    pub fn is_range(value: &str) -> bool {
+        value.starts_with(['[', '(', '^', '~'])
+    }
+
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let intervals = match value.strip_prefix(['^', '~']) {
+            Some(base) => shorthand_interval(base, value.starts_with('^')).map(|interval| vec![interval]),
+            None => maven_intervals(value.trim()),
+        }
+        .map_err(|reason| {
+            format!(
+                "Invalid version range \"{value}\": {reason}.\nFix: use Maven range syntax such as \"[1.2,2.0)\", or \"^1.4\" and \"~1.4.2\"."
+            )
+        })?;
+
+        Ok(Self {
+            text: value.to_string(),
+            intervals,
+        })
+    }
+
+    /// Whether the version lies in the range. Prereleases and snapshots only match when a bound
+    /// of the range is one, so `^1.4` never picks `2.0.0-rc1` or `1.5-SNAPSHOT`.
+    pub fn contains(&self, version: &str) -> bool {
+        if is_prerelease(version) && !self.allows_prereleases() {
+            return false;
+        }
+
+        self.intervals.iter().any(|interval| {
+            let above = interval.lower.as_ref().is_none_or(|lower| {
+                match compare_versions(version, &lower.version) {
+                    Ordering::Greater => true,
+                    Ordering::Equal => lower.inclusive,
+                    Ordering::Less => false,
+                }
+            });
+            let below = interval.upper.as_ref().is_none_or(|upper| {
+                match compare_versions(version, &upper.version) {
+                    Ordering::Less => true,
+                    Ordering::Equal => upper.inclusive,
+                    Ordering::Greater => false,
+                }
+            });
+            above && below
+        })
+    }
+
+    /// The highest of the versions which lie in the range.
+    pub fn highest<'a>(&self, versions: &'a [String]) -> Option<&'a String> {
+        versions
+            .iter()
+            .filter(|version| self.contains(version))
+            .max_by(|a, b| compare_versions(a, b))
+    }
+
+    /// The range in Maven syntax, which is how shorthands are written to a pom.xml.
+    pub fn to_maven(&self) -> String {
+        if !self.text.starts_with(['^', '~']) {
+            return self.text.clone();
+        }
+
+        let bound = |bound: &Option<Bound>| {
+            bound
+                .as_ref()
+                .map(|bound| bound.version.clone())
+                .unwrap_or_default()
+        };
+        self.intervals
+            .iter()
+            .map(|interval| format!("[{},{})", bound(&interval.lower), bound(&interval.upper)))
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    fn allows_prereleases(&self) -> bool {
+        self.intervals
+            .iter()
+            .flat_map(|interval| [&interval.lower, &interval.upper])
+            .flatten()
+            .any(|bound| is_prerelease(&bound.version))
+    }
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// `^` keeps the leading non-zero number and `~` keeps the minor version when one is given, so
+/// `^0.4` is `[0.4,0.5)`, `~1.4.2` is `[1.4.2,1.5)` and `~1` is `[1,2)`.
+fn shorthand_interval(base: &str, caret: bool) -> Result<Interval, String> {
+    let numbers: Vec<u64> = base
+        .split(['.', '-'])
+        .map_while(|part| part.parse::<u64>().ok())
+        .collect();
+    if numbers.is_empty() {
+        return Err(String::from("expected a version after `^` or `~`"));
+    }
+
+    let kept = if caret {
+        numbers
+            .iter()
+            .position(|number| *number != 0)
+            .unwrap_or(numbers.len() - 1)
+    } else {
+        (numbers.len() - 1).min(1)
+    };
+    let mut upper: Vec<u64> = numbers[..=kept].to_vec();
+    upper[kept] += 1;
+
+    Ok(Interval {
+        lower: Some(Bound {
+            version: base.to_string(),
+            inclusive: true,
+        }),
+        upper: Some(Bound {
+            version: upper
+                .iter()
+                .map(u64::to_string)
+                .collect::<Vec<String>>()
+                .join("."),
+            inclusive: false,
+        }),
+    })
+}
+
+fn maven_intervals(value: &str) -> Result<Vec<Interval>, String> {
+    let mut intervals = Vec::new();
+    let mut rest = value;
+    while !rest.is_empty() {
+        let lower_inclusive = match rest.chars().next() {
+            Some('[') => true,
+            Some('(') => false,
+            _ => return Err(String::from("each range must start with `[` or `(`")),
+        };
+        let Some(end) = rest.find([']', ')']) else {
+            return Err(String::from("a range is not closed with `]` or `)`"));
+        };
+        let upper_inclusive = rest[end..].starts_with(']');
+        intervals.push(maven_interval(
+            rest[1..end].trim(),
+            lower_inclusive,
+            upper_inclusive,
+        )?);
+
+        rest = rest[end + 1..].trim_start();
+        if let Some(next) = rest.strip_prefix(',') {
+            rest = next.trim_start();
+            if rest.is_empty() {
+                return Err(String::from("a trailing `,` is not followed by a range"));
+            }
+        } else if !rest.is_empty() {
+            return Err(String::from("ranges must be separated by `,`"));
+        }
+    }
+
+    if intervals.is_empty() {
+        Err(String::from("no range is given"))
+    } else {
+        Ok(intervals)
+    }
+}
+
+fn maven_interval(
+    inner: &str,
+    lower_inclusive: bool,
+    upper_inclusive: bool,
+) -> Result<Interval, String> {
+    let bound = |version: &str, inclusive: bool| {
+        let version = version.trim();
+        (!version.is_empty()).then(|| Bound {
+            version: version.to_string(),
+            inclusive,
+        })
+    };
+
+    let Some((lower, upper)) = inner.split_once(',') else {
+        // `[1.5]` is exactly 1.5, and is the only form without a comma.
+        if !lower_inclusive || !upper_inclusive || inner.is_empty() {
+            return Err(format!("`{inner}` must be written as `[{inner}]`"));
+        }
+        return Ok(Interval {
+            lower: bound(inner, true),
+            upper: bound(inner, true),
+        });
+    };
+
+    let interval = Interval {
+        lower: bound(lower, lower_inclusive),
+        upper: bound(upper, upper_inclusive),
+    };
+    if let (Some(lower), Some(upper)) = (&interval.lower, &interval.upper)
+        && compare_versions(&lower.version, &upper.version) == Ordering::Greater
+    {
+        return Err(format!(
+            "the lower bound {} is above the upper bound {}",
+            lower.version, upper.version
+        ));
+    }
+    Ok(interval)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_numbers_numerically_and_prereleases_first() {
+        let mut versions = vec![
+            "1.10.0",
+            "1.2.0",
+            "1.2.0-SNAPSHOT",
+            "1.2.0-rc1",
+            "1.2.0-beta2",
+            "1.2.0-beta10",
+            "1.2.0-alpha",
+            "1.9",
+        ];
+        versions.sort_by(|a, b| compare_versions(a, b));
+
+        assert_eq!(
+            versions,
+            vec![
+                "1.2.0-alpha",
+                "1.2.0-beta2",
+                "1.2.0-beta10",
+                "1.2.0-rc1",
+                "1.2.0-SNAPSHOT",
+                "1.2.0",
+                "1.9",
+                "1.10.0",
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_trailing_zeroes_and_tag_prefixes() {
+        assert_eq!(compare_versions("1.0.0", "1"), Ordering::Equal);
+        assert_eq!(compare_versions("v2.1", "2.1.0"), Ordering::Equal);
+        assert_eq!(compare_versions("2.1.1", "v2.1"), Ordering::Greater);
+    }
+
+    #[test]
+    fn reads_major_versions_and_prereleases() {
+        assert_eq!(major("v3.2.1"), Some(3));
+        assert_eq!(major("33.0.0-jre"), Some(33));
+        assert_eq!(major("latest"), None);
+        assert!(is_prerelease("2.0.0-M1"));
+        assert!(is_prerelease("1.0-SNAPSHOT"));
+        assert!(!is_prerelease("33.0.0-jre"));
+        assert!(!is_prerelease("5.3.2.Final"));
+    }
+
+    #[test]
+    fn matches_maven_ranges_and_picks_the_highest_version() {
+        let versions: Vec<String> = ["1.1", "1.2", "1.5.3", "2.0-rc1", "2.0", "3.1"]
+            .iter()
+            .map(|version| version.to_string())
+            .collect();
+        let highest = |range: &str| {
+            VersionRange::parse(range)
+                .unwrap()
+                .highest(&versions)
+                .cloned()
+        };
+
+        assert_eq!(highest("[1.2,2.0)").as_deref(), Some("1.5.3"));
+        assert_eq!(highest("[1.2,2.0]").as_deref(), Some("2.0"));
+        assert_eq!(highest("(,1.2)").as_deref(), Some("1.1"));
+        assert_eq!(highest("[1.0,1.2),[3.0,)").as_deref(), Some("3.1"));
+        assert_eq!(highest("[2.0-rc1,2.0)").as_deref(), Some("2.0-rc1"));
+        assert_eq!(highest("[1.2]").as_deref(), Some("1.2"));
+        assert_eq!(highest("[4.0,)"), None);
+    }
+
+    #[test]
+    fn expands_caret_and_tilde_shorthands() {
+        let maven = |range: &str| VersionRange::parse(range).unwrap().to_maven();
+
+        assert_eq!(maven("^1.4"), "[1.4,2)");
+        assert_eq!(maven("^0.4.1"), "[0.4.1,0.5)");
+        assert_eq!(maven("~1.4.2"), "[1.4.2,1.5)");
+        assert_eq!(maven("~1.4"), "[1.4,1.5)");
+        assert_eq!(maven("~1"), "[1,2)");
+        assert_eq!(maven("[1.2,2.0)"), "[1.2,2.0)");
+
+        let caret = VersionRange::parse("^1.4").unwrap();
+        assert!(caret.contains("1.9.0"));
+        assert!(!caret.contains("1.3"));
+        assert!(!caret.contains("2.0.0-rc1"));
+        assert!(!caret.contains("1.5-SNAPSHOT"));
+    }
+
+    #[test]
+    fn rejects_malformed_ranges() {
+        for range in [
+            "[1.0,2.0",
+            "(1.0)",
+            "[2.0,1.0]",
+            "[1.0,2.0),",
+            "^",
+            "[1.0]x",
+        ] {
+            assert!(
+                VersionRange::parse(range)
+                    .unwrap_err()
+                    .contains(&format!("Invalid version range \"{range}\"")),
+                "{range}"
+            );
+        }
+    }
+}
@@ -1,12 +1,12 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fs, path::Path};
 
 use regex::Regex;
 use xml::{EmitterConfig, EventWriter, common::XmlVersion, writer::XmlEvent};
 
 use crate::dependency::resolver::ResolveContext;
-use crate::dependency::{Dependency, UpdateContext};
+use crate::dependency::{AttachmentKind, Dependency, UpdateContext};
 use crate::model::lockfile::try_read_lockfile;
-use crate::model::{Configuration, Project};
+use crate::model::{Configuration, LockfileAttachment, Project};
 use crate::util::consts;
 use crate::workspace::nature::Nature;
 
@@ -77,21 +77,36 @@ pub fn generate_classpath(
                 ),
             ) {
                 Ok(resolved) => {
-                    for path in resolved.paths() {
-                        let path: &str = path.to_str().unwrap();
-                        let dep = XmlEvent::start_element("classpathentry")
+                    for artifact in &resolved.artifacts {
+                        let path: &str = artifact.path.to_str().unwrap();
+                        let lock = artifact.lock.as_ref();
+                        let sourcepath = lock
+                            .and_then(|lock| lock.attachment(AttachmentKind::Sources))
+                            .map(LockfileAttachment::cache_path)
+                            .filter(|sourcepath| Path::new(sourcepath).exists());
+                        let javadoc_location = match dependencies_opt.javadoc() {
+                            Some(javadoc_url) => Some(javadoc_url.clone()),
+                            None => lock
+                                .and_then(|lock| lock.attachment(AttachmentKind::Javadoc))
+                                .and_then(|javadoc| javadoc_jar_location(javadoc.cache_path())),
+                        };
+
+                        let mut dep = XmlEvent::start_element("classpathentry")
                             .attr("kind", "lib")
                             .attr("path", path);
+                        if let Some(sourcepath) = sourcepath {
+                            dep = dep.attr("sourcepath", sourcepath);
+                        }
 
                         writer.write(dep).map_err(|e| e.to_string())?;
 
-                        if let Some(javadoc_url) = dependencies_opt.javadoc() {
+                        if let Some(javadoc_location) = &javadoc_location {
                             let attributes = XmlEvent::start_element("attributes");
                             writer.write(attributes).map_err(|e| e.to_string())?;
 
                             let attribute = XmlEvent::start_element("attribute")
                                 .attr("name", "javadoc_location")
-                                .attr("value", javadoc_url);
+                                .attr("value", javadoc_location);
 
                             writer.write(attribute).map_err(|e| e.to_string())?;
                             writer
@@ -154,6 +169,12 @@ pub fn generate_classpath(
     Ok(String::from_utf8(bytes).unwrap())
 }
 
+/// Eclipse reads javadoc from inside an archive through a `jar:` URL, which must be absolute.
+fn javadoc_jar_location(cache_path: &str) -> Option<String> {
+    let path = fs::canonicalize(cache_path).ok()?;
+    Some(format!("jar:file:{}!/", path.to_str()?))
+}
+
 fn has_maven_nature(project: &Project) -> bool {
     project
         .info()
@@ -165,8 +186,9 @@ fn has_maven_nature(project: &Project) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::{LockfileArtifact, lockfile};
     use crate::test_support::{TempDir, with_current_dir};
-    use std::fs;
+    use crate::workspace::files;
 
     fn regexes() -> HashMap<&'static str, Regex> {
         let mut regexes = HashMap::new();
@@ -265,6 +287,80 @@ mod tests {
         );
     }
 
+    #[test]
+    fn generated_classpath_attaches_locked_sources_and_javadoc_jars() {
+        let temp = TempDir::new("classpath-attachments");
+        let project = project_from_toml(
+            &temp,
+            r#"
+            [project]
+            name = "Demo"
+            version = "1.0.0"
+            description = "Demo"
+            natures = [ "eclipse" ]
+
+            [dependencies.github]
+            library = { repository = "Owner/Library", tag = "v1.0.0", attach = [ "sources", "javadoc" ] }
+
+            [configuration.main]
+            sources = [ "src/" ]
+            dependencies = [ "library" ]
+            targets = [ "target/demo.jar" ]
+            "#,
+        );
+        let configuration = project.info().configurations().get("main").unwrap();
+
+        let xml = with_current_dir(temp.path(), || {
+            let cache = ".wisteria/cache/Owner/Library/v1.0.0";
+            fs::create_dir_all(cache).unwrap();
+            let mut attachments = Vec::new();
+            for kind in [AttachmentKind::Sources, AttachmentKind::Javadoc] {
+                let cache_path = format!("{cache}/Library-{kind}.jar");
+                fs::write(&cache_path, kind.classifier()).unwrap();
+                attachments.push(LockfileAttachment::new(
+                    kind,
+                    format!("https://example.com/Library-{kind}.jar"),
+                    cache_path.clone(),
+                    files::generate_sha2_for_file(Path::new(&cache_path)).unwrap(),
+                ));
+            }
+            let jar = format!("{cache}/Library.jar");
+            fs::write(&jar, "library").unwrap();
+            let artifact = LockfileArtifact::new(
+                String::from("library"),
+                String::from("github"),
+                Some(String::from("v1.0.0")),
+                String::from(
+                    "https://github.com/Owner/Library/releases/download/v1.0.0/Library.jar",
+                ),
+                jar.clone(),
+                files::generate_sha2_for_file(Path::new(&jar)).unwrap(),
+            )
+            .with_attachments(attachments);
+            lockfile::write_lockfile(
+                &lockfile::lockfile_artifacts_to_toml(vec![artifact]).unwrap(),
+            )
+            .unwrap();
+
+            generate_classpath(&project, configuration, &regexes()).unwrap()
+        });
+
+        let javadoc = temp
+            .path()
+            .join(".wisteria/cache/Owner/Library/v1.0.0/Library-javadoc.jar")
+            .canonicalize()
+            .unwrap();
+        assert!(
+            xml.contains(
+                r#"sourcepath=".wisteria/cache/Owner/Library/v1.0.0/Library-sources.jar""#
+            )
+        );
+        assert!(xml.contains(&format!(
+            r#"name="javadoc_location" value="jar:file:{}!/""#,
+            javadoc.to_string_lossy()
+        )));
+    }
+
     #[test]
     fn generated_classpath_uses_maven_container_for_maven_dependencies() {
         let temp = TempDir::new("classpath-maven");
@@ -1,11 +1,11 @@
 use std::collections::BTreeMap;
 
-use reqwest::blocking::Client;
 use xml::{EmitterConfig, EventWriter, writer::XmlEvent};
 
-use crate::dependency::Dependency;
+use crate::dependency::version::VersionRange;
+use crate::dependency::{Dependency, policy};
 use crate::maven::{repository, repository::ArtifactVersion};
-use crate::model::{Configuration, Project};
+use crate::model::{Configuration, Lockfile, LockfileArtifact, Project, lockfile};
 use crate::workspace::download;
 
 const DEFAULT_MAVEN_CENTRAL: &str = "https://repo1.maven.org/maven2";
@@ -58,10 +58,13 @@ pub fn generate_pom(project: &Project, configuration: &Configuration) -> Result<
         .write(XmlEvent::start_element("dependencies"))
         .map_err(|e| e.to_string())?;
 
-    let client: Client = Client::builder()
-        .user_agent(download::USER_AGENT)
-        .build()
-        .unwrap();
+    let client = download::client()?;
+    let lockfile_only = download::offline() || policy::locked();
+    let lockfile = if lockfile_only {
+        lockfile::try_read_lockfile()?
+    } else {
+        None
+    };
 
     let Some(configuration_dependencies) = configuration.dependencies() else {
         writer
@@ -81,7 +84,7 @@ pub fn generate_pom(project: &Project, configuration: &Configuration) -> Result<
 
         match dependency {
             Dependency::FetchFromMaven {
-                url,
+                repositories,
                 group_id,
                 artifact_id,
                 version,
@@ -95,17 +98,35 @@ pub fn generate_pom(project: &Project, configuration: &Configuration) -> Result<
                 write_text_element(&mut writer, "groupId", group_id)?;
                 write_text_element(&mut writer, "artifactId", artifact_id)?;
 
-                let target_version = artifact_version(version.as_ref());
-                let maven_version = repository::get_version(
-                    &client,
-                    url,
-                    group_id,
-                    artifact_id,
-                    classifier.as_ref(),
-                    &target_version,
-                )
-                .map_err(|e| e.to_string())?;
-                write_text_element(&mut writer, "version", &maven_version.0)?;
+                let maven_version = if lockfile_only {
+                    locked_version(
+                        dependency_reference.name(),
+                        version.as_ref(),
+                        lockfile.as_ref(),
+                    )?
+                } else {
+                    let target_version = ArtifactVersion::load(version.as_ref())?;
+                    repositories
+                        .iter()
+                        .map(|candidate| {
+                            repository::get_version(
+                                client,
+                                candidate.url(),
+                                group_id,
+                                artifact_id,
+                                classifier.as_ref(),
+                                &target_version,
+                            )
+                        })
+                        .find(Result::is_ok)
+                        .unwrap_or_else(|| {
+                            Err(format!(
+                                "No repository has a matching version of {group_id}:{artifact_id}"
+                            ))
+                        })?
+                        .0
+                };
+                write_text_element(&mut writer, "version", &maven_version)?;
 
                 if let Some(classifier) = classifier {
                     write_text_element(&mut writer, "classifier", classifier)?;
@@ -144,19 +165,24 @@ fn collect_repositories(
     };
 
     for dependency_reference in configuration_dependencies {
-        let Some(Dependency::FetchFromMaven { url, .. }) =
-            project.dependencies().get(dependency_reference.name())
+        let Some(Dependency::FetchFromMaven {
+            repositories: candidates,
+            ..
+        }) = project.dependencies().get(dependency_reference.name())
         else {
             continue;
         };
 
-        if is_default_maven_central(url) || repositories.values().any(|known_url| known_url == url)
-        {
-            continue;
-        }
+        for url in candidates.iter().map(|candidate| candidate.url()) {
+            if is_default_maven_central(url)
+                || repositories.values().any(|known_url| known_url == url)
+            {
+                continue;
+            }
 
-        let id = format!("wisteria-repository-{}", repositories.len() + 1);
-        repositories.insert(id, url.clone());
+            let id = format!("wisteria-repository-{}", repositories.len() + 1);
+            repositories.insert(id, url.to_string());
+        }
     }
 
     repositories
@@ -203,13 +229,28 @@ fn write_repositories<W: std::io::Write>(
         .map_err(|e| e.to_string())
 }
 
-fn artifact_version(version: Option<&String>) -> ArtifactVersion {
-    match version.map(|version| version.as_str()) {
-        Some("latest") | None => ArtifactVersion::Latest,
-        Some("release") => ArtifactVersion::Release,
-        Some(version) => ArtifactVersion::Version {
-            version: version.to_string(),
-        },
+/// Offline or locked, the version comes from `wisteria.lock`, or from an explicit version or range
+/// in project.toml.
+fn locked_version(
+    name: &str,
+    version: Option<&String>,
+    lockfile: Option<&Lockfile>,
+) -> Result<String, String> {
+    if let Some(locked) = lockfile
+        .and_then(|lockfile| lockfile.artifact_for_dependency(name))
+        .and_then(LockfileArtifact::version)
+    {
+        return Ok(locked.to_string());
+    }
+
+    match version.map(String::as_str) {
+        Some("latest" | "release") | None => Err(format!(
+            "Cannot look up the version of Maven dependency \"{name}\" for pom.xml from wisteria.lock while offline or locked.\nFix: run `wisteria update {name}` while online to lock it, or set an explicit `version` in project.toml."
+        )),
+        Some(version) if VersionRange::is_range(version) => {
+            Ok(VersionRange::parse(version)?.to_maven())
+        }
+        Some(version) => Ok(version.to_string()),
     }
 }
 
@@ -272,6 +313,36 @@ mod tests {
         assert!(pom.contains("<dependencies />") || pom.contains("<dependencies>"));
     }
 
+    #[test]
+    fn locked_version_prefers_locked_version_then_explicit_version() {
+        let lockfile = Lockfile::from_artifacts_for_test(vec![LockfileArtifact::new(
+            String::from("gson"),
+            String::from("maven"),
+            Some(String::from("2.10.1")),
+            String::from("https://repo1.maven.org/maven2/gson-2.10.1.jar"),
+            String::from(".wisteria/cache/gson.jar"),
+            String::from("hash"),
+        )]);
+
+        assert_eq!(
+            locked_version("gson", None, Some(&lockfile)).unwrap(),
+            "2.10.1"
+        );
+        assert_eq!(
+            locked_version("guava", Some(&String::from("33.0.0-jre")), Some(&lockfile)).unwrap(),
+            "33.0.0-jre"
+        );
+        assert_eq!(
+            locked_version("guava", Some(&String::from("^33.0")), None).unwrap(),
+            "[33.0,34)"
+        );
+        assert!(
+            locked_version("guava", Some(&String::from("latest")), None)
+                .unwrap_err()
+                .contains("while offline or locked")
+        );
+    }
+
     #[test]
     fn collect_repositories_skips_default_central_and_deduplicates_custom_urls() {
         let temp = TempDir::new("pom-repositories");
@@ -310,16 +381,23 @@ mod tests {
 
     #[test]
     fn artifact_version_parses_special_version_selectors() {
+        let artifact_version = |version: Option<&str>| {
+            ArtifactVersion::load(version.map(String::from).as_ref()).unwrap()
+        };
         assert!(matches!(artifact_version(None), ArtifactVersion::Latest));
         assert!(matches!(
-            artifact_version(Some(&String::from("latest"))),
+            artifact_version(Some("latest")),
             ArtifactVersion::Latest
         ));
         assert!(matches!(
-            artifact_version(Some(&String::from("release"))),
+            artifact_version(Some("release")),
             ArtifactVersion::Release
         ));
-        match artifact_version(Some(&String::from("1.0.0"))) {
+        assert!(matches!(
+            artifact_version(Some("^1.4")),
+            ArtifactVersion::Range { .. }
+        ));
+        match artifact_version(Some("1.0.0")) {
             ArtifactVersion::Version { version } => assert_eq!(version, "1.0.0"),
             _ => panic!("expected explicit version"),
         }
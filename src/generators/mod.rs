@@ -2,6 +2,7 @@ pub mod eclipse;
 pub mod git;
 pub mod maven;
 pub mod metadata;
+pub mod sbom;
 pub mod wisteria_project;
 
 pub use metadata::{WISTERIA_METADATA_TEMPLATE, generate_metadata};
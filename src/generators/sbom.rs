@@ -0,0 +1,555 @@
+use std::{
+    collections::HashMap,
+    env,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use regex::Regex;
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+
+use crate::dependency::resolver::ResolveContext;
+use crate::dependency::{Dependency, DependencyScope, UpdateContext};
+use crate::model::{Configuration, Lockfile, LockfileArtifact, Project, ProjectInfo};
+use crate::workspace::{credentials, files};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SbomFormat {
+    #[default]
+    CycloneDx,
+    Spdx,
+}
+
+impl SbomFormat {
+    pub fn load(value: &str) -> Result<Self, String> {
+        match value {
+            "cyclonedx" | "cdx" => Ok(Self::CycloneDx),
+            "spdx" => Ok(Self::Spdx),
+            _ => Err(format!(
+                "Unknown SBOM format \"{value}\". Expected one of [cyclonedx, spdx]."
+            )),
+        }
+    }
+}
+
+/// One jar that ends up in the SBOM, from the lockfile or, for local dependencies, from disk.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SbomComponent {
+    dependency: String,
+    name: String,
+    group: Option<String>,
+    version: Option<String>,
+    purl: Option<String>,
+    sha256: String,
+    download_url: Option<String>,
+    scope: DependencyScope,
+}
+
+impl SbomComponent {
+    fn reference(&self) -> String {
+        match &self.purl {
+            Some(purl) => purl.clone(),
+            None => format!("{}/{}", self.dependency, self.name),
+        }
+    }
+}
+
+/// Collects the components of every dependency the configuration references. Fetched dependencies
+/// must be locked, and local files and folders are hashed where they are.
+pub fn sbom_components(
+    project: &Project,
+    configuration: &Configuration,
+    lockfile: Option<&Lockfile>,
+    regexes: &HashMap<&str, Regex>,
+) -> Result<Vec<SbomComponent>, String> {
+    let mut components = Vec::new();
+    let Some(references) = configuration.dependencies() else {
+        return Ok(components);
+    };
+
+    for reference in references {
+        let name = reference.name();
+        let Some(dependency) = project.dependencies().get(name) else {
+            continue;
+        };
+
+        match dependency {
+            Dependency::LocalFile { .. } | Dependency::LocalFolder { .. } => {
+                let resolved = dependency.resolve(
+                    name,
+                    configuration.environment(),
+                    regexes,
+                    ResolveContext::new(UpdateContext::ResolveOnly),
+                )?;
+                for path in resolved.paths() {
+                    components.push(SbomComponent {
+                        dependency: name.to_string(),
+                        name: file_name(&path.to_string_lossy()),
+                        group: None,
+                        version: None,
+                        purl: None,
+                        sha256: files::generate_sha2_for_file(path)?,
+                        download_url: None,
+                        scope: reference.scope(),
+                    });
+                }
+            }
+            _ if dependency.lockfile_source().is_some() => {
+                let artifacts: Vec<&LockfileArtifact> = lockfile
+                    .map(|lockfile| {
+                        lockfile
+                            .artifacts()
+                            .iter()
+                            .filter(|artifact| artifact.name() == name)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                if artifacts.is_empty() {
+                    return Err(format!(
+                        "Dependency `{name}` is not locked in wisteria.lock, so its version and hash are unknown.\nFix: run `wisteria sync {name}`, or `wisteria update {name}` if it is not cached yet."
+                    ));
+                }
+
+                for artifact in artifacts {
+                    components.push(locked_component(dependency, artifact, reference.scope()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(components)
+}
+
+fn locked_component(
+    dependency: &Dependency,
+    artifact: &LockfileArtifact,
+    scope: DependencyScope,
+) -> SbomComponent {
+    let version = artifact.version().map(String::from);
+    let (name, group, purl) = match dependency {
+        Dependency::FetchFromMaven {
+            group_id,
+            artifact_id,
+            classifier,
+            ..
+        } => (
+            artifact_id.clone(),
+            Some(group_id.clone()),
+            version.as_ref().map(|version| {
+                let mut purl = format!("pkg:maven/{group_id}/{artifact_id}@{version}");
+                if let Some(classifier) = classifier {
+                    purl.push_str(&format!("?classifier={classifier}"));
+                }
+                purl
+            }),
+        ),
+        Dependency::FetchFromGithub {
+            username,
+            repository,
+            ..
+        } => (
+            repository.clone(),
+            Some(username.clone()),
+            version
+                .as_ref()
+                .map(|version| format!("pkg:github/{username}/{repository}@{version}")),
+        ),
+        _ => (file_name(artifact.cache_path()), None, None),
+    };
+
+    SbomComponent {
+        dependency: artifact.name().to_string(),
+        name,
+        group,
+        version,
+        purl,
+        sha256: artifact.hash().to_string(),
+        download_url: Some(credentials::redact(artifact.fetch_url())),
+        scope,
+    }
+}
+
+fn file_name(path: &str) -> String {
+    Path::new(path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string())
+}
+
+/// Renders the SBOM as pretty-printed JSON. The timestamp honours `SOURCE_DATE_EPOCH` so that
+/// reproducible builds produce identical documents.
+pub fn generate_sbom(
+    format: SbomFormat,
+    info: &ProjectInfo,
+    components: &[SbomComponent],
+) -> Result<String, String> {
+    let timestamp = rfc3339(build_timestamp());
+    let document = match format {
+        SbomFormat::CycloneDx => cyclonedx(info, components, &timestamp),
+        SbomFormat::Spdx => spdx(info, components, &timestamp),
+    };
+
+    serde_json::to_string_pretty(&document).map_err(|e| format!("Could not write SBOM: {e}"))
+}
+
+fn license_expression(info: &ProjectInfo) -> Option<String> {
+    (!info.license().is_empty()).then(|| info.license().join(" AND "))
+}
+
+fn cyclonedx(info: &ProjectInfo, components: &[SbomComponent], timestamp: &str) -> Value {
+    let root = format!("{}@{}", info.name(), info.version());
+    let mut metadata_component = json!({
+        "type": "application",
+        "bom-ref": root,
+        "name": info.name(),
+        "version": info.version(),
+    });
+    if let Some(expression) = license_expression(info) {
+        metadata_component["licenses"] = json!([{ "expression": expression }]);
+    }
+
+    let entries: Vec<Value> = components
+        .iter()
+        .map(|component| {
+            let mut entry = json!({
+                "type": "library",
+                "bom-ref": component.reference(),
+                "name": component.name,
+                "scope": cyclonedx_scope(component.scope),
+                "hashes": [{ "alg": "SHA-256", "content": component.sha256 }],
+                "properties": [
+                    { "name": "wisteria:dependency", "value": component.dependency },
+                    { "name": "wisteria:scope", "value": component.scope.to_string() },
+                ],
+            });
+            if let Some(group) = &component.group {
+                entry["group"] = json!(group);
+            }
+            if let Some(version) = &component.version {
+                entry["version"] = json!(version);
+            }
+            if let Some(purl) = &component.purl {
+                entry["purl"] = json!(purl);
+            }
+            if let Some(url) = &component.download_url {
+                entry["externalReferences"] = json!([{ "type": "distribution", "url": url }]);
+            }
+            entry
+        })
+        .collect();
+
+    json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "metadata": {
+            "timestamp": timestamp,
+            "tools": {
+                "components": [{
+                    "type": "application",
+                    "name": "wisteria",
+                    "version": env!("CARGO_PKG_VERSION"),
+                }],
+            },
+            "component": metadata_component,
+        },
+        "components": entries,
+        "dependencies": [{
+            "ref": root,
+            "dependsOn": components.iter().map(SbomComponent::reference).collect::<Vec<String>>(),
+        }],
+    })
+}
+
+/// Test dependencies never ship, and provided ones are supplied by the runtime environment.
+fn cyclonedx_scope(scope: DependencyScope) -> &'static str {
+    match scope {
+        DependencyScope::Compile | DependencyScope::Runtime => "required",
+        DependencyScope::Provided => "optional",
+        DependencyScope::Test => "excluded",
+    }
+}
+
+fn spdx(info: &ProjectInfo, components: &[SbomComponent], timestamp: &str) -> Value {
+    let root = format!("SPDXRef-Package-{}", spdx_id(info.name()));
+    let mut packages = vec![json!({
+        "SPDXID": root,
+        "name": info.name(),
+        "versionInfo": info.version(),
+        "downloadLocation": "NOASSERTION",
+        "filesAnalyzed": false,
+        "licenseConcluded": "NOASSERTION",
+        "licenseDeclared": license_expression(info).unwrap_or(String::from("NOASSERTION")),
+        "copyrightText": "NOASSERTION",
+        "primaryPackagePurpose": "APPLICATION",
+    })];
+    let mut relationships = vec![json!({
+        "spdxElementId": "SPDXRef-DOCUMENT",
+        "relationshipType": "DESCRIBES",
+        "relatedSpdxElement": root,
+    })];
+
+    for (index, component) in components.iter().enumerate() {
+        let id = format!("SPDXRef-Package-{}-{}", index + 1, spdx_id(&component.name));
+        let mut package = json!({
+            "SPDXID": id,
+            "name": component.name,
+            "downloadLocation": component.download_url.as_deref().unwrap_or("NOASSERTION"),
+            "filesAnalyzed": false,
+            "checksums": [{ "algorithm": "SHA256", "checksumValue": component.sha256 }],
+            "licenseConcluded": "NOASSERTION",
+            "licenseDeclared": "NOASSERTION",
+            "copyrightText": "NOASSERTION",
+            "primaryPackagePurpose": "LIBRARY",
+        });
+        if let Some(version) = &component.version {
+            package["versionInfo"] = json!(version);
+        }
+        if let Some(group) = &component.group {
+            package["supplier"] = json!(format!("Organization: {group}"));
+        }
+        if let Some(purl) = &component.purl {
+            package["externalRefs"] = json!([{
+                "referenceCategory": "PACKAGE-MANAGER",
+                "referenceType": "purl",
+                "referenceLocator": purl,
+            }]);
+        }
+        packages.push(package);
+        relationships.push(json!({
+            "spdxElementId": id,
+            "relationshipType": spdx_relationship(component.scope),
+            "relatedSpdxElement": root,
+        }));
+    }
+
+    json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": format!("{}-{}", info.name(), info.version()),
+        "documentNamespace": format!(
+            "https://spdx.org/spdxdocs/{}-{}-{}",
+            spdx_id(info.name()),
+            spdx_id(info.version()),
+            document_digest(components)
+        ),
+        "creationInfo": {
+            "created": timestamp,
+            "creators": [format!("Tool: wisteria-{}", env!("CARGO_PKG_VERSION"))],
+        },
+        "packages": packages,
+        "relationships": relationships,
+    })
+}
+
+fn spdx_relationship(scope: DependencyScope) -> &'static str {
+    match scope {
+        DependencyScope::Compile => "DEPENDENCY_OF",
+        DependencyScope::Runtime => "RUNTIME_DEPENDENCY_OF",
+        DependencyScope::Provided => "PROVIDED_DEPENDENCY_OF",
+        DependencyScope::Test => "TEST_DEPENDENCY_OF",
+    }
+}
+
+/// SPDX identifiers may only contain letters, numbers, `.` and `-`.
+fn spdx_id(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// Keeps the document namespace unique per set of components, and stable across runs.
+fn document_digest(components: &[SbomComponent]) -> String {
+    let mut hasher = Sha256::new();
+    for component in components {
+        hasher.update(component.reference());
+        hasher.update(&component.sha256);
+    }
+    hex::encode(hasher.finalize())
+}
+
+fn build_timestamp() -> u64 {
+    env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.parse().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default()
+        })
+}
+
+/// Formats seconds since the Unix epoch as a UTC RFC 3339 timestamp.
+fn rfc3339(seconds: u64) -> String {
+    let days = (seconds / 86_400) as i64;
+    let time = seconds % 86_400;
+
+    // Converts days since 1970-01-01 to a civil date, from Howard Hinnant's `civil_from_days`.
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::cli::commands::envvar_regexes;
+    use crate::test_support::{TempDir, with_current_dir};
+
+    fn project(temp: &TempDir) -> Project {
+        let project_file = temp.path().join("project.toml");
+        fs::write(
+            &project_file,
+            r#"
+            [project]
+            name = "Demo"
+            version = "1.0.0"
+            description = "Demo project"
+            license = [ "MIT" ]
+
+            [dependencies.maven]
+            gson = { group_id = "com.google.code.gson", artifact_id = "gson", version = "2.11.0" }
+
+            [dependencies.archive]
+            local = { path = "lib/local.jar" }
+
+            [configuration.main]
+            sources = [ "src" ]
+            dependencies = [ "gson", { name = "local", scope = "provided" } ]
+            "#,
+        )
+        .unwrap();
+        fs::create_dir_all(temp.path().join("lib")).unwrap();
+        fs::write(temp.path().join("lib/local.jar"), "local").unwrap();
+
+        Project::from(Some(project_file.to_string_lossy().to_string())).unwrap()
+    }
+
+    fn lockfile() -> Lockfile {
+        Lockfile::from_artifacts_for_test(vec![LockfileArtifact::new(
+            String::from("gson"),
+            String::from("maven"),
+            Some(String::from("2.11.0")),
+            String::from(
+                "https://repo1.maven.org/maven2/com/google/code/gson/gson/2.11.0/gson-2.11.0.jar",
+            ),
+            String::from(".wisteria/cache/com.google.code.gson/gson-2.11.0.jar"),
+            String::from("abc123"),
+        )])
+    }
+
+    fn components(temp: &TempDir, project: &Project) -> Vec<SbomComponent> {
+        with_current_dir(temp.path(), || {
+            sbom_components(
+                project,
+                &project.info().configurations()["main"],
+                Some(&lockfile()),
+                &envvar_regexes(),
+            )
+            .unwrap()
+        })
+    }
+
+    #[test]
+    fn collects_locked_and_local_components() {
+        let temp = TempDir::new("sbom-components");
+        let project = project(&temp);
+        let components = components(&temp, &project);
+
+        assert_eq!(components.len(), 2);
+        assert_eq!(
+            components[0].purl.as_deref(),
+            Some("pkg:maven/com.google.code.gson/gson@2.11.0")
+        );
+        assert_eq!(components[0].sha256, "abc123");
+        assert_eq!(components[1].name, "local.jar");
+        assert_eq!(
+            components[1].sha256,
+            files::generate_sha2_for_file(&temp.path().join("lib/local.jar")).unwrap()
+        );
+        assert_eq!(components[1].scope, DependencyScope::Provided);
+    }
+
+    #[test]
+    fn rejects_unlocked_fetched_dependencies() {
+        let temp = TempDir::new("sbom-unlocked");
+        let project = project(&temp);
+        let error = sbom_components(
+            &project,
+            &project.info().configurations()["main"],
+            None,
+            &envvar_regexes(),
+        )
+        .unwrap_err();
+
+        assert!(error.contains("wisteria sync gson"));
+    }
+
+    #[test]
+    fn writes_cyclonedx_and_spdx_documents() {
+        let temp = TempDir::new("sbom-documents");
+        let project = project(&temp);
+        let components = components(&temp, &project);
+
+        let cyclonedx = cyclonedx(project.info(), &components, "2026-01-01T00:00:00Z");
+        assert_eq!(
+            cyclonedx["metadata"]["component"]["licenses"][0]["expression"],
+            "MIT"
+        );
+        assert_eq!(cyclonedx["components"][0]["group"], "com.google.code.gson");
+        assert_eq!(cyclonedx["components"][1]["scope"], "optional");
+        assert_eq!(
+            cyclonedx["dependencies"][0]["dependsOn"][1],
+            "local/local.jar"
+        );
+
+        let spdx = spdx(project.info(), &components, "2026-01-01T00:00:00Z");
+        assert_eq!(spdx["packages"][0]["licenseDeclared"], "MIT");
+        assert_eq!(
+            spdx["packages"][1]["externalRefs"][0]["referenceLocator"],
+            "pkg:maven/com.google.code.gson/gson@2.11.0"
+        );
+        assert_eq!(
+            spdx["relationships"][2]["relationshipType"],
+            "PROVIDED_DEPENDENCY_OF"
+        );
+    }
+
+    #[test]
+    fn formats_rfc3339_timestamps() {
+        assert_eq!(rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(rfc3339(1_792_367_999), "2026-10-18T23:59:59Z");
+    }
+}
@@ -1,2 +1,3 @@
 pub mod compiler_flags;
+pub mod launcher;
 pub mod manifest;
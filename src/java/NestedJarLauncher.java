@@ -0,0 +1,78 @@
+package wisteria.launcher;
+
+import java.io.File;
+import java.io.IOException;
+import java.io.InputStream;
+import java.lang.reflect.Method;
+import java.net.URL;
+import java.net.URLClassLoader;
+import java.nio.file.Files;
+import java.nio.file.Path;
+import java.nio.file.StandardCopyOption;
+import java.util.ArrayList;
+import java.util.Enumeration;
+import java.util.List;
+import java.util.jar.JarEntry;
+import java.util.jar.JarFile;
+
+/**
+ * Bootstrap entry point written by Wisteria for jars with nested dependencies.
+ *
+ * Nested jars under META-INF/lib/ are extracted unmodified to a temporary folder and loaded alongside this jar, so
+ * signed and multi-release dependencies keep working. The real main class is read from the Wisteria-Main-Class
+ * manifest attribute.
+ */
+public final class NestedJarLauncher {
+    private static final String LIBRARY_PREFIX = "META-INF/lib/";
+    private static final String MAIN_CLASS_ATTRIBUTE = "Wisteria-Main-Class";
+
+    private NestedJarLauncher() {}
+
+    public static void main(String[] args) throws Throwable {
+        File self = new File(NestedJarLauncher.class.getProtectionDomain().getCodeSource().getLocation().toURI());
+        List<URL> urls = new ArrayList<URL>();
+        urls.add(self.toURI().toURL());
+
+        String mainClass;
+        try (JarFile jar = new JarFile(self)) {
+            mainClass = jar.getManifest().getMainAttributes().getValue(MAIN_CLASS_ATTRIBUTE);
+            if (mainClass == null) {
+                throw new IllegalStateException("Missing " + MAIN_CLASS_ATTRIBUTE + " manifest attribute");
+            }
+
+            Path directory = Files.createTempDirectory("wisteria-nested");
+            directory.toFile().deleteOnExit();
+            Enumeration<JarEntry> entries = jar.entries();
+            while (entries.hasMoreElements()) {
+                JarEntry entry = entries.nextElement();
+                if (entry.isDirectory() || !entry.getName().startsWith(LIBRARY_PREFIX)) {
+                    continue;
+                }
+
+                urls.add(extract(jar, entry, directory).toUri().toURL());
+            }
+        }
+
+        ClassLoader parent = ClassLoader.getSystemClassLoader().getParent();
+        URLClassLoader loader = new URLClassLoader(urls.toArray(new URL[0]), parent);
+        Thread.currentThread().setContextClassLoader(loader);
+
+        Method main = Class.forName(mainClass, false, loader).getMethod("main", String[].class);
+        try {
+            main.invoke(null, (Object) args);
+        } catch (java.lang.reflect.InvocationTargetException e) {
+            throw e.getCause();
+        }
+    }
+
+    private static Path extract(JarFile jar, JarEntry entry, Path directory) throws IOException {
+        String name = entry.getName().substring(LIBRARY_PREFIX.length()).replace('/', '_');
+        Path target = directory.resolve(name);
+        try (InputStream input = jar.getInputStream(entry)) {
+            Files.copy(input, target, StandardCopyOption.REPLACE_EXISTING);
+        }
+        target.toFile().deleteOnExit();
+
+        return target;
+    }
+}
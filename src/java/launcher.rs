@@ -0,0 +1,4 @@
+pub const NESTED_LAUNCHER_SOURCE: &str = include_str!("NestedJarLauncher.java");
+pub const NESTED_LAUNCHER_SOURCE_FILE: &str = "wisteria/launcher/NestedJarLauncher.java";
+pub const NESTED_LAUNCHER_CLASS: &str = "wisteria.launcher.NestedJarLauncher";
+pub const NESTED_LIBRARY_DIR: &str = "META-INF/lib";
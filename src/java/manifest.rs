@@ -6,6 +6,7 @@ pub enum ManifestEntry {
     Version { version: String },
     CreatedBy { signature: String },
     MainClass { class: String },
+    NestedMainClass { class: String },
     ClassPath { path: Vec<String> },
 }
 
@@ -45,6 +46,7 @@ impl ManifestEntry {
             ManifestEntry::Version { version } => format!("Manifest-Version: {version}\n"),
             ManifestEntry::CreatedBy { signature } => format!("Created-By: {signature}\n"),
             ManifestEntry::MainClass { class } => format!("Main-Class: {class}\n"),
+            ManifestEntry::NestedMainClass { class } => format!("Wisteria-Main-Class: {class}\n"),
             ManifestEntry::ClassPath { path } => {
                 let mut attribute_raw: String = String::from("Class-Path: ");
 
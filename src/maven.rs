@@ -1,4 +1,5 @@
 pub mod metadata;
+pub mod pom;
 pub mod repository;
 
 pub use metadata as nexus;
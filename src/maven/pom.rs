@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use serde_xml_rs::from_str;
+
+//
+// XML decoding stuff
+//
+/// The parts of a POM which are needed to find an artifact's licenses, or the versions a BOM
+/// manages.
+#[derive(Deserialize)]
+pub struct Pom {
+    parent: Option<PomParent>,
+    licenses: Option<PomLicenses>,
+    #[serde(default)]
+    properties: HashMap<String, String>,
+    #[serde(rename = "dependencyManagement")]
+    dependency_management: Option<PomDependencyManagement>,
+}
+
+#[derive(Deserialize)]
+pub struct PomParent {
+    #[serde(rename = "groupId")]
+    pub group_id: String,
+    #[serde(rename = "artifactId")]
+    pub artifact_id: String,
+    pub version: String,
+}
+
+#[derive(Deserialize)]
+struct PomLicenses {
+    #[serde(default)]
+    license: Vec<PomLicense>,
+}
+
+#[derive(Deserialize)]
+struct PomDependencyManagement {
+    dependencies: Option<PomDependencies>,
+}
+
+#[derive(Deserialize)]
+struct PomDependencies {
+    #[serde(default)]
+    dependency: Vec<PomDependency>,
+}
+
+/// A `<dependency>` of `<dependencyManagement>`, with properties not yet replaced.
+#[derive(Deserialize, Debug, PartialEq, Eq)]
+pub struct PomDependency {
+    #[serde(rename = "groupId")]
+    pub group_id: String,
+    #[serde(rename = "artifactId")]
+    pub artifact_id: String,
+    pub version: Option<String>,
+    pub scope: Option<String>,
+    #[serde(rename = "type")]
+    pub packaging: Option<String>,
+}
+
+impl PomDependency {
+    /// Whether this entry imports the managed versions of another BOM.
+    pub fn is_import(&self) -> bool {
+        self.scope.as_deref() == Some("import")
+    }
+}
+
+#[derive(Clone, Deserialize, Debug, PartialEq, Eq)]
+pub struct PomLicense {
+    pub name: Option<String>,
+    pub url: Option<String>,
+}
+
+impl Pom {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        from_str(text).map_err(|e| format!("Could not decode POM: {e}"))
+    }
+
+    pub fn parent(&self) -> Option<&PomParent> {
+        self.parent.as_ref()
+    }
+
+    pub fn properties(&self) -> &HashMap<String, String> {
+        &self.properties
+    }
+
+    pub fn managed_dependencies(&self) -> &[PomDependency] {
+        self.dependency_management
+            .as_ref()
+            .and_then(|management| management.dependencies.as_ref())
+            .map(|dependencies| dependencies.dependency.as_slice())
+            .unwrap_or_default()
+    }
+
+    pub fn licenses(&self) -> &[PomLicense] {
+        self.licenses
+            .as_ref()
+            .map(|licenses| licenses.license.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_licenses_and_parent_from_a_full_pom() {
+        let pom = Pom::parse(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <project xmlns="http://maven.apache.org/POM/4.0.0">
+              <modelVersion>4.0.0</modelVersion>
+              <parent>
+                <groupId>com.google.code.gson</groupId>
+                <artifactId>gson-parent</artifactId>
+                <version>2.11.0</version>
+              </parent>
+              <artifactId>gson</artifactId>
+              <name>Gson</name>
+              <licenses>
+                <license>
+                  <name>Apache-2.0</name>
+                  <url>https://www.apache.org/licenses/LICENSE-2.0.txt</url>
+                </license>
+              </licenses>
+              <dependencies>
+                <dependency>
+                  <groupId>com.google.errorprone</groupId>
+                  <artifactId>error_prone_annotations</artifactId>
+                </dependency>
+              </dependencies>
+            </project>"#,
+        )
+        .unwrap();
+
+        assert_eq!(pom.parent().unwrap().artifact_id, "gson-parent");
+        assert_eq!(
+            pom.licenses(),
+            [PomLicense {
+                name: Some(String::from("Apache-2.0")),
+                url: Some(String::from(
+                    "https://www.apache.org/licenses/LICENSE-2.0.txt"
+                )),
+            }]
+        );
+    }
+
+    #[test]
+    fn reads_properties_and_managed_dependencies_from_a_bom() {
+        let pom = Pom::parse(
+            r#"<project>
+              <artifactId>jackson-bom</artifactId>
+              <properties>
+                <jackson.version>2.17.1</jackson.version>
+              </properties>
+              <dependencyManagement>
+                <dependencies>
+                  <dependency>
+                    <groupId>com.fasterxml.jackson.core</groupId>
+                    <artifactId>jackson-databind</artifactId>
+                    <version>${jackson.version}</version>
+                  </dependency>
+                  <dependency>
+                    <groupId>org.junit</groupId>
+                    <artifactId>junit-bom</artifactId>
+                    <version>5.10.2</version>
+                    <type>pom</type>
+                    <scope>import</scope>
+                  </dependency>
+                </dependencies>
+              </dependencyManagement>
+            </project>"#,
+        )
+        .unwrap();
+
+        assert_eq!(pom.properties()["jackson.version"], "2.17.1");
+        let managed = pom.managed_dependencies();
+        assert_eq!(managed.len(), 2);
+        assert_eq!(managed[0].version.as_deref(), Some("${jackson.version}"));
+        assert!(!managed[0].is_import());
+        assert!(managed[1].is_import());
+    }
+
+    #[test]
+    fn allows_poms_without_licenses() {
+        let pom = Pom::parse("<project><artifactId>library</artifactId></project>").unwrap();
+
+        assert!(pom.parent().is_none());
+        assert!(pom.licenses().is_empty());
+        assert!(pom.managed_dependencies().is_empty());
+    }
+}
@@ -1,14 +1,40 @@
+use std::fs;
+
 use reqwest::blocking::Client;
 use serde_xml_rs::from_str;
 
+use crate::dependency::version::VersionRange;
 use crate::maven::metadata::{MavenMetadata, SnapshotMetadata};
+use crate::workspace::{credentials, download, settings};
 
 const MAVEN_METADATA_FILE: &str = "maven-metadata.xml";
 
 pub enum ArtifactVersion {
     Latest,
     Release,
-    Version { version: String },
+    Version {
+        version: String,
+    },
+    /// The highest published version in the range.
+    Range {
+        range: VersionRange,
+    },
+}
+
+impl ArtifactVersion {
+    /// Reads the `version` of a Maven dependency, which defaults to the latest version.
+    pub fn load(version: Option<&String>) -> Result<Self, String> {
+        match version.map(String::as_str) {
+            Some("latest") | None => Ok(ArtifactVersion::Latest),
+            Some("release") => Ok(ArtifactVersion::Release),
+            Some(version) if VersionRange::is_range(version) => Ok(ArtifactVersion::Range {
+                range: VersionRange::parse(version)?,
+            }),
+            Some(version) => Ok(ArtifactVersion::Version {
+                version: version.to_string(),
+            }),
+        }
+    }
 }
 
 pub fn get_artifact(
@@ -35,33 +61,7 @@ pub fn get_artifact(
     let metadata: MavenMetadata =
         from_str(&version_text).map_err(|e| format!("Could not decode maven metadata: {e}"))?;
 
-    let target_version = match version {
-        ArtifactVersion::Latest => match metadata.latest() {
-            Some(v) => v.clone(),
-            None => {
-                return Err(format!(
-                    "Artifact {artifact_id} does not specify a latest version, must explicitly specify a version"
-                ));
-            }
-        },
-        ArtifactVersion::Release => match metadata.release() {
-            Some(v) => v.clone(),
-            None => {
-                return Err(format!(
-                    "Artifact {artifact_id} does not specify a release version, must explicitly specify a version"
-                ));
-            }
-        },
-        ArtifactVersion::Version { version } => {
-            if !metadata.versions().contains(version) {
-                return Err(format!(
-                    "Artifact {artifact_id} does not have a version {version}"
-                ));
-            }
-
-            version.clone()
-        }
-    };
+    let target_version = select_version(&metadata, artifact_id, version)?;
 
     // Check if there we're dealing with a snapshot-based repository
     request_url = String::from(url);
@@ -127,6 +127,64 @@ pub fn get_artifact(
     ))
 }
 
+fn select_version(
+    metadata: &MavenMetadata,
+    artifact_id: &str,
+    version: &ArtifactVersion,
+) -> Result<String, String> {
+    match version {
+        ArtifactVersion::Latest => metadata.latest().cloned().ok_or_else(|| {
+            format!(
+                "Artifact {artifact_id} does not specify a latest version, must explicitly specify a version"
+            )
+        }),
+        ArtifactVersion::Release => metadata.release().cloned().ok_or_else(|| {
+            format!(
+                "Artifact {artifact_id} does not specify a release version, must explicitly specify a version"
+            )
+        }),
+        ArtifactVersion::Version { version } => {
+            if !metadata.versions().contains(version) {
+                return Err(format!(
+                    "Artifact {artifact_id} does not have a version {version}"
+                ));
+            }
+
+            Ok(version.clone())
+        }
+        ArtifactVersion::Range { range } => {
+            range.highest(metadata.versions()).cloned().ok_or_else(|| {
+                format!("Artifact {artifact_id} does not have a version in the range {range}")
+            })
+        }
+    }
+}
+
+/// Lists every version an artifact's `maven-metadata.xml` in the repository at `url` publishes.
+pub fn get_versions(
+    client: &Client,
+    url: &str,
+    group_id: &str,
+    artifact_id: &str,
+) -> Result<Vec<String>, String> {
+    let mut request_url = String::from(url);
+    if !request_url.ends_with("/") {
+        request_url.push('/')
+    }
+
+    request_url = format!(
+        "{request_url}{}/{}/{MAVEN_METADATA_FILE}",
+        group_id.replace(".", "/"),
+        artifact_id.replace(".", "/")
+    );
+
+    let version_text: String = get_text_at_url(&request_url, client)?;
+    let metadata: MavenMetadata =
+        from_str(&version_text).map_err(|e| format!("Could not decode maven metadata: {e}"))?;
+
+    Ok(metadata.versions().to_vec())
+}
+
 pub fn get_version(
     client: &Client,
     url: &str,
@@ -152,33 +210,7 @@ pub fn get_version(
     let metadata: MavenMetadata =
         from_str(&version_text).map_err(|e| format!("Could not decode maven metadata: {e}"))?;
 
-    let target_version = match version {
-        ArtifactVersion::Latest => match metadata.latest() {
-            Some(v) => v.clone(),
-            None => {
-                return Err(format!(
-                    "Artifact {artifact_id} does not specify a latest version, must explicitly specify a version"
-                ));
-            }
-        },
-        ArtifactVersion::Release => match metadata.release() {
-            Some(v) => v.clone(),
-            None => {
-                return Err(format!(
-                    "Artifact {artifact_id} does not specify a release version, must explicitly specify a version"
-                ));
-            }
-        },
-        ArtifactVersion::Version { version } => {
-            if !metadata.versions().contains(version) {
-                return Err(format!(
-                    "Artifact {artifact_id} does not have a version {version}"
-                ));
-            }
-
-            version.clone()
-        }
-    };
+    let target_version = select_version(&metadata, artifact_id, version)?;
 
     // Check if there we're dealing with a snapshot-based repository
     request_url = String::from(url);
@@ -206,10 +238,31 @@ pub fn get_version(
     Ok((target_version, snapshot_value))
 }
 
-fn get_text_at_url(url: &str, client: &Client) -> Result<String, String> {
-    let response = match client.get(url).send() {
+/// URL of the POM for a released artifact in the repository at `url`.
+pub fn pom_url(url: &str, group_id: &str, artifact_id: &str, version: &str) -> String {
+    format!(
+        "{}/{}/{}/{version}/{artifact_id}-{version}.pom",
+        url.trim_end_matches('/'),
+        group_id.replace(".", "/"),
+        artifact_id.replace(".", "/")
+    )
+}
+
+pub fn get_text_at_url(url: &str, client: &Client) -> Result<String, String> {
+    let url = &settings::settings().mirror(url);
+    if let Some(path) = download::local_file(url) {
+        return fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {e}", path.display()));
+    }
+    download::ensure_online(url)?;
+
+    let response = match credentials::authorize(client.get(url), url).send() {
         Ok(r) => r,
-        Err(e) => return Err(format!("Could not reach server at URL {url} ({e})")),
+        Err(e) => {
+            return Err(credentials::redact(&format!(
+                "Could not reach server at URL {url} ({e})"
+            )));
+        }
     };
 
     if !response.status().is_success() {
@@ -6,8 +6,11 @@ use toml::{Table, Value};
 
 use crate::{
     build::{
+        distribution::ImplicitDistributionTask,
+        image::ImplicitImageTask,
         javadoc::ImplicitJavadocTask,
         run::ImplicitRunTask,
+        shell::ImplicitShellTask,
         task::{DefinedTask, ImplicitBuildTask, TaskRunner},
     },
     cli::args::StartupFlags,
@@ -49,6 +52,145 @@ impl JavadocConfiguration {
     }
 }
 
+#[derive(Clone, PartialEq, Eq)]
+pub struct ShellConfiguration {
+    imports: Option<Vec<String>>,
+}
+
+impl ShellConfiguration {
+    fn from(configuration_name: &str, toml: &Table) -> Result<Self, String> {
+        let imports = toml_utils::read_optional_string_array("imports", toml).map_err(|error| {
+            format!("Invalid [configuration.{configuration_name}.shell].imports: {error}")
+        })?;
+
+        Ok(Self { imports })
+    }
+
+    pub fn imports(&self) -> Option<&Vec<String>> {
+        self.imports.as_ref()
+    }
+
+    fn inherit_from(&mut self, configuration: &ShellConfiguration) {
+        self.imports = inherit_vec(self.imports.as_mut(), configuration.imports.as_ref());
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct ImageConfiguration {
+    output_dir: Option<String>,
+    launcher: Option<String>,
+    modules: Option<Vec<String>>,
+}
+
+impl ImageConfiguration {
+    fn from(configuration_name: &str, toml: &Table) -> Result<Self, String> {
+        let modules = toml_utils::read_optional_string_array("modules", toml).map_err(|error| {
+            format!("Invalid [configuration.{configuration_name}.image].modules: {error}")
+        })?;
+
+        Ok(Self {
+            output_dir: read_optional_image_string(configuration_name, toml, "output-dir")?,
+            launcher: read_optional_image_string(configuration_name, toml, "launcher")?,
+            modules,
+        })
+    }
+
+    pub fn output_dir(&self) -> Option<&String> {
+        self.output_dir.as_ref()
+    }
+
+    pub fn launcher(&self) -> Option<&String> {
+        self.launcher.as_ref()
+    }
+
+    pub fn modules(&self) -> Option<&Vec<String>> {
+        self.modules.as_ref()
+    }
+
+    fn inherit_from(&mut self, configuration: &ImageConfiguration) {
+        if self.output_dir.is_none() {
+            self.output_dir = configuration.output_dir.clone();
+        }
+        if self.launcher.is_none() {
+            self.launcher = configuration.launcher.clone();
+        }
+        self.modules = inherit_vec(self.modules.as_mut(), configuration.modules.as_ref());
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DistributionFormat {
+    Zip,
+    TarGz,
+}
+
+impl DistributionFormat {
+    fn load(value: &str) -> Result<Self, String> {
+        match value.to_lowercase().as_str() {
+            "zip" => Ok(DistributionFormat::Zip),
+            "tar.gz" | "tgz" => Ok(DistributionFormat::TarGz),
+            _ => Err(format!(
+                "Unknown distribution format \"{value}\".\nFix: use `format = \"zip\"` or `format = \"tar.gz\"`."
+            )),
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DistributionFormat::Zip => "zip",
+            DistributionFormat::TarGz => "tar.gz",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct DistributionConfiguration {
+    output_dir: Option<String>,
+    format: Option<DistributionFormat>,
+    launcher: Option<String>,
+}
+
+impl DistributionConfiguration {
+    fn from(configuration_name: &str, toml: &Table) -> Result<Self, String> {
+        let format = read_optional_distribution_string(configuration_name, toml, "format")?
+            .map(|format| DistributionFormat::load(&format))
+            .transpose()
+            .map_err(|error| {
+                format!("Invalid [configuration.{configuration_name}.distribution].format: {error}")
+            })?;
+
+        Ok(Self {
+            output_dir: read_optional_distribution_string(configuration_name, toml, "output-dir")?,
+            format,
+            launcher: read_optional_distribution_string(configuration_name, toml, "launcher")?,
+        })
+    }
+
+    pub fn output_dir(&self) -> Option<&String> {
+        self.output_dir.as_ref()
+    }
+
+    pub fn format(&self) -> DistributionFormat {
+        self.format.unwrap_or(DistributionFormat::Zip)
+    }
+
+    pub fn launcher(&self) -> Option<&String> {
+        self.launcher.as_ref()
+    }
+
+    fn inherit_from(&mut self, configuration: &DistributionConfiguration) {
+        if self.output_dir.is_none() {
+            self.output_dir = configuration.output_dir.clone();
+        }
+        if self.format.is_none() {
+            self.format = configuration.format;
+        }
+        if self.launcher.is_none() {
+            self.launcher = configuration.launcher.clone();
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Configuration {
     name: String,
@@ -57,9 +199,13 @@ pub struct Configuration {
     includes: Option<Vec<String>>,
     targets: Option<Vec<String>>,
     javadoc: Option<JavadocConfiguration>,
+    shell: Option<ShellConfiguration>,
+    image: Option<ImageConfiguration>,
+    distribution: Option<DistributionConfiguration>,
 
     entry: Option<String>,
     java_version: u8,
+    copy_libraries: Option<bool>,
 
     tasks: HashMap<String, Rc<dyn TaskRunner>>,
     compiler_flags: Option<Vec<CompilerFlags>>,
@@ -101,10 +247,49 @@ impl Configuration {
             }
             None => None,
         };
+        let shell = match toml.get("shell") {
+            Some(v) if v.is_table() => {
+                Some(ShellConfiguration::from(&name, v.as_table().unwrap())?)
+            }
+            Some(v) => {
+                return Err(format!(
+                    "Invalid [configuration.{name}].shell: expected a table, found {}.\nFix: define shell settings under `[configuration.{name}.shell]`, or remove `shell`.",
+                    v.type_str()
+                ));
+            }
+            None => None,
+        };
+        let image = match toml.get("image") {
+            Some(v) if v.is_table() => {
+                Some(ImageConfiguration::from(&name, v.as_table().unwrap())?)
+            }
+            Some(v) => {
+                return Err(format!(
+                    "Invalid [configuration.{name}].image: expected a table, found {}.\nFix: define runtime image settings under `[configuration.{name}.image]`, or remove `image`.",
+                    v.type_str()
+                ));
+            }
+            None => None,
+        };
+        let distribution = match toml.get("distribution") {
+            Some(v) if v.is_table() => Some(DistributionConfiguration::from(
+                &name,
+                v.as_table().unwrap(),
+            )?),
+            Some(v) => {
+                return Err(format!(
+                    "Invalid [configuration.{name}].distribution: expected a table, found {}.\nFix: define distribution settings under `[configuration.{name}.distribution]`, or remove `distribution`.",
+                    v.type_str()
+                ));
+            }
+            None => None,
+        };
 
         let entry = read_optional_string_for_configuration(&name, "entry", toml)?;
         let java_version =
             read_optional_integer_for_configuration(&name, "java_version", toml)?.unwrap_or(8);
+        let copy_libraries = toml_utils::read_optional_boolean("copy_libraries", toml)
+            .map_err(|error| contextual_configuration_error(&name, "copy_libraries", error))?;
         let inherit: Option<String> =
             read_optional_string_for_configuration(&name, "inherit", toml)?;
 
@@ -196,8 +381,12 @@ impl Configuration {
             includes,
             targets,
             javadoc,
+            shell,
+            image,
+            distribution,
             entry,
             java_version,
+            copy_libraries,
             tasks,
             compiler_flags,
             environment,
@@ -237,6 +426,41 @@ impl Configuration {
         self.javadoc.as_ref().and_then(JavadocConfiguration::target)
     }
 
+    pub fn shell_imports(&self) -> Option<&Vec<String>> {
+        self.shell.as_ref().and_then(ShellConfiguration::imports)
+    }
+
+    pub fn image(&self) -> Option<&ImageConfiguration> {
+        self.image.as_ref()
+    }
+
+    pub fn image_output_dir(&self) -> &str {
+        self.image
+            .as_ref()
+            .and_then(ImageConfiguration::output_dir)
+            .map(String::as_str)
+            .unwrap_or(consts::DEFAULT_IMAGE_DIR)
+    }
+
+    pub fn distribution(&self) -> Option<&DistributionConfiguration> {
+        self.distribution.as_ref()
+    }
+
+    pub fn distribution_output_dir(&self) -> &str {
+        self.distribution
+            .as_ref()
+            .and_then(DistributionConfiguration::output_dir)
+            .map(String::as_str)
+            .unwrap_or(consts::DEFAULT_DISTRIBUTION_DIR)
+    }
+
+    pub fn distribution_format(&self) -> DistributionFormat {
+        self.distribution
+            .as_ref()
+            .map(DistributionConfiguration::format)
+            .unwrap_or(DistributionFormat::Zip)
+    }
+
     pub fn entry(&self) -> Option<&String> {
         self.entry.as_ref()
     }
@@ -245,6 +469,10 @@ impl Configuration {
         self.java_version
     }
 
+    pub fn copy_libraries(&self) -> bool {
+        self.copy_libraries.unwrap_or(false)
+    }
+
     pub fn tasks(&self) -> &HashMap<String, Rc<dyn TaskRunner>> {
         &self.tasks
     }
@@ -267,6 +495,10 @@ impl Configuration {
                 String::from("javadocs"),
                 Rc::new(ImplicitJavadocTask::new()),
             );
+            self.tasks.insert(
+                String::from("shell"),
+                Rc::new(ImplicitShellTask::new(flags.clone())),
+            );
 
             if self.targets().is_some() {
                 self.tasks
@@ -274,6 +506,12 @@ impl Configuration {
             }
 
             if self.entry.is_some() {
+                self.tasks
+                    .insert(String::from("image"), Rc::new(ImplicitImageTask::new()));
+                self.tasks.insert(
+                    String::from("distribution"),
+                    Rc::new(ImplicitDistributionTask::new()),
+                );
                 self.tasks
                     .insert(String::from("run"), Rc::new(ImplicitRunTask::new(flags)));
             }
@@ -299,10 +537,31 @@ impl Configuration {
             (None, Some(parent_javadoc)) => self.javadoc = Some(parent_javadoc.clone()),
             _ => {}
         }
+        match (self.shell.as_mut(), configuration.shell.as_ref()) {
+            (Some(shell), Some(parent_shell)) => shell.inherit_from(parent_shell),
+            (None, Some(parent_shell)) => self.shell = Some(parent_shell.clone()),
+            _ => {}
+        }
+        match (self.image.as_mut(), configuration.image.as_ref()) {
+            (Some(image), Some(parent_image)) => image.inherit_from(parent_image),
+            (None, Some(parent_image)) => self.image = Some(parent_image.clone()),
+            _ => {}
+        }
+        match (
+            self.distribution.as_mut(),
+            configuration.distribution.as_ref(),
+        ) {
+            (Some(distribution), Some(parent)) => distribution.inherit_from(parent),
+            (None, Some(parent)) => self.distribution = Some(parent.clone()),
+            _ => {}
+        }
         if self.entry.is_none() && configuration.entry.is_some() {
             self.entry = configuration.entry.clone();
         }
         self.java_version = configuration.java_version;
+        if self.copy_libraries.is_none() {
+            self.copy_libraries = configuration.copy_libraries;
+        }
         for (k, task) in configuration.tasks() {
             if !self.tasks.contains_key(k) {
                 self.tasks.insert(k.clone(), task.clone());
@@ -356,8 +615,40 @@ impl Configuration {
             }
         }
 
+        if let Some(image) = &self.image {
+            if let Some(output_dir) = image.output_dir() {
+                println!("│\tRuntime image    {output_dir}")
+            }
+
+            if let Some(modules) = image.modules() {
+                println!(
+                    "│\tImage modules    {}",
+                    toml_utils::string_vec_to_string(modules)
+                )
+            }
+        }
+
+        if let Some(distribution) = &self.distribution {
+            println!(
+                "│\tDistribution     {} ({})",
+                self.distribution_output_dir(),
+                distribution.format().extension()
+            )
+        }
+
+        if let Some(imports) = self.shell_imports() {
+            println!(
+                "│\tShell imports    {}",
+                toml_utils::string_vec_to_string(imports)
+            )
+        }
+
         println!("│\tJava version     {}", self.java_version);
 
+        if self.copy_libraries() {
+            println!("│\tLibraries        Copied to lib/ beside each target")
+        }
+
         let mut environment: String = String::new();
         for (k, v) in &self.environment {
             environment.push_str(k);
@@ -525,15 +816,20 @@ fn read_table_as_dependency_reference(
         })?;
 
     // Check for incompatible scope + packaging combinations
-    if matches!(packaging, Some(PackagingType::Shade))
+    if let Some(packaging) = packaging
         && matches!(scope, DependencyScope::Provided | DependencyScope::Test)
     {
+        let label = match packaging {
+            PackagingType::Shade => "Shaded",
+            PackagingType::Nest => "Nested",
+        };
+
         return Err(contextual_dependency_reference_error(
             configuration_name,
             index,
             "package",
-            String::from(
-                "Shaded packaging type is incompatible with provided/test scope.\nFix: remove `package = \"shade\"` or change scope to `compile` or `runtime`.",
+            format!(
+                "{label} packaging type is incompatible with provided/test scope.\nFix: remove `package = \"{packaging}\"` or change scope to `compile` or `runtime`."
             ),
         ));
     }
@@ -589,6 +885,36 @@ fn read_optional_javadoc_string(
     }
 }
 
+fn read_optional_image_string(
+    configuration_name: &str,
+    toml: &Table,
+    key: &str,
+) -> Result<Option<String>, String> {
+    match toml.get(key) {
+        Some(Value::String(value)) => Ok(Some(value.clone())),
+        Some(value) => Err(format!(
+            "Invalid [configuration.{configuration_name}.image].{key}: expected a string, found {}.\nFix: write image `{key}` as a quoted string, or remove the key.",
+            value.type_str()
+        )),
+        None => Ok(None),
+    }
+}
+
+fn read_optional_distribution_string(
+    configuration_name: &str,
+    toml: &Table,
+    key: &str,
+) -> Result<Option<String>, String> {
+    match toml.get(key) {
+        Some(Value::String(value)) => Ok(Some(value.clone())),
+        Some(value) => Err(format!(
+            "Invalid [configuration.{configuration_name}.distribution].{key}: expected a string, found {}.\nFix: write distribution `{key}` as a quoted string, or remove the key.",
+            value.type_str()
+        )),
+        None => Ok(None),
+    }
+}
+
 fn inherit_vec<T: Clone + Eq>(
     inheritor: Option<&mut Vec<T>>,
     host: Option<&Vec<T>>,
@@ -610,7 +936,7 @@ fn inherit_vec<T: Clone + Eq>(
     }
 }
 
-fn validate_unique_dependencies(
+pub(crate) fn validate_unique_dependencies(
     configuration_name: &str,
     references: &[DependencyReference],
 ) -> Result<(), String> {
@@ -835,6 +1161,117 @@ mod tests {
         );
     }
 
+    #[test]
+    fn configuration_loads_and_inherits_shell_imports() {
+        let parent = Configuration::from(
+            String::from("base"),
+            &table(
+                r#"
+                [shell]
+                imports = [ "com.example.*" ]
+                "#,
+            ),
+            String::from("Demo"),
+            String::from("1.0.0"),
+        )
+        .unwrap();
+        let mut child = Configuration::from(
+            String::from("child"),
+            &table(
+                r#"
+                [shell]
+                imports = "java.util.concurrent.*"
+                "#,
+            ),
+            String::from("Demo"),
+            String::from("1.0.0"),
+        )
+        .unwrap();
+
+        child.inherit_from(&parent).unwrap();
+
+        assert_eq!(
+            child.shell_imports().unwrap(),
+            &vec![
+                String::from("java.util.concurrent.*"),
+                String::from("com.example.*")
+            ]
+        );
+    }
+
+    #[test]
+    fn configuration_loads_distribution_format_and_rejects_unknown_formats() {
+        let configuration = Configuration::from(
+            String::from("main"),
+            &table(
+                r#"
+                [distribution]
+                format = "tar.gz"
+                "#,
+            ),
+            String::from("Demo"),
+            String::from("1.0.0"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            configuration.distribution_format(),
+            DistributionFormat::TarGz
+        );
+        assert_eq!(
+            configuration.distribution_output_dir(),
+            consts::DEFAULT_DISTRIBUTION_DIR
+        );
+
+        let error = Configuration::from(
+            String::from("main"),
+            &table(
+                r#"
+                [distribution]
+                format = "rar"
+                "#,
+            ),
+            String::from("Demo"),
+            String::from("1.0.0"),
+        )
+        .err()
+        .unwrap();
+
+        assert!(error.contains("Invalid [configuration.main.distribution].format"));
+    }
+
+    #[test]
+    fn copy_libraries_defaults_to_false_and_is_inherited() {
+        let parent = Configuration::from(
+            String::from("base"),
+            &table("copy_libraries = true"),
+            String::from("Demo"),
+            String::from("1.0.0"),
+        )
+        .unwrap();
+        let mut child = Configuration::from(
+            String::from("child"),
+            &table(""),
+            String::from("Demo"),
+            String::from("1.0.0"),
+        )
+        .unwrap();
+
+        assert!(!child.copy_libraries());
+        child.inherit_from(&parent).unwrap();
+        assert!(child.copy_libraries());
+
+        let error = Configuration::from(
+            String::from("main"),
+            &table(r#"copy_libraries = "yes""#),
+            String::from("Demo"),
+            String::from("1.0.0"),
+        )
+        .err()
+        .unwrap();
+        assert!(error.contains("Invalid [configuration.main].copy_libraries"));
+    }
+
     #[test]
     fn inherit_from_appends_unique_values_and_inherits_missing_fields() {
         let parent = Configuration::from(
@@ -1023,6 +1460,31 @@ mod tests {
         assert!(error.contains("incompatible with provided/test scope"));
     }
 
+    #[test]
+    fn loads_nested_runtime_reference_and_rejects_nested_test_reference() {
+        let configuration = Configuration::from(
+            String::from("main"),
+            &table(r#"dependencies = [ { name = "dep-a", scope = "runtime", package = "nest" } ]"#),
+            String::from("Demo"),
+            String::from("1.0.0"),
+        )
+        .unwrap();
+        assert!(configuration.dependencies().unwrap()[0].is_nested());
+
+        let error = match Configuration::from(
+            String::from("main"),
+            &table(r#"dependencies = [ { name = "dep-a", scope = "test", package = "nest" } ]"#),
+            String::from("Demo"),
+            String::from("1.0.0"),
+        ) {
+            Ok(_) => panic!("expected incompatible dependency reference to fail"),
+            Err(error) => error,
+        };
+
+        assert!(error.contains("Nested packaging type is incompatible"));
+        assert!(error.contains("remove `package = \"nest\"`"));
+    }
+
     #[test]
     fn rejects_out_of_range_java_version() {
         let error = match Configuration::from(
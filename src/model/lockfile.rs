@@ -1,9 +1,12 @@
-use std::{fs, io::ErrorKind};
+use std::{collections::BTreeSet, fs, io::ErrorKind};
 
 use serde::{Deserialize, Serialize};
 use toml::Value;
 
-use crate::{dependency::resolver::ResolvedDependency, util::consts};
+use crate::{
+    dependency::{AttachmentKind, resolver::ResolvedDependency},
+    util::consts,
+};
 
 pub const LOCKFILE_HEADER_COMMENT: &str = "# Automatically generated by Wisteria, do not edit.\n";
 pub const LOCKFILE_SCHEMA_VERSION: u16 = 1;
@@ -22,6 +25,47 @@ pub struct LockfileArtifact {
     fetch_url: String,
     cache_path: String,
     hash: String,
+    /// Fingerprint of the trusted key whose signature was verified when the artifact was locked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signing_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    attachment: Vec<LockfileAttachment>,
+}
+
+/// A sources or javadoc jar locked beside an artifact, see [`AttachmentKind`].
+#[derive(Deserialize, Serialize, Clone, PartialEq, Debug)]
+pub struct LockfileAttachment {
+    kind: AttachmentKind,
+    fetch_url: String,
+    cache_path: String,
+    hash: String,
+}
+
+impl LockfileAttachment {
+    pub fn new(kind: AttachmentKind, fetch_url: String, cache_path: String, hash: String) -> Self {
+        Self {
+            kind,
+            fetch_url,
+            cache_path,
+            hash,
+        }
+    }
+
+    pub fn kind(&self) -> AttachmentKind {
+        self.kind
+    }
+
+    pub fn fetch_url(&self) -> &str {
+        &self.fetch_url
+    }
+
+    pub fn cache_path(&self) -> &str {
+        &self.cache_path
+    }
+
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
 }
 
 impl LockfileArtifact {
@@ -40,9 +84,22 @@ impl LockfileArtifact {
             fetch_url,
             cache_path,
             hash,
+            signing_key: None,
+            attachment: Vec::new(),
+        }
+    }
+
+    pub fn with_signing_key(self, signing_key: Option<String>) -> Self {
+        Self {
+            signing_key,
+            ..self
         }
     }
 
+    pub fn with_attachments(self, attachment: Vec<LockfileAttachment>) -> Self {
+        Self { attachment, ..self }
+    }
+
     pub fn name(&self) -> &str {
         &self.name
     }
@@ -66,6 +123,20 @@ impl LockfileArtifact {
     pub fn hash(&self) -> &str {
         &self.hash
     }
+
+    pub fn signing_key(&self) -> Option<&str> {
+        self.signing_key.as_deref()
+    }
+
+    pub fn attachments(&self) -> &[LockfileAttachment] {
+        &self.attachment
+    }
+
+    pub fn attachment(&self, kind: AttachmentKind) -> Option<&LockfileAttachment> {
+        self.attachment
+            .iter()
+            .find(|attachment| attachment.kind() == kind)
+    }
 }
 
 impl Lockfile {
@@ -88,6 +159,60 @@ impl Lockfile {
     }
 }
 
+/// How the lock entries of one dependency differ between two lockfiles.
+#[derive(Debug, PartialEq)]
+pub enum LockfileChange {
+    Added(LockfileArtifact),
+    Removed(LockfileArtifact),
+    Changed {
+        old: LockfileArtifact,
+        new: LockfileArtifact,
+    },
+}
+
+impl LockfileChange {
+    pub fn name(&self) -> &str {
+        match self {
+            LockfileChange::Added(artifact) | LockfileChange::Removed(artifact) => artifact.name(),
+            LockfileChange::Changed { new, .. } => new.name(),
+        }
+    }
+}
+
+/// Compares lock entries by dependency name. A dependency with one differing entry on each side
+/// is reported as changed, and anything else as separate removals and additions.
+pub fn diff_lockfile_artifacts(
+    old: &[LockfileArtifact],
+    new: &[LockfileArtifact],
+) -> Vec<LockfileChange> {
+    let names: BTreeSet<&str> = old.iter().chain(new).map(LockfileArtifact::name).collect();
+    let mut changes = Vec::new();
+
+    for name in names {
+        let removed: Vec<&LockfileArtifact> = old
+            .iter()
+            .filter(|artifact| artifact.name() == name && !new.contains(artifact))
+            .collect();
+        let added: Vec<&LockfileArtifact> = new
+            .iter()
+            .filter(|artifact| artifact.name() == name && !old.contains(artifact))
+            .collect();
+
+        match (removed.as_slice(), added.as_slice()) {
+            ([old], [new]) => changes.push(LockfileChange::Changed {
+                old: (*old).clone(),
+                new: (*new).clone(),
+            }),
+            _ => {
+                changes.extend(removed.into_iter().cloned().map(LockfileChange::Removed));
+                changes.extend(added.into_iter().cloned().map(LockfileChange::Added));
+            }
+        }
+    }
+
+    changes
+}
+
 /// Attempts to read wisteria.lock in the current directory. If the file does not exist, Ok(None)
 /// will be returned.
 pub fn try_read_lockfile() -> Result<Option<Lockfile>, String> {
@@ -273,6 +398,8 @@ mod tests {
             fetch_url: String::from("https://example/gson.jar"),
             cache_path: String::from(".wisteria/cache/com.google.code.gson/gson.jar"),
             hash: String::from("foo"),
+            signing_key: None,
+            attachment: Vec::new(),
         }
     }
 
@@ -284,6 +411,8 @@ mod tests {
             fetch_url: String::from("https://github.com/Khyonie/Anenome.jar"),
             cache_path: String::from(".wisteria/cache/Khyonie/Anenome/Anenome.jar"),
             hash: String::from("bar"),
+            signing_key: None,
+            attachment: Vec::new(),
         }
     }
 
@@ -443,6 +572,7 @@ hash = "foo"
                         lock: None,
                     },
                 ],
+                warnings: Vec::new(),
             },
             ResolvedDependency {
                 name: String::from("also-lockable"),
@@ -450,6 +580,7 @@ hash = "foo"
                     path: PathBuf::from(".wisteria/cache/anenome.jar"),
                     lock: Some(lockable_artifacts[1].clone()),
                 }],
+                warnings: Vec::new(),
             },
         ];
 
@@ -464,7 +595,15 @@ hash = "foo"
 
     #[test]
     fn lockfile_serialization_makes_round_trip() {
-        let lockable_artifacts = vec![gson_artifact(), anenome_artifact()];
+        let lockable_artifacts = vec![
+            gson_artifact().with_attachments(vec![LockfileAttachment::new(
+                AttachmentKind::Sources,
+                String::from("https://example/gson-sources.jar"),
+                String::from(".wisteria/cache/com.google.code.gson/gson-sources.jar"),
+                String::from("baz"),
+            )]),
+            anenome_artifact().with_signing_key(Some(String::from("ABCD1234"))),
+        ];
 
         let lockfile = Lockfile {
             schema: LOCKFILE_SCHEMA_VERSION,
@@ -474,7 +613,40 @@ hash = "foo"
 
         let parsed: Lockfile = toml::from_str(&toml).unwrap();
 
-        assert_eq!(parsed.artifact, lockable_artifacts)
+        assert_eq!(parsed.artifact, lockable_artifacts);
+        assert_eq!(toml.matches("signing_key").count(), 1);
+        assert!(toml.contains("[[artifact.attachment]]\nkind = \"sources\""));
+    }
+
+    #[test]
+    fn diff_reports_added_removed_and_changed_dependencies() {
+        let updated_gson = LockfileArtifact {
+            version: Some(String::from("2.15.0")),
+            hash: String::from("baz"),
+            ..gson_artifact()
+        };
+        let guava = LockfileArtifact {
+            name: String::from("guava"),
+            ..gson_artifact()
+        };
+
+        let changes = diff_lockfile_artifacts(
+            &[gson_artifact(), anenome_artifact()],
+            &[updated_gson.clone(), guava.clone()],
+        );
+
+        assert_eq!(
+            changes,
+            vec![
+                LockfileChange::Removed(anenome_artifact()),
+                LockfileChange::Changed {
+                    old: gson_artifact(),
+                    new: updated_gson,
+                },
+                LockfileChange::Added(guava),
+            ]
+        );
+        assert!(diff_lockfile_artifacts(&[gson_artifact()], &[gson_artifact()]).is_empty());
     }
 
     #[test]
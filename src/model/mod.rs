@@ -3,8 +3,9 @@ pub mod lockfile;
 pub mod metadata;
 pub mod migration;
 pub mod project;
+pub mod project_edit;
 
-pub use configuration::Configuration;
-pub use lockfile::{Lockfile, LockfileArtifact};
+pub use configuration::{Configuration, DistributionFormat};
+pub use lockfile::{Lockfile, LockfileArtifact, LockfileAttachment};
 pub use metadata::Metadata;
 pub use project::{Project, ProjectInfo};
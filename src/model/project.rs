@@ -1,14 +1,17 @@
-use std::{collections::HashMap, fs::read_to_string};
+use std::{collections::HashMap, fs::read_to_string, time::Duration};
 
 use toml::Table;
 
 use crate::{
     cli::args::StartupFlags,
     config::toml_utils,
-    dependency::{Dependency, load_dependency_map, migrate_legacy_dependency_table},
+    dependency::{
+        Dependency, MavenRepository, assign_repositories, bom, license::LicensePolicy,
+        load_dependency_map, load_repositories, migrate_legacy_dependency_table,
+    },
     model::Configuration,
     util::consts,
-    workspace::nature::Nature,
+    workspace::{download::DownloadSettings, global_cache::CacheMode, nature::Nature},
 };
 
 /// Collection of identifying information for a project.
@@ -22,6 +25,11 @@ pub struct ProjectInfo {
     homepage: Option<String>,
     sourcepage: Option<String>,
     natures: Vec<Nature>,
+    concurrency: Option<usize>,
+    connect_timeout: Option<u8>,
+    read_timeout: Option<u8>,
+    retries: Option<u8>,
+    cache: CacheMode,
     configurations: HashMap<String, Configuration>,
 }
 
@@ -29,6 +37,10 @@ pub struct ProjectInfo {
 pub struct Project {
     info: ProjectInfo,
     dependencies: HashMap<String, Dependency>,
+    repositories: Vec<MavenRepository>,
+    license_policy: LicensePolicy,
+    /// The BOM which supplied the version of each aligned Maven dependency.
+    aligned_by: HashMap<String, String>,
 }
 
 impl Project {
@@ -71,6 +83,11 @@ impl Project {
             homepage: read_project_optional_string("homepage", toml)?,
             sourcepage: read_project_optional_string("sourcepage", toml)?,
             natures: read_project_natures(toml)?,
+            concurrency: flags.jobs.or(read_project_concurrency(toml)?),
+            connect_timeout: read_project_optional_integer("connect_timeout", toml)?,
+            read_timeout: read_project_optional_integer("read_timeout", toml)?,
+            retries: read_project_optional_integer("retries", toml)?,
+            cache: read_project_cache_mode(toml)?,
             configurations: match configuration_map {
                 Some(v) if v.is_table() => {
                     let v = v.as_table().unwrap();
@@ -141,10 +158,27 @@ impl Project {
             },
         };
 
-        let dependencies: HashMap<String, Dependency> = load_dependency_map(dependencies_map)?;
+        let mut dependencies: HashMap<String, Dependency> = load_dependency_map(dependencies_map)?;
+        let repositories = load_repositories(project_toml.get("repositories"))?;
+        assign_repositories(&mut dependencies, &repositories)?;
         validate_configuration_dependency_references(&info.configurations, &dependencies)?;
+        let license_policy = LicensePolicy::load(project_toml.get("licenses"))?;
+        if let Some(name) = license_policy
+            .declared_names()
+            .find(|name| !dependencies.contains_key(*name))
+        {
+            return Err(format!(
+                "[licenses.declared] lists \"{name}\", which is not a declared dependency.\nFix: remove it, or use the name the dependency is declared under in [dependencies]."
+            ));
+        }
 
-        Ok(Project { info, dependencies })
+        Ok(Project {
+            info,
+            dependencies,
+            repositories,
+            license_policy,
+            aligned_by: HashMap::new(),
+        })
     }
 
     pub fn info(&self) -> &ProjectInfo {
@@ -155,6 +189,27 @@ impl Project {
         &self.dependencies
     }
 
+    pub fn repositories(&self) -> &[MavenRepository] {
+        &self.repositories
+    }
+
+    pub fn license_policy(&self) -> &LicensePolicy {
+        &self.license_policy
+    }
+
+    /// Gives unversioned Maven dependencies the versions their BOMs manage. With `cached_only`, BOM
+    /// POMs are never downloaded, and dependencies of a BOM which is not cached keep resolving from
+    /// the lockfile.
+    pub fn align_versions(&mut self, cached_only: bool) -> Result<(), String> {
+        self.aligned_by = bom::align_versions(&mut self.dependencies, cached_only)?;
+        Ok(())
+    }
+
+    /// The BOM whose managed version a dependency took, if it declares no `version` itself.
+    pub fn aligned_by(&self, name: &str) -> Option<&str> {
+        self.aligned_by.get(name).map(String::as_str)
+    }
+
     pub fn print_info(&self) {
         println!(
             "╒══[ Information for project \"{}\" ]═════════════",
@@ -188,13 +243,30 @@ impl Project {
             println!("│\tSource           {s}")
         }
 
+        if let Some(concurrency) = self.info.concurrency {
+            println!("│\tConcurrency      {concurrency}")
+        }
+
         println!("│\tConfigurations   {}", self.info.configurations.len());
         println!("│\tDependencies     {}", self.dependencies.len());
 
         if !self.dependencies.is_empty() {
             println!("╞ Dependencies:");
             for (name, dependency) in &self.dependencies {
-                println!("│\t{:<16} ({})", name, dependency.type_str())
+                let version = match dependency {
+                    Dependency::FetchFromMaven { version, bom, .. } => {
+                        let mut version = format!(" {}", version.as_deref().unwrap_or("latest"));
+                        if *bom {
+                            version.push_str(", BOM");
+                        }
+                        if let Some(bom) = self.aligned_by(name) {
+                            version.push_str(&format!(", aligned by {bom}"));
+                        }
+                        version
+                    }
+                    _ => String::new(),
+                };
+                println!("│\t{:<16} ({}){version}", name, dependency.type_str())
             }
         }
         println!("│");
@@ -230,6 +302,11 @@ fn read_project_optional_string(key: &str, toml: &Table) -> Result<Option<String
         .map_err(|error| contextual_project_error(key, error))
 }
 
+fn read_project_optional_integer(key: &str, toml: &Table) -> Result<Option<u8>, String> {
+    toml_utils::read_optional_integer(key, toml)
+        .map_err(|error| contextual_project_error(key, error))
+}
+
 fn read_project_string_array(key: &str, toml: &Table) -> Result<Option<Vec<String>>, String> {
     toml_utils::read_optional_string_array(key, toml)
         .map_err(|error| contextual_project_error(key, error))
@@ -256,6 +333,29 @@ fn read_project_natures(toml: &Table) -> Result<Vec<Nature>, String> {
     Ok(parsed)
 }
 
+fn read_project_concurrency(toml: &Table) -> Result<Option<usize>, String> {
+    match toml_utils::read_optional_integer("concurrency", toml)
+        .map_err(|error| contextual_project_error("concurrency", error))?
+    {
+        Some(0) => Err(String::from(
+            "Invalid [project].concurrency: expected at least 1.\nFix: set `concurrency` to the number of dependencies to resolve at once, or remove it to use the default.",
+        )),
+        concurrency => Ok(concurrency.map(usize::from)),
+    }
+}
+
+fn read_project_cache_mode(toml: &Table) -> Result<CacheMode, String> {
+    match read_project_optional_string("cache", toml)? {
+        Some(value) => CacheMode::load(&value).map_err(|error| {
+            contextual_project_error(
+                "cache",
+                format!("{error}\nFix: use `cache = \"project\"` to keep artifacts only in this project, or remove the key to share them."),
+            )
+        }),
+        None => Ok(CacheMode::default()),
+    }
+}
+
 fn contextual_project_error(key: &str, error: String) -> String {
     format!("Invalid [project].{key}: {error}")
 }
@@ -291,8 +391,15 @@ fn validate_configuration_dependency_references(
         };
 
         for (index, reference) in references.iter().enumerate() {
-            if dependencies.contains_key(reference.name()) {
-                continue;
+            match dependencies.get(reference.name()) {
+                Some(dependency) if dependency.is_bom() => {
+                    return Err(format!(
+                        "Invalid [configuration.{configuration_name}].dependencies[{index}]: `{}` is a BOM, which only supplies versions and has no jar.\nFix: remove this reference; the BOM aligns Maven dependencies without a `version` in every configuration.",
+                        reference.name()
+                    ));
+                }
+                Some(_) => continue,
+                None => {}
             }
 
             return Err(format!(
@@ -340,6 +447,32 @@ impl ProjectInfo {
         self.natures.as_ref()
     }
 
+    /// How many dependencies may be resolved and downloaded at once.
+    pub fn concurrency(&self) -> usize {
+        self.concurrency.unwrap_or(consts::DEFAULT_CONCURRENCY)
+    }
+
+    pub fn cache_mode(&self) -> CacheMode {
+        self.cache
+    }
+
+    /// Timeouts are in seconds; unset keys keep the download defaults.
+    pub fn download_settings(&self) -> DownloadSettings {
+        let defaults = DownloadSettings::default();
+        DownloadSettings {
+            connect_timeout: self
+                .connect_timeout
+                .map(|seconds| Duration::from_secs(seconds.into()))
+                .unwrap_or(defaults.connect_timeout),
+            read_timeout: self
+                .read_timeout
+                .map(|seconds| Duration::from_secs(seconds.into()))
+                .unwrap_or(defaults.read_timeout),
+            retries: self.retries.map(u32::from).unwrap_or(defaults.retries),
+            offline: defaults.offline,
+        }
+    }
+
     pub fn configurations(&self) -> &HashMap<String, Configuration> {
         &self.configurations
     }
@@ -681,4 +814,98 @@ mod tests {
         assert!(error.contains("Invalid [project].natures entry"));
         assert!(error.contains("supported natures"));
     }
+
+    #[test]
+    fn jobs_flag_overrides_project_concurrency() {
+        let temp = TempDir::new("project-concurrency");
+        let project_file = write_project(
+            &temp,
+            r#"
+            [project]
+            name = "Demo"
+            version = "1.0.0"
+            description = "Demo project"
+            concurrency = 2
+            "#,
+        );
+
+        let project = Project::from(Some(project_file.clone())).unwrap();
+        assert_eq!(project.info().concurrency(), 2);
+
+        let flags = StartupFlags {
+            jobs: Some(6),
+            ..StartupFlags::default()
+        };
+        let project = Project::from_with_flags(Some(project_file), flags).unwrap();
+        assert_eq!(project.info().concurrency(), 6);
+    }
+
+    #[test]
+    fn download_settings_fall_back_to_defaults() {
+        let temp = TempDir::new("project-download-settings");
+        let project_file = write_project(
+            &temp,
+            r#"
+            [project]
+            name = "Demo"
+            version = "1.0.0"
+            description = "Demo project"
+            read_timeout = 90
+            retries = 0
+            "#,
+        );
+
+        let settings = Project::from(Some(project_file))
+            .unwrap()
+            .info()
+            .download_settings();
+
+        assert_eq!(
+            settings.connect_timeout,
+            DownloadSettings::default().connect_timeout
+        );
+        assert_eq!(settings.read_timeout, Duration::from_secs(90));
+        assert_eq!(settings.retries, 0);
+    }
+
+    #[test]
+    fn reads_project_cache_mode() {
+        let temp = TempDir::new("project-cache-mode");
+        let project_file = write_project(
+            &temp,
+            r#"
+            [project]
+            name = "Demo"
+            version = "1.0.0"
+            description = "Demo project"
+            cache = "project"
+            "#,
+        );
+
+        let project = Project::from(Some(project_file)).unwrap();
+
+        assert_eq!(project.info().cache_mode(), CacheMode::Project);
+    }
+
+    #[test]
+    fn rejects_zero_project_concurrency() {
+        let temp = TempDir::new("project-zero-concurrency");
+        let project_file = write_project(
+            &temp,
+            r#"
+            [project]
+            name = "Demo"
+            version = "1.0.0"
+            description = "Demo project"
+            concurrency = 0
+            "#,
+        );
+
+        let error = match Project::from(Some(project_file)) {
+            Ok(_) => panic!("expected zero concurrency to fail"),
+            Err(error) => error,
+        };
+
+        assert!(error.contains("Invalid [project].concurrency"));
+    }
 }
@@ -0,0 +1,398 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use toml_edit::{Array, DocumentMut, InlineTable, Item, Table, Value};
+
+use crate::dependency::parse::duplicate_dependency_error;
+use crate::dependency::{DependencyReference, DependencyScope};
+use crate::model::Project;
+use crate::model::configuration::validate_unique_dependencies;
+
+/// `group:artifact[:version]` as given on the command line.
+#[derive(Debug, PartialEq, Eq)]
+pub struct MavenCoordinates {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: Option<String>,
+}
+
+impl MavenCoordinates {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let parts: Vec<&str> = value.split(':').collect();
+        if !(2..=3).contains(&parts.len()) || parts.iter().any(|part| part.is_empty()) {
+            return Err(format!(
+                "Invalid Maven coordinates \"{value}\".\nFix: use `group:artifact` or `group:artifact:version`, for example `com.google.code.gson:gson:2.11.0`."
+            ));
+        }
+
+        Ok(Self {
+            group_id: parts[0].to_string(),
+            artifact_id: parts[1].to_string(),
+            version: parts.get(2).map(|version| version.to_string()),
+        })
+    }
+}
+
+/// Edits project.toml in place, keeping its comments and formatting.
+pub struct ProjectEditor {
+    path: PathBuf,
+    original: String,
+    document: DocumentMut,
+}
+
+impl ProjectEditor {
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let original = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read project file {}: {e}", path.display()))?;
+        let document = original.parse::<DocumentMut>().map_err(|e| {
+            format!(
+                "Could not parse {} as TOML: {e}\nFix: correct the TOML syntax before editing dependencies from the command line.",
+                path.display()
+            )
+        })?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            original,
+            document,
+        })
+    }
+
+    /// Adds `name` under `[dependencies.maven]`.
+    pub fn add_maven_dependency(
+        &mut self,
+        name: &str,
+        coordinates: &MavenCoordinates,
+    ) -> Result<(), String> {
+        if self
+            .dependency_names()
+            .iter()
+            .any(|existing| existing == name)
+        {
+            return Err(duplicate_dependency_error(name));
+        }
+
+        let mut dependency = InlineTable::new();
+        dependency.insert("group_id", Value::from(coordinates.group_id.as_str()));
+        dependency.insert("artifact_id", Value::from(coordinates.artifact_id.as_str()));
+        if let Some(version) = &coordinates.version {
+            dependency.insert("version", Value::from(version.as_str()));
+        }
+
+        let dependencies = self
+            .document
+            .as_table_mut()
+            .entry("dependencies")
+            .or_insert_with(|| {
+                let mut table = Table::new();
+                table.set_implicit(true);
+                Item::Table(table)
+            });
+        let Some(dependencies) = dependencies.as_table_like_mut() else {
+            return Err(String::from(
+                "project.toml has a `dependencies` key which is not a table.\nFix: declare dependencies in tables such as `[dependencies.maven]`.",
+            ));
+        };
+
+        if !dependencies.contains_key("maven") {
+            dependencies.insert("maven", Item::Table(Table::new()));
+        }
+        let Some(group) = dependencies
+            .get_mut("maven")
+            .and_then(Item::as_table_like_mut)
+        else {
+            return Err(String::from(
+                "`dependencies.maven` in project.toml is not a table.\nFix: make `[dependencies.maven]` a table of dependencies.",
+            ));
+        };
+
+        group.insert(name, Item::Value(Value::InlineTable(dependency)));
+        Ok(())
+    }
+
+    /// References `name` from `[configuration.<configuration>]`, written as a plain name for
+    /// compile scope and as `{ name, scope }` otherwise.
+    pub fn add_reference(
+        &mut self,
+        configuration: &str,
+        name: &str,
+        scope: DependencyScope,
+    ) -> Result<(), String> {
+        let known_configurations = self.configuration_names().join(", ");
+        let Some(table) = self
+            .document
+            .get_mut("configuration")
+            .and_then(|configurations| configurations.get_mut(configuration))
+            .and_then(Item::as_table_like_mut)
+        else {
+            return Err(format!(
+                "No configuration named \"{configuration}\" has been defined in project.toml. Valid configurations: [{known_configurations}].\nFix: pass an existing configuration with `--to <configuration>`."
+            ));
+        };
+
+        if !table.contains_key("dependencies") {
+            table.insert("dependencies", Item::Value(Value::Array(Array::new())));
+        }
+        let Some(references) = table.get_mut("dependencies").and_then(Item::as_array_mut) else {
+            return Err(format!(
+                "Invalid [configuration.{configuration}].dependencies: expected an array.\nFix: use `dependencies = [ \"dependency-name\" ]`."
+            ));
+        };
+
+        let mut existing: Vec<DependencyReference> = references
+            .iter()
+            .filter_map(reference_name)
+            .map(|existing| {
+                DependencyReference::new(existing.to_string(), DependencyScope::Compile, None)
+            })
+            .collect();
+        existing.push(DependencyReference::new(name.to_string(), scope, None));
+        validate_unique_dependencies(configuration, &existing)?;
+
+        let reference = match scope {
+            DependencyScope::Compile => Value::from(name),
+            _ => {
+                let mut reference = InlineTable::new();
+                reference.insert("name", Value::from(name));
+                reference.insert("scope", Value::from(scope.to_string()));
+                Value::InlineTable(reference)
+            }
+        };
+        push_formatted(references, reference);
+        Ok(())
+    }
+
+    /// Removes the dependency and every configuration's reference to it. Returns the names of the
+    /// configurations which referenced it.
+    pub fn remove_dependency(&mut self, name: &str) -> Result<Vec<String>, String> {
+        let mut removed = false;
+        if let Some(dependencies) = self
+            .document
+            .get_mut("dependencies")
+            .and_then(Item::as_table_like_mut)
+        {
+            let legacy = dependencies
+                .get(name)
+                .and_then(Item::as_table_like)
+                .is_some_and(|dependency| dependency.contains_key("type"));
+            if legacy {
+                removed = dependencies.remove(name).is_some();
+            }
+            for (_, group) in dependencies.iter_mut() {
+                if let Some(group) = group.as_table_like_mut()
+                    && !group.contains_key("type")
+                {
+                    removed |= group.remove(name).is_some();
+                }
+            }
+        }
+
+        if !removed {
+            return Err(format!(
+                "No dependency named \"{name}\" is declared in project.toml.\nFix: pass one of the declared dependency names: [{}].",
+                self.dependency_names().join(", ")
+            ));
+        }
+
+        let mut configurations = Vec::new();
+        if let Some(table) = self
+            .document
+            .get_mut("configuration")
+            .and_then(Item::as_table_like_mut)
+        {
+            for (configuration, item) in table.iter_mut() {
+                let Some(references) = item.get_mut("dependencies").and_then(Item::as_array_mut)
+                else {
+                    continue;
+                };
+
+                let before = references.len();
+                references.retain(|reference| reference_name(reference) != Some(name));
+                if references.len() != before {
+                    configurations.push(configuration.get().to_string());
+                }
+            }
+        }
+
+        Ok(configurations)
+    }
+
+    /// Writes the edited file, and puts the original back if the result is not a valid project.
+    pub fn save(self) -> Result<(), String> {
+        fs::write(&self.path, self.document.to_string())
+            .map_err(|e| format!("Could not write {}: {e}", self.path.display()))?;
+
+        if let Err(error) = Project::from(Some(self.path.to_string_lossy().to_string())) {
+            let _ = fs::write(&self.path, &self.original);
+            return Err(format!(
+                "The edited project.toml would not load, so it was left unchanged.\n\n{error}"
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Names declared in any dependency group, or as legacy `[dependencies]` entries with a `type`.
+    fn dependency_names(&self) -> Vec<String> {
+        let Some(dependencies) = self
+            .document
+            .get("dependencies")
+            .and_then(Item::as_table_like)
+        else {
+            return Vec::new();
+        };
+
+        let mut names = Vec::new();
+        for (key, item) in dependencies.iter() {
+            match item.as_table_like() {
+                Some(group) if !group.contains_key("type") => {
+                    names.extend(group.iter().map(|(name, _)| name.to_string()));
+                }
+                _ => names.push(key.to_string()),
+            }
+        }
+        names
+    }
+
+    fn configuration_names(&self) -> Vec<String> {
+        self.document
+            .get("configuration")
+            .and_then(Item::as_table_like)
+            .map(|table| table.iter().map(|(name, _)| name.to_string()).collect())
+            .unwrap_or_default()
+    }
+}
+
+fn reference_name(reference: &Value) -> Option<&str> {
+    match reference {
+        Value::String(name) => Some(name.value()),
+        Value::InlineTable(table) => table.get("name").and_then(Value::as_str),
+        _ => None,
+    }
+}
+
+/// Pushes onto an array, placing the value on its own line when the array already spans lines.
+fn push_formatted(array: &mut Array, value: Value) {
+    let prefix = array
+        .iter()
+        .last()
+        .and_then(|last| last.decor().prefix())
+        .and_then(|prefix| prefix.as_str())
+        .filter(|prefix| prefix.contains('\n'))
+        .map(String::from);
+
+    array.push(value);
+    if let Some(prefix) = prefix
+        && let Some(last) = array.iter_mut().last()
+    {
+        last.decor_mut().set_prefix(prefix);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    const PROJECT: &str = r#"# Demo project
+[project]
+name = "Demo"
+version = "1.0.0"
+description = "Demo project"
+
+[dependencies.maven]
+# Used for JSON
+gson = { group_id = "com.google.code.gson", artifact_id = "gson", version = "2.11.0" }
+
+[configuration.main]
+sources = [ "src" ]
+dependencies = [
+    "gson",
+]
+"#;
+
+    fn editor(temp: &TempDir) -> ProjectEditor {
+        let path = temp.path().join("project.toml");
+        fs::write(&path, PROJECT).unwrap();
+        ProjectEditor::open(&path).unwrap()
+    }
+
+    #[test]
+    fn adds_maven_dependency_and_reference_keeping_comments() {
+        let temp = TempDir::new("project-edit-add");
+        let mut editor = editor(&temp);
+        let coordinates = MavenCoordinates::parse("com.google.guava:guava:33.0.0-jre").unwrap();
+
+        editor.add_maven_dependency("guava", &coordinates).unwrap();
+        editor
+            .add_reference("main", "guava", DependencyScope::Runtime)
+            .unwrap();
+        editor.save().unwrap();
+
+        let edited = fs::read_to_string(temp.path().join("project.toml")).unwrap();
+        assert!(edited.starts_with("# Demo project\n"));
+        assert!(edited.contains("# Used for JSON\n"));
+        assert!(edited.contains(
+            r#"guava = { group_id = "com.google.guava", artifact_id = "guava", version = "33.0.0-jre" }"#
+        ));
+        assert!(
+            edited.contains("    \"gson\",\n    { name = \"guava\", scope = \"runtime\" },\n]")
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_names_and_references() {
+        let temp = TempDir::new("project-edit-duplicate");
+        let mut editor = editor(&temp);
+        let coordinates = MavenCoordinates::parse("com.google.code.gson:gson").unwrap();
+
+        assert!(
+            editor
+                .add_maven_dependency("gson", &coordinates)
+                .unwrap_err()
+                .contains("Duplicate dependency name")
+        );
+        assert!(
+            editor
+                .add_reference("main", "gson", DependencyScope::Compile)
+                .unwrap_err()
+                .contains("already referenced")
+        );
+        assert!(
+            editor
+                .add_reference("test", "gson", DependencyScope::Compile)
+                .unwrap_err()
+                .contains("Valid configurations: [main]")
+        );
+    }
+
+    #[test]
+    fn removes_dependency_and_its_references() {
+        let temp = TempDir::new("project-edit-remove");
+        let mut editor = editor(&temp);
+
+        assert_eq!(editor.remove_dependency("gson").unwrap(), vec!["main"]);
+        assert!(editor.remove_dependency("gson").is_err());
+        editor.save().unwrap();
+
+        let edited = fs::read_to_string(temp.path().join("project.toml")).unwrap();
+        assert!(!edited.contains("gson"));
+        assert!(edited.contains("sources = [ \"src\" ]"));
+    }
+
+    #[test]
+    fn parses_maven_coordinates() {
+        assert_eq!(
+            MavenCoordinates::parse("org.example:library").unwrap(),
+            MavenCoordinates {
+                group_id: String::from("org.example"),
+                artifact_id: String::from("library"),
+                version: None,
+            }
+        );
+        assert!(MavenCoordinates::parse("org.example").is_err());
+        assert!(MavenCoordinates::parse("org.example::1.0").is_err());
+    }
+}
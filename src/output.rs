@@ -5,6 +5,8 @@ use std::{
 
 use serde_json::json;
 
+use crate::workspace::credentials;
+
 const STATUS_STYLE: &str = "\x1b[1;7m";
 const RESET_STYLE: &str = "\x1b[0m";
 const DEFAULT_TERMINAL_WIDTH: usize = 80;
@@ -33,12 +35,13 @@ impl OutputMode {
 }
 
 pub fn renderer(mode: OutputMode) -> Box<dyn OutputRenderer> {
-    match mode {
+    let inner: Box<dyn OutputRenderer> = match mode {
         OutputMode::Auto if io::stdout().is_terminal() => Box::new(TerminalOutput::new()),
         OutputMode::Auto | OutputMode::Plain => Box::new(PlainOutput),
         OutputMode::Terminal => Box::new(TerminalOutput::new()),
         OutputMode::Json => Box::new(JsonOutput),
-    }
+    };
+    Box::new(RedactedOutput { inner })
 }
 
 pub trait OutputRenderer {
@@ -72,9 +75,91 @@ pub trait OutputRenderer {
     );
     fn log(&mut self, message: &str);
 
+    /// Reports bytes downloaded for a running step, and the expected total when known.
+    fn step_progress(&mut self, _operation: &str, _item: &str, _bytes: u64, _total: Option<u64>) {}
+
     fn suspend(&mut self) {}
 }
 
+/// Removes repository credentials from everything passed on to the wrapped renderer.
+struct RedactedOutput {
+    inner: Box<dyn OutputRenderer>,
+}
+
+impl OutputRenderer for RedactedOutput {
+    fn operation_started(&mut self, operation: &str, total_steps: usize) {
+        self.inner.operation_started(operation, total_steps);
+    }
+
+    fn operation_completed(&mut self, operation: &str, message: &str) {
+        self.inner
+            .operation_completed(operation, &credentials::redact(message));
+    }
+
+    fn step_started(
+        &mut self,
+        operation: &str,
+        action: &str,
+        item: &str,
+        index: usize,
+        total: usize,
+    ) {
+        self.inner
+            .step_started(operation, action, &credentials::redact(item), index, total);
+    }
+
+    fn step_completed(
+        &mut self,
+        operation: &str,
+        action: &str,
+        item: &str,
+        index: usize,
+        total: usize,
+        message: &str,
+    ) {
+        self.inner.step_completed(
+            operation,
+            action,
+            &credentials::redact(item),
+            index,
+            total,
+            &credentials::redact(message),
+        );
+    }
+
+    fn step_failed(
+        &mut self,
+        operation: &str,
+        action: &str,
+        item: &str,
+        index: usize,
+        total: usize,
+        message: &str,
+    ) {
+        self.inner.step_failed(
+            operation,
+            action,
+            &credentials::redact(item),
+            index,
+            total,
+            &credentials::redact(message),
+        );
+    }
+
+    fn log(&mut self, message: &str) {
+        self.inner.log(&credentials::redact(message));
+    }
+
+    fn step_progress(&mut self, operation: &str, item: &str, bytes: u64, total: Option<u64>) {
+        self.inner
+            .step_progress(operation, &credentials::redact(item), bytes, total);
+    }
+
+    fn suspend(&mut self) {
+        self.inner.suspend();
+    }
+}
+
 pub fn log_process_output(renderer: &mut dyn OutputRenderer, stdout: &[u8], stderr: &[u8]) {
     log_process_stream(renderer, stdout);
     log_process_stream(renderer, stderr);
@@ -253,6 +338,7 @@ struct TerminalOutput {
     live_lines: usize,
     operation: Option<String>,
     total_steps: usize,
+    completed_steps: usize,
     active_line: Option<String>,
     status_line: Option<String>,
     spinner_index: usize,
@@ -264,6 +350,7 @@ impl TerminalOutput {
             live_lines: 0,
             operation: None,
             total_steps: 0,
+            completed_steps: 0,
             active_line: None,
             status_line: None,
             spinner_index: 0,
@@ -313,6 +400,7 @@ impl TerminalOutput {
         }
     }
 
+    /// Steps may finish out of order when they run concurrently, so the status counts completions.
     fn update_status(&mut self, operation: &str, index: usize, total: usize) {
         let spinner = self.next_spinner();
         self.status_line = Some(format_status_content(spinner, operation, index, total));
@@ -364,6 +452,19 @@ fn truncate_visible_line(line: &str, width: usize) -> String {
     truncated
 }
 
+fn format_download_progress(bytes: u64, total: Option<u64>) -> String {
+    let megabytes = |bytes: u64| bytes as f64 / 1_000_000.0;
+    match total {
+        Some(total) if total > 0 => format!(
+            "{:.1}/{:.1} MB ({}%)",
+            megabytes(bytes),
+            megabytes(total),
+            bytes.min(total) * 100 / total
+        ),
+        _ => format!("{:.1} MB", megabytes(bytes)),
+    }
+}
+
 fn is_meaningful_step_message(message: &str) -> bool {
     !matches!(message, "" | "Done")
 }
@@ -382,6 +483,7 @@ impl OutputRenderer for TerminalOutput {
     fn operation_started(&mut self, operation: &str, total_steps: usize) {
         self.operation = Some(operation.to_string());
         self.total_steps = total_steps;
+        self.completed_steps = 0;
         self.active_line = None;
         let spinner = self.next_spinner();
         self.status_line = Some(format_status_content(spinner, operation, 0, total_steps));
@@ -403,11 +505,11 @@ impl OutputRenderer for TerminalOutput {
         operation: &str,
         action: &str,
         item: &str,
-        index: usize,
+        _index: usize,
         total: usize,
     ) {
         self.active_line = Some(format!("{action} {item}"));
-        self.update_status(operation, index, total);
+        self.update_status(operation, (self.completed_steps + 1).min(total), total);
         self.redraw();
     }
 
@@ -416,11 +518,12 @@ impl OutputRenderer for TerminalOutput {
         operation: &str,
         action: &str,
         item: &str,
-        index: usize,
+        _index: usize,
         total: usize,
         message: &str,
     ) {
-        self.update_status(operation, index, total);
+        self.completed_steps += 1;
+        self.update_status(operation, self.completed_steps.min(total), total);
         if is_meaningful_step_message(message) {
             self.active_line = Some(format_step_completion(action, item, message));
         }
@@ -432,11 +535,12 @@ impl OutputRenderer for TerminalOutput {
         operation: &str,
         action: &str,
         item: &str,
-        index: usize,
+        _index: usize,
         total: usize,
         message: &str,
     ) {
-        self.update_status(operation, index, total);
+        self.completed_steps += 1;
+        self.update_status(operation, self.completed_steps.min(total), total);
         self.log_above_status(&format!("{action} {item}: Failed"));
         self.log_above_status(message);
     }
@@ -445,6 +549,14 @@ impl OutputRenderer for TerminalOutput {
         self.log_above_status(message);
     }
 
+    fn step_progress(&mut self, _operation: &str, item: &str, bytes: u64, total: Option<u64>) {
+        self.active_line = Some(format!(
+            "Downloading {item} {}",
+            format_download_progress(bytes, total)
+        ));
+        self.redraw();
+    }
+
     fn suspend(&mut self) {
         self.clear_live_region();
         let _ = io::stdout().flush();
@@ -492,6 +604,15 @@ mod tests {
         assert_eq!(truncate_visible_line("abc", 2), "ab");
     }
 
+    #[test]
+    fn terminal_download_progress_shows_percentage_when_total_is_known() {
+        assert_eq!(
+            format_download_progress(1_500_000, Some(3_000_000)),
+            "1.5/3.0 MB (50%)"
+        );
+        assert_eq!(format_download_progress(250_000, None), "0.2 MB");
+    }
+
     #[test]
     fn terminal_step_completion_summarizes_common_build_steps() {
         assert_eq!(
@@ -1,6 +1,6 @@
 use std::env::consts;
 
-pub const USAGE_TEXT: &str = r#"Usage: wisteria <(tasks...) | refresh | new | sync | fetch | verify | update | info | switch | migrate > 
+pub const USAGE_TEXT: &str = r#"Usage: wisteria <(tasks...) | refresh | new | sync | fetch | verify | outdated | tree | licenses | sbom | audit | add | remove | update | info | switch | migrate > 
     --output <auto | plain | terminal | json>
         Controls command output. JSON output is emitted as newline-delimited JSON events.
     (tasks...)
@@ -18,6 +18,20 @@ pub const USAGE_TEXT: &str = r#"Usage: wisteria <(tasks...) | refresh | new | sy
         Downloads cached artifacts to match wisteria.lock without changing the lockfile
     verify
         Checks that project.toml, wisteria.lock, and cached dependencies agree
+    outdated [(dependencies...) | all]
+        Lists newer versions of locked Maven and GitHub dependencies without changing anything
+    tree [--why <dependency>]
+        Shows the current configuration's dependencies with their scope, packaging, version and jars
+    licenses
+        Reports each configuration's dependency licenses and checks shipped ones against [licenses]
+    sbom [cyclonedx | spdx]
+        Prints a software bill of materials for the current configuration from wisteria.lock
+    audit --database <path> [--fail-on <low | medium | high | critical>]
+        Checks locked Maven dependencies against a local OSV database without network access
+    add maven <group>:<artifact>[:<version>]
+        Declares a Maven dependency and references it from a configuration, keeping project.toml formatting
+    remove <(dependencies...)>
+        Removes dependencies and every configuration's references to them
     update <(dependencies...) | all>
         Re-fetches the given dependencies, or all dependencies in a project file
     clean <classes | dependencies | targets | javadocs | metadata | natures | all>
@@ -37,9 +51,43 @@ Flags:
         Uses a minimal project.toml template
         (new)
     --project <project file>
-        Uses a specific project file"#;
+        Uses a specific project file
+    --jobs <count>
+        Resolves and downloads up to <count> dependencies at once
+    --offline
+        Forbids network access; only wisteria.lock and the cache are used
+    --locked
+        Uses only locked artifacts and fails if wisteria.lock is missing or would change
+        (tasks, switch, refresh, update)
+    --frozen
+        Same as --locked and --offline together
+    --dry-run
+        Shows how locked versions would change without downloading, locking or refreshing
+        (update)
+    --scope <compile | runtime | provided | test>
+        Scope of the added dependency reference, compile by default
+        (add)
+    --to <configuration>
+        Configuration to reference the added dependency from, the current one by default
+        (add)
+    --name <name>
+        Name to declare the added dependency under, the artifact ID by default
+        (add)
+    --sync
+        Locks the added dependency with `update`, or drops removed ones with `sync`
+        (add, remove)
+    --why <dependency>
+        Lists the references, in every configuration, which pull in a dependency or jar
+        (tree)
+    --database <path>
+        OSV database to check against: a directory of JSON advisories, a JSON file, or the Maven all.zip export
+        (audit)
+    --fail-on <low | medium | high | critical>
+        Lowest advisory severity that makes the audit fail, low by default
+        (audit)"#;
 
 pub const PROJECT_FILE: &str = "project.toml";
+pub const DEFAULT_CONCURRENCY: usize = 8;
 pub const WISTERIA2_BACKUP_EXTENSION: &str = "wisteria2.bak";
 
 pub const WISTERIA_DIR: &str = ".wisteria";
@@ -48,18 +96,30 @@ pub const LOCKFILE_TEMP: &str = "wisteria.lock.tmp";
 pub const TEMP_FILE_EXTENSION: &str = "tmp";
 pub const METADATA_FILE: &str = ".wisteria/metadata.toml";
 pub const CACHE_PATH: &str = ".wisteria/cache";
+pub const TRUSTED_KEYS_PATH: &str = ".wisteria/trusted-keys";
+pub const USER_CONFIG_DIR: &str = "wisteria";
+pub const CREDENTIALS_FILE: &str = "credentials.toml";
+pub const SETTINGS_FILE: &str = "settings.toml";
+pub const GLOBAL_CACHE_DIR: &str = "wisteria";
+pub const SIGNATURE_EXTENSION: &str = "asc";
 pub const WORK_DIR: &str = ".wisteria/work";
 pub const SOURCE_OUT_PATH: &str = ".wisteria/work/src";
 pub const BINARY_OUT_PATH: &str = ".wisteria/work/bin";
 pub const SHADED_OUT_PATH: &str = ".wisteria/work/shaded";
+pub const NESTED_OUT_PATH: &str = ".wisteria/work/nested";
+pub const LAUNCHER_SOURCE_PATH: &str = ".wisteria/work/launcher";
 pub const TARGET_JAR_PATH: &str = ".wisteria/work/target.jar";
 pub const MANIFEST_DIR: &str = ".wisteria/work/bin/META-INF";
 pub const MANIFEST_FILE: &str = ".wisteria/work/bin/META-INF/MANIFEST.MF";
+pub const SHELL_STARTUP_FILE: &str = ".wisteria/work/startup.jsh";
+pub const DISTRIBUTION_WORK_PATH: &str = ".wisteria/work/distribution";
 
 pub const PROJECT_SOURCE_DIR: &str = "src";
 pub const LEGACY_PROJECT_LIBRARY_DIR: &str = "lib";
 
 pub const DEFAULT_JAVADOC_DIR: &str = "target/javadoc/{configuration}/";
+pub const DEFAULT_IMAGE_DIR: &str = "target/image/{configuration}/";
+pub const DEFAULT_DISTRIBUTION_DIR: &str = "target/distributions/{configuration}/";
 
 pub const ECLIPSE_SETTINGS_DIR: &str = ".settings";
 pub const ECLIPSE_JDT_PREFS_FILE: &str = ".settings/org.eclipse.jdt.core.prefs";
@@ -0,0 +1,186 @@
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+};
+
+use sha1::Sha1;
+use sha2::Sha512;
+
+use crate::workspace::files;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl ChecksumAlgorithm {
+    /// Sidecar algorithms, strongest first.
+    pub const SIDECARS: [ChecksumAlgorithm; 3] = [
+        ChecksumAlgorithm::Sha512,
+        ChecksumAlgorithm::Sha256,
+        ChecksumAlgorithm::Sha1,
+    ];
+
+    pub fn load(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().replace('-', "").as_str() {
+            "sha1" => Some(ChecksumAlgorithm::Sha1),
+            "sha256" => Some(ChecksumAlgorithm::Sha256),
+            "sha512" => Some(ChecksumAlgorithm::Sha512),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Sha1 => "sha1",
+            ChecksumAlgorithm::Sha256 => "sha256",
+            ChecksumAlgorithm::Sha512 => "sha512",
+        }
+    }
+
+    fn hex_length(&self) -> usize {
+        match self {
+            ChecksumAlgorithm::Sha1 => 40,
+            ChecksumAlgorithm::Sha256 => 64,
+            ChecksumAlgorithm::Sha512 => 128,
+        }
+    }
+
+    pub fn digest_file(&self, path: &Path) -> Result<String, String> {
+        match self {
+            ChecksumAlgorithm::Sha1 => {
+                use sha1::Digest;
+                let mut hasher = Sha1::new();
+                read_file(path, |bytes| hasher.update(bytes))?;
+                Ok(hex::encode(hasher.finalize()))
+            }
+            ChecksumAlgorithm::Sha256 => files::generate_sha2_for_file(path),
+            ChecksumAlgorithm::Sha512 => {
+                use sha2::Digest;
+                let mut hasher = Sha512::new();
+                read_file(path, |bytes| hasher.update(bytes))?;
+                Ok(hex::encode(hasher.finalize()))
+            }
+        }
+    }
+}
+
+/// A digest published by the server an artifact was downloaded from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Checksum {
+    algorithm: ChecksumAlgorithm,
+    digest: String,
+}
+
+impl Checksum {
+    /// Sidecar files hold the hex digest, optionally followed by the file name.
+    pub fn from_sidecar(algorithm: ChecksumAlgorithm, contents: &str) -> Option<Self> {
+        let digest = contents.split_whitespace().next()?.to_ascii_lowercase();
+        if digest.len() != algorithm.hex_length() || !digest.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return None;
+        }
+
+        Some(Self { algorithm, digest })
+    }
+
+    /// Parses `<algorithm>:<hex>` digests, as published on GitHub release assets.
+    pub fn from_prefixed(value: &str) -> Option<Self> {
+        let (algorithm, digest) = value.split_once(':')?;
+        Self::from_sidecar(ChecksumAlgorithm::load(algorithm)?, digest)
+    }
+
+    pub fn algorithm(&self) -> ChecksumAlgorithm {
+        self.algorithm
+    }
+
+    pub fn verify(&self, path: &Path) -> Result<(), String> {
+        let actual = self.algorithm.digest_file(path)?;
+        if actual != self.digest {
+            return Err(format!(
+                "{} checksum mismatch for {}: the server published {}, but the download hashes to {actual}.\nFix: retry the download; if the mismatch persists, the server may be serving a corrupted or tampered file.",
+                self.algorithm.extension().to_ascii_uppercase(),
+                path.display(),
+                self.digest
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+const BUFFER_SIZE: usize = 0x2000;
+fn read_file(path: &Path, mut consume: impl FnMut(&[u8])) -> Result<(), String> {
+    let file = File::open(path).map_err(|e| {
+        format!(
+            "Failed to generate checksum for file {}: {e}",
+            path.display()
+        )
+    })?;
+    let mut reader = BufReader::new(file);
+    let mut buffer = [0; BUFFER_SIZE];
+
+    loop {
+        let count = reader
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read file while hashing it: {e}"))?;
+        if count == 0 {
+            return Ok(());
+        }
+        consume(&buffer[..count]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use std::fs;
+
+    #[test]
+    fn parses_sidecar_and_prefixed_digests() {
+        let sha1 = "a9993e364706816aba3e25717850c26c9cd0d89d";
+        assert_eq!(
+            Checksum::from_sidecar(ChecksumAlgorithm::Sha1, &format!("{sha1}  abc.jar\n")),
+            Some(Checksum {
+                algorithm: ChecksumAlgorithm::Sha1,
+                digest: String::from(sha1),
+            })
+        );
+        assert_eq!(
+            Checksum::from_sidecar(ChecksumAlgorithm::Sha256, sha1),
+            None
+        );
+        assert_eq!(
+            Checksum::from_prefixed(&format!("sha1:{}", sha1.to_ascii_uppercase()))
+                .map(|checksum| checksum.algorithm()),
+            Some(ChecksumAlgorithm::Sha1)
+        );
+        assert_eq!(Checksum::from_prefixed("md5:abc"), None);
+    }
+
+    #[test]
+    fn verify_compares_file_digest() {
+        let temp = TempDir::new("checksum-verify");
+        let path = temp.path().join("abc.jar");
+        fs::write(&path, "abc").unwrap();
+
+        let sha256 = Checksum::from_sidecar(
+            ChecksumAlgorithm::Sha256,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        )
+        .unwrap();
+        assert!(sha256.verify(&path).is_ok());
+
+        fs::write(&path, "abd").unwrap();
+        assert!(
+            sha256
+                .verify(&path)
+                .unwrap_err()
+                .contains("SHA256 checksum mismatch")
+        );
+    }
+}
@@ -1,34 +1,421 @@
-use std::{fs::File, io::copy};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{Read, Write},
+    path::Path,
+    sync::OnceLock,
+    thread,
+    time::{Duration, Instant},
+};
 
-use reqwest::{StatusCode, blocking::Client};
+use reqwest::{
+    StatusCode,
+    blocking::{Client, RequestBuilder, Response},
+    header::{CONTENT_LENGTH, RANGE, RETRY_AFTER},
+};
+
+use crate::util::consts;
+use crate::workspace::{checksum::Checksum, credentials, settings};
 
 pub const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/91.0.4472.124 Safari/537.36";
 
-pub fn download_silent(name: String, url: String, filepath: String) -> Result<f32, String> {
-    let client: Client = Client::new();
-    let mut response = match client.get(&url).header("User-Agent", USER_AGENT).send() {
-        Ok(r) => r,
-        Err(e) => {
-            return Err(format!(
-                "{url}, status: {}",
-                e.status()
-                    .unwrap_or(StatusCode::SERVICE_UNAVAILABLE)
-                    .as_str()
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(120);
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+static SETTINGS: OnceLock<DownloadSettings> = OnceLock::new();
+static CLIENT: OnceLock<Client> = OnceLock::new();
+
+/// Receives the bytes downloaded so far, and the expected total when the server reports one.
+pub type ProgressReporter<'a> = dyn Fn(u64, Option<u64>) + Sync + 'a;
+
+/// Network behaviour shared by every download.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DownloadSettings {
+    pub connect_timeout: Duration,
+    pub read_timeout: Duration,
+    pub retries: u32,
+    /// Forbids every network request, so only the lockfile and caches are used.
+    pub offline: bool,
+}
+
+impl Default for DownloadSettings {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(10),
+            read_timeout: Duration::from_secs(30),
+            retries: 3,
+            offline: false,
+        }
+    }
+}
+
+/// Sets the download settings for this process. Only the first call has any effect.
+pub fn configure(settings: DownloadSettings) {
+    let _ = SETTINGS.set(settings);
+}
+
+fn settings() -> DownloadSettings {
+    SETTINGS.get().copied().unwrap_or_default()
+}
+
+pub fn offline() -> bool {
+    settings().offline
+}
+
+/// Fails with a clear error instead of touching the network for `url` while offline.
+pub fn ensure_online(url: &str) -> Result<(), String> {
+    if offline() {
+        Err(format!(
+            "Cannot reach {url} because Wisteria is offline.\nFix: run `wisteria fetch` while online so every locked artifact is cached, or run without `--offline`, `--frozen` and `offline = true` in the user settings file."
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// The HTTP client shared by every resolver, so concurrent downloads reuse one connection pool.
+pub fn client() -> Result<&'static Client, String> {
+    if let Some(client) = CLIENT.get() {
+        return Ok(client);
+    }
+
+    let settings = settings();
+    let builder = Client::builder()
+        .user_agent(USER_AGENT)
+        .connect_timeout(settings.connect_timeout)
+        .timeout(settings.read_timeout);
+    let client = settings::settings()
+        .apply(builder)?
+        .build()
+        .map_err(|e| format!("Could not create HTTP client: {e}"))?;
+    Ok(CLIENT.get_or_init(|| client))
+}
+
+/// A GET request for `url` on the shared client, sent to its mirror when one is configured and
+/// carrying the credentials for the URL actually requested.
+pub fn request(url: &str) -> Result<RequestBuilder, String> {
+    ensure_online(url)?;
+    let url = settings::settings().mirror(url);
+    Ok(credentials::authorize(client()?.get(&url), &url))
+}
+
+/// Returns the path behind a `file://` URL, so local repositories are read without HTTP.
+pub fn local_file(url: &str) -> Option<&Path> {
+    url.strip_prefix("file://").map(Path::new)
+}
+
+/// Downloads `url` into a temporary file beside `filepath`, which is renamed into place once complete
+/// and, when given, matching `checksum`. A temporary file left behind by an interrupted download is
+/// resumed with an HTTP range request.
+pub fn download_silent(
+    name: String,
+    url: String,
+    filepath: String,
+    checksum: Option<&Checksum>,
+    progress: Option<&ProgressReporter<'_>>,
+) -> Result<f32, String> {
+    let temp_path = format!("{filepath}.{}", consts::TEMP_FILE_EXTENSION);
+    let retries = settings().retries;
+
+    let mut attempt: u32 = 0;
+    let size = loop {
+        match download_attempt(&name, &url, &temp_path, progress) {
+            Ok(size) => break size,
+            Err(failure) => match failure.retry_after {
+                Some(delay) if attempt < retries => {
+                    thread::sleep(delay.unwrap_or_else(|| backoff(attempt)));
+                    attempt += 1;
+                }
+                _ if attempt > 0 => {
+                    return Err(credentials::redact(&format!(
+                        "{} (gave up after {} attempts)",
+                        failure.message,
+                        attempt + 1
+                    )));
+                }
+                _ => return Err(credentials::redact(&failure.message)),
+            },
+        }
+    };
+
+    if let Some(checksum) = checksum
+        && let Err(error) = checksum.verify(Path::new(&temp_path))
+    {
+        let _ = fs::remove_file(&temp_path);
+        return Err(error);
+    }
+
+    fs::rename(&temp_path, &filepath).map_err(|e| {
+        format!("Could not move downloaded file {temp_path} into place at {filepath}: {e}")
+    })?;
+
+    Ok(size as f32 / 1000000.0)
+}
+
+struct AttemptFailure {
+    message: String,
+    /// `None` when the attempt cannot succeed on retry, `Some(None)` to retry after the usual
+    /// backoff, and `Some(Some(delay))` when the server asked for a specific delay.
+    retry_after: Option<Option<Duration>>,
+}
+
+impl AttemptFailure {
+    fn fatal(message: String) -> Self {
+        Self {
+            message,
+            retry_after: None,
+        }
+    }
+
+    fn retry(message: String, delay: Option<Duration>) -> Self {
+        Self {
+            message,
+            retry_after: Some(delay),
+        }
+    }
+}
+
+fn download_attempt(
+    name: &str,
+    url: &str,
+    temp_path: &str,
+    progress: Option<&ProgressReporter<'_>>,
+) -> Result<u64, AttemptFailure> {
+    if let Some(source) = local_file(url) {
+        return copy_local_file(name, source, temp_path, progress);
+    }
+
+    let existing = fs::metadata(temp_path).map(|m| m.len()).unwrap_or(0);
+
+    let mut request = request(url).map_err(AttemptFailure::fatal)?;
+    if existing > 0 {
+        request = request.header(RANGE, format!("bytes={existing}-"));
+    }
+
+    let mut response = request.send().map_err(|e| {
+        let reason = if e.is_timeout() {
+            "timed out"
+        } else {
+            "connection failed"
+        };
+        AttemptFailure::retry(format!("{url}, {reason}: {e}"), None)
+    })?;
+
+    let status = response.status();
+    let resumed = match status {
+        StatusCode::PARTIAL_CONTENT if existing > 0 => true,
+        StatusCode::RANGE_NOT_SATISFIABLE if existing > 0 => {
+            // The partial file no longer lines up with the remote file, so start over.
+            let _ = fs::remove_file(temp_path);
+            return Err(AttemptFailure::retry(
+                format!("{url}, status: {}", status.as_str()),
+                Some(Duration::ZERO),
+            ));
+        }
+        status if status.is_success() => false,
+        status if is_retryable(status) => {
+            return Err(AttemptFailure::retry(
+                format!("{url}, status: {}", status.as_str()),
+                retry_after(&response),
             ));
         }
+        status => {
+            return Err(AttemptFailure::fatal(format!(
+                "{url}, status: {}",
+                status.as_str()
+            )));
+        }
     };
 
-    if !response.status().is_success() {
-        return Err(format!("{url}, status: {}", response.status().as_str()));
+    let mut file = if resumed {
+        OpenOptions::new().append(true).open(temp_path)
+    } else {
+        File::create(temp_path)
+    }
+    .map_err(|e| {
+        AttemptFailure::fatal(format!(
+            "Could not create file {temp_path} for dependency {name}: {e}"
+        ))
+    })?;
+
+    let offset = if resumed { existing } else { 0 };
+    let total = content_length(&response).map(|length| length + offset);
+    copy_with_progress(&mut response, &mut file, offset, total, progress).map_err(|e| {
+        AttemptFailure::retry(
+            format!("Could not copy from URL {url} into file {temp_path}: {e}"),
+            None,
+        )
+    })
+}
+
+fn copy_local_file(
+    name: &str,
+    source: &Path,
+    temp_path: &str,
+    progress: Option<&ProgressReporter<'_>>,
+) -> Result<u64, AttemptFailure> {
+    let mut reader = File::open(source)
+        .map_err(|e| AttemptFailure::fatal(format!("Could not read {}: {e}", source.display())))?;
+    let total = reader.metadata().ok().map(|metadata| metadata.len());
+    let mut file = File::create(temp_path).map_err(|e| {
+        AttemptFailure::fatal(format!(
+            "Could not create file {temp_path} for dependency {name}: {e}"
+        ))
+    })?;
+
+    copy_with_progress(&mut reader, &mut file, 0, total, progress).map_err(|e| {
+        AttemptFailure::fatal(format!(
+            "Could not copy {} into file {temp_path}: {e}",
+            source.display()
+        ))
+    })
+}
+
+fn copy_with_progress(
+    response: &mut impl Read,
+    file: &mut File,
+    offset: u64,
+    total: Option<u64>,
+    progress: Option<&ProgressReporter<'_>>,
+) -> std::io::Result<u64> {
+    let mut buffer = [0u8; 64 * 1024];
+    let mut downloaded = offset;
+    let mut last_report: Option<Instant> = None;
+
+    loop {
+        let read = response.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+
+        file.write_all(&buffer[..read])?;
+        downloaded += read as u64;
+
+        if let Some(progress) = progress
+            && last_report.is_none_or(|last| last.elapsed() >= PROGRESS_INTERVAL)
+        {
+            progress(downloaded, total);
+            last_report = Some(Instant::now());
+        }
+    }
+
+    file.flush()?;
+    if let Some(progress) = progress {
+        progress(downloaded, total);
     }
 
-    let mut file = File::create(&filepath)
-        .map_err(|e| format!("Could not create file {filepath} for dependency {name}: {e}"))?;
+    Ok(downloaded)
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status.is_server_error()
+}
+
+fn content_length(response: &Response) -> Option<u64> {
+    response
+        .headers()
+        .get(CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+}
+
+/// Only the delay-seconds form of `Retry-After` is honoured; dates fall back to the usual backoff.
+fn retry_after(response: &Response) -> Option<Duration> {
+    parse_retry_after(response.headers().get(RETRY_AFTER)?.to_str().ok()?)
+}
+
+fn parse_retry_after(value: &str) -> Option<Duration> {
+    value
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(|seconds| Duration::from_secs(seconds).min(MAX_RETRY_AFTER))
+}
+
+fn backoff(attempt: u32) -> Duration {
+    INITIAL_BACKOFF
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(MAX_BACKOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+    use std::{
+        io::{BufRead, BufReader},
+        net::TcpListener,
+        path::PathBuf,
+    };
+
+    const BODY: &[u8] = b"0123456789";
+
+    /// Reads one request and returns the value of its `Range` header, if any.
+    fn read_range(reader: &mut BufReader<std::net::TcpStream>) -> Option<String> {
+        let mut range = None;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line.trim().is_empty() {
+                return range;
+            }
+            if let Some(value) = line.to_ascii_lowercase().strip_prefix("range: bytes=") {
+                range = Some(value.trim().to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn interrupted_download_resumes_with_range_request() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/library.jar", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            assert_eq!(read_range(&mut reader), None);
+            let mut stream = reader.into_inner();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\n0123")
+                .unwrap();
+            drop(stream);
+
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            assert_eq!(read_range(&mut reader).as_deref(), Some("4-"));
+            let mut stream = reader.into_inner();
+            stream
+                .write_all(b"HTTP/1.1 206 Partial Content\r\nContent-Length: 6\r\n\r\n456789")
+                .unwrap();
+        });
+
+        let temp = TempDir::new("download-resume");
+        let filepath = temp.path().join("library.jar");
+        let filepath = filepath.to_string_lossy().to_string();
+
+        download_silent(String::from("library"), url, filepath.clone(), None, None).unwrap();
+        server.join().unwrap();
+
+        assert_eq!(fs::read(&filepath).unwrap(), BODY);
+        assert!(!PathBuf::from(format!("{filepath}.tmp")).exists());
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_cap() {
+        assert_eq!(backoff(0), Duration::from_millis(500));
+        assert_eq!(backoff(1), Duration::from_secs(1));
+        assert_eq!(backoff(2), Duration::from_secs(2));
+        assert_eq!(backoff(20), MAX_BACKOFF);
+    }
 
-    match copy(&mut response, &mut file) {
-        Ok(v) => Ok(v as f32 / 1000000.0),
-        Err(e) => Err(format!(
-            "Could not copy from URL {url} into file {filepath}: {e}"
-        )),
+    #[test]
+    fn parse_retry_after_reads_seconds_and_ignores_dates() {
+        assert_eq!(parse_retry_after("7"), Some(Duration::from_secs(7)));
+        assert_eq!(parse_retry_after("86400"), Some(MAX_RETRY_AFTER));
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"), None);
     }
 }
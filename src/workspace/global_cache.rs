@@ -0,0 +1,278 @@
+use std::{
+    fs::{self, File},
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use crate::util::consts;
+use crate::workspace::{files, paths};
+
+static ROOT: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+/// Where downloaded artifacts are kept.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CacheMode {
+    /// Artifacts are shared between projects through the user-level cache, keyed by SHA-256.
+    #[default]
+    Global,
+    /// Artifacts only live in the project's own `.wisteria/cache`.
+    Project,
+}
+
+impl CacheMode {
+    pub fn load(value: &str) -> Result<Self, String> {
+        match value {
+            "global" => Ok(Self::Global),
+            "project" => Ok(Self::Project),
+            _ => Err(format!(
+                "Unknown cache mode \"{value}\". Expected one of [global, project]."
+            )),
+        }
+    }
+}
+
+/// Sets the cache mode for this process. Only the first call has any effect, and the global cache
+/// stays disabled until it is called.
+pub fn configure(mode: CacheMode) {
+    let _ = ROOT.set(match mode {
+        CacheMode::Global => paths::user_cache_dir(),
+        CacheMode::Project => None,
+    });
+}
+
+fn root() -> Option<&'static Path> {
+    ROOT.get()?.as_deref()
+}
+
+/// Links the globally cached artifact with `hash` to `target`. Returns whether it was cached.
+pub fn restore(hash: &str, target: &Path) -> Result<bool, String> {
+    match root() {
+        Some(root) => restore_from(root, hash, target),
+        None => Ok(false),
+    }
+}
+
+/// Adds the artifact at `source`, whose SHA-256 is `hash`, to the global cache and links it back
+/// into the project cache so both share one copy on disk.
+pub fn store(source: &Path, hash: &str) -> Result<(), String> {
+    match root() {
+        Some(root) => store_in(root, source, hash),
+        None => Ok(()),
+    }
+}
+
+fn entry_path(root: &Path, hash: &str) -> Result<PathBuf, String> {
+    if hash.len() < 2 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "Cannot use \"{hash}\" as a global cache key: expected a hex SHA-256 hash."
+        ));
+    }
+
+    Ok(root.join("sha256").join(&hash[..2]).join(hash))
+}
+
+fn restore_from(root: &Path, hash: &str, target: &Path) -> Result<bool, String> {
+    let entry = entry_path(root, hash)?;
+    if !entry.exists() {
+        return Ok(false);
+    }
+
+    let _lock = lock_entry(&entry, false)?;
+    if files::generate_sha2_for_file(&entry)? != hash {
+        // A damaged entry would otherwise be linked into every project that locks this hash.
+        let _ = fs::remove_file(&entry);
+        return Ok(false);
+    }
+
+    link(&entry, target)?;
+    Ok(true)
+}
+
+fn store_in(root: &Path, source: &Path, hash: &str) -> Result<(), String> {
+    let entry = entry_path(root, hash)?;
+    if same_file(&entry, source) {
+        return Ok(());
+    }
+
+    if !entry.exists() {
+        let _lock = lock_entry(&entry, true)?;
+        if !entry.exists() {
+            let temp = entry.with_extension(consts::TEMP_FILE_EXTENSION);
+            fs::copy(source, &temp).map_err(|e| {
+                format!(
+                    "Could not copy {} into the global cache at {}: {e}",
+                    source.display(),
+                    temp.display()
+                )
+            })?;
+            let copied = files::generate_sha2_for_file(&temp)?;
+            if copied != hash {
+                let _ = fs::remove_file(&temp);
+                return Err(format!(
+                    "Could not add {} to the global cache: its SHA-256 is {copied}, but {hash} was expected.\nFix: run `wisteria fetch` to download the artifact again.",
+                    source.display()
+                ));
+            }
+            fs::rename(&temp, &entry).map_err(|e| {
+                let _ = fs::remove_file(&temp);
+                format!(
+                    "Could not move {} into place in the global cache: {e}",
+                    entry.display()
+                )
+            })?;
+        }
+    }
+
+    let _lock = lock_entry(&entry, false)?;
+    link(&entry, source)
+}
+
+/// Replaces `target` with a hard link to `entry`, or a copy when linking is not possible, such as
+/// across file systems. `fs::copy` clones the file on file systems which support reflinks.
+fn link(entry: &Path, target: &Path) -> Result<(), String> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            format!(
+                "Could not create parent directories for {}: {e}",
+                target.display()
+            )
+        })?;
+    }
+
+    let mut temp = target.as_os_str().to_owned();
+    temp.push(format!(".{}", consts::TEMP_FILE_EXTENSION));
+    let temp = PathBuf::from(temp);
+
+    let _ = fs::remove_file(&temp);
+    if fs::hard_link(entry, &temp).is_err() {
+        fs::copy(entry, &temp).map_err(|e| {
+            format!(
+                "Could not copy {} from the global cache to {}: {e}",
+                entry.display(),
+                temp.display()
+            )
+        })?;
+    }
+
+    fs::rename(&temp, target).map_err(|e| {
+        let _ = fs::remove_file(&temp);
+        format!(
+            "Could not move {} into place from the global cache: {e}",
+            target.display()
+        )
+    })
+}
+
+#[cfg(unix)]
+fn same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+/// Without inode numbers, files with the same length and contents are treated as one, so an
+/// unchanged project copy is not relinked on every build.
+#[cfg(not(unix))]
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(a_metadata), Ok(b_metadata)) if a_metadata.len() == b_metadata.len() => {
+            match (
+                files::generate_sha2_for_file(a),
+                files::generate_sha2_for_file(b),
+            ) {
+                (Ok(a_hash), Ok(b_hash)) => a_hash == b_hash,
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+/// Holds a lock on `{entry}.lock` until dropped, so concurrent builds never see a partial entry.
+fn lock_entry(entry: &Path, exclusive: bool) -> Result<File, String> {
+    if let Some(parent) = entry.parent() {
+        fs::create_dir_all(parent).map_err(|e| {
+            format!(
+                "Could not create global cache directory {}: {e}",
+                parent.display()
+            )
+        })?;
+    }
+
+    let path = entry.with_extension("lock");
+    let file = File::create(&path)
+        .map_err(|e| format!("Could not open global cache lock {}: {e}", path.display()))?;
+    let locked = if exclusive {
+        file.lock()
+    } else {
+        file.lock_shared()
+    };
+    locked.map_err(|e| format!("Could not lock global cache entry {}: {e}", path.display()))?;
+
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    #[test]
+    fn stores_artifacts_by_hash_and_restores_them_into_other_projects() {
+        let temp = TempDir::new("global-cache-store");
+        let root = temp.path().join("global");
+        let first = temp
+            .path()
+            .join("first/.wisteria/cache/library/library.jar");
+        let second = temp
+            .path()
+            .join("second/.wisteria/cache/library/library.jar");
+        fs::create_dir_all(first.parent().unwrap()).unwrap();
+        fs::write(&first, "library").unwrap();
+        let hash = files::generate_sha2_for_file(&first).unwrap();
+
+        store_in(&root, &first, &hash).unwrap();
+
+        assert!(entry_path(&root, &hash).unwrap().exists());
+        assert!(restore_from(&root, &hash, &second).unwrap());
+        assert_eq!(fs::read_to_string(&second).unwrap(), "library");
+        assert!(!restore_from(&root, &"0".repeat(64), &second).unwrap());
+    }
+
+    #[test]
+    fn restore_drops_damaged_entries() {
+        let temp = TempDir::new("global-cache-damaged");
+        let root = temp.path().join("global");
+        let hash = "ab".repeat(32);
+        let entry = entry_path(&root, &hash).unwrap();
+        fs::create_dir_all(entry.parent().unwrap()).unwrap();
+        fs::write(&entry, "not the hashed contents").unwrap();
+
+        assert!(!restore_from(&root, &hash, &temp.path().join("library.jar")).unwrap());
+        assert!(!entry.exists());
+    }
+
+    #[test]
+    fn store_rejects_files_which_do_not_match_their_hash() {
+        let temp = TempDir::new("global-cache-mismatch");
+        let root = temp.path().join("global");
+        let source = temp.path().join("library.jar");
+        fs::write(&source, "tampered").unwrap();
+        let hash = "ab".repeat(32);
+
+        assert!(
+            store_in(&root, &source, &hash)
+                .unwrap_err()
+                .contains("was expected")
+        );
+        assert!(!entry_path(&root, &hash).unwrap().exists());
+    }
+
+    #[test]
+    fn rejects_keys_that_are_not_hashes() {
+        assert!(entry_path(Path::new("/cache"), "../escape").is_err());
+    }
+}
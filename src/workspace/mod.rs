@@ -1,5 +1,10 @@
+pub mod checksum;
+pub mod credentials;
 pub mod download;
 pub mod files;
+pub mod global_cache;
 pub mod nature;
 pub mod paths;
 pub mod refresh;
+pub mod settings;
+pub mod signature;
@@ -7,6 +7,8 @@ use std::{
 
 use regex::Regex;
 
+use crate::util::consts;
+
 pub fn resolve_filepath(
     path: &str,
     environment: &HashMap<String, String>,
@@ -73,6 +75,41 @@ pub fn ensure_parents(filepath: &str) -> Result<PathBuf, String> {
         .map_err(|e| format!("Could not create parent directories for file {filepath}: {e}"))
 }
 
+/// Path of `file` in the user-level Wisteria directory, or `None` without a home directory.
+pub fn user_file(file: &str) -> Option<PathBuf> {
+    user_config_dir().map(|dir| dir.join(file))
+}
+
+/// `$XDG_CONFIG_HOME/wisteria`, falling back to `~/.config/wisteria`, or `%APPDATA%\wisteria` on
+/// Windows.
+pub fn user_config_dir() -> Option<PathBuf> {
+    let base = match env::consts::OS {
+        "windows" => var_os("APPDATA").map(PathBuf::from),
+        _ => var_os("XDG_CONFIG_HOME")
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                resolve_os_var("HOME", "HOMEPATH").map(|home| PathBuf::from(home).join(".config"))
+            }),
+    }?;
+    Some(base.join(consts::USER_CONFIG_DIR))
+}
+
+/// `$XDG_CACHE_HOME/wisteria`, falling back to `~/.cache/wisteria`, or `%LOCALAPPDATA%\wisteria`
+/// on Windows.
+pub fn user_cache_dir() -> Option<PathBuf> {
+    let base = match env::consts::OS {
+        "windows" => var_os("LOCALAPPDATA").map(PathBuf::from),
+        _ => var_os("XDG_CACHE_HOME")
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                resolve_os_var("HOME", "HOMEPATH").map(|home| PathBuf::from(home).join(".cache"))
+            }),
+    }?;
+    Some(base.join(consts::GLOBAL_CACHE_DIR))
+}
+
 fn resolve_os_var(unix: &str, windows: &str) -> Option<String> {
     match env::consts::OS {
         "macos" | "linux" => var_os(unix).map(|s| s.to_string_lossy().to_string()),
@@ -0,0 +1,275 @@
+use std::{fs, io::ErrorKind, path::Path, path::PathBuf, sync::OnceLock};
+
+use reqwest::{Certificate, NoProxy, Proxy, blocking::ClientBuilder};
+use toml::Table;
+
+use crate::config::toml_utils;
+use crate::util::consts;
+use crate::workspace::paths;
+
+static SETTINGS: OnceLock<UserSettings> = OnceLock::new();
+
+/// Machine-wide network settings read from the user-level settings file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UserSettings {
+    offline: bool,
+    mirrors: Vec<Mirror>,
+    proxy: Option<ProxySettings>,
+    ca_certificates: Vec<PathBuf>,
+}
+
+/// Requests for URLs under `url` are sent to the same path under `mirror` instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Mirror {
+    url: String,
+    mirror: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ProxySettings {
+    url: String,
+    no_proxy: Vec<String>,
+}
+
+impl UserSettings {
+    /// Reads `settings.toml` from the user configuration directory. A missing file gives the default settings.
+    pub fn load() -> Result<Self, String> {
+        match paths::user_file(consts::SETTINGS_FILE) {
+            Some(path) => Self::load_file(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn load_file(path: &Path) -> Result<Self, String> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => {
+                return Err(format!(
+                    "Could not read settings file {}: {e}",
+                    path.display()
+                ));
+            }
+        };
+
+        let table: Table = contents.parse().map_err(|e| {
+            format!(
+                "Could not parse settings file {}: {e}\nFix: correct the TOML syntax, or move the file aside to use the default settings.",
+                path.display()
+            )
+        })?;
+
+        Self::load_table(&table)
+            .map_err(|error| format!("Invalid settings file {}: {error}", path.display()))
+    }
+
+    fn load_table(table: &Table) -> Result<Self, String> {
+        let mirrors = match toml_utils::read_optional_table("mirrors", table)? {
+            Some(mirrors) => mirrors
+                .keys()
+                .map(|url| {
+                    Ok(Mirror {
+                        url: url.clone(),
+                        mirror: toml_utils::read_string(url, mirrors)?,
+                    })
+                })
+                .collect::<Result<Vec<Mirror>, String>>()?,
+            None => Vec::new(),
+        };
+
+        let proxy = match toml_utils::read_optional_table("proxy", table)? {
+            Some(proxy) => Some(ProxySettings {
+                url: toml_utils::read_string("url", proxy)?,
+                no_proxy: toml_utils::read_optional_string_array("no_proxy", proxy)?
+                    .unwrap_or_default(),
+            }),
+            None => None,
+        };
+
+        let ca_certificates = match toml_utils::read_optional_table("tls", table)? {
+            Some(tls) => toml_utils::read_optional_string_array("ca_certificates", tls)?
+                .unwrap_or_default()
+                .into_iter()
+                .map(PathBuf::from)
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Ok(Self {
+            offline: toml_utils::read_optional_boolean("offline", table)?.unwrap_or_default(),
+            mirrors,
+            proxy,
+            ca_certificates,
+        })
+    }
+
+    pub fn offline(&self) -> bool {
+        self.offline
+    }
+
+    /// The URL a request for `url` is actually sent to, after the most specific mirror rewrite.
+    pub fn mirror(&self, url: &str) -> String {
+        let mirror = self
+            .mirrors
+            .iter()
+            .filter_map(|mirror| Some((mirror, url_prefix(url, &mirror.url)?)))
+            .max_by_key(|(_, prefix)| prefix.len());
+
+        match mirror {
+            Some((mirror, prefix)) => format!(
+                "{}{}",
+                mirror.mirror.trim_end_matches('/'),
+                &url[prefix.len()..]
+            ),
+            None => url.to_string(),
+        }
+    }
+
+    /// Applies the proxy and extra trusted certificate authorities to an HTTP client.
+    pub fn apply(&self, mut builder: ClientBuilder) -> Result<ClientBuilder, String> {
+        if let Some(proxy) = &self.proxy {
+            let configured = Proxy::all(&proxy.url)
+                .map_err(|e| format!("Invalid proxy URL {}: {e}", proxy.url))?
+                .no_proxy(NoProxy::from_string(&proxy.no_proxy.join(",")));
+            builder = builder.proxy(configured);
+        }
+
+        for path in &self.ca_certificates {
+            let pem = fs::read(path).map_err(|e| {
+                format!(
+                    "Could not read CA bundle {}: {e}\nFix: point [tls].ca_certificates in the settings file at readable PEM files.",
+                    path.display()
+                )
+            })?;
+            let certificates = Certificate::from_pem_bundle(&pem)
+                .map_err(|e| format!("Could not load CA bundle {}: {e}", path.display()))?;
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        Ok(builder)
+    }
+}
+
+/// Sets the user settings for this process. Only the first call has any effect.
+pub fn configure(settings: UserSettings) {
+    let _ = SETTINGS.set(settings);
+}
+
+pub fn settings() -> &'static UserSettings {
+    SETTINGS.get_or_init(UserSettings::default)
+}
+
+/// `prefix` without its trailing slash, when `url` is that URL or lies below it. A prefix only
+/// matches whole path segments, so `https://host/repo` covers neither `https://host.evil/` nor
+/// `https://host/repo-other/`.
+pub fn url_prefix<'a>(url: &str, prefix: &'a str) -> Option<&'a str> {
+    let prefix = prefix.trim_end_matches('/');
+    let rest = url.strip_prefix(prefix)?;
+    (rest.is_empty() || rest.starts_with('/')).then_some(prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::TempDir;
+
+    fn settings(toml: &str) -> Result<UserSettings, String> {
+        UserSettings::load_table(&toml.parse::<Table>().unwrap())
+    }
+
+    #[test]
+    fn loads_mirrors_proxy_and_certificates() {
+        let settings = settings(
+            r#"
+            offline = true
+
+            [mirrors]
+            "https://repo1.maven.org/maven2/" = "https://artifactory.example.com/maven-central/"
+
+            [proxy]
+            url = "http://proxy.example.com:3128"
+            no_proxy = ["localhost", ".example.com"]
+
+            [tls]
+            ca_certificates = ["/etc/ssl/corporate.pem"]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            settings.proxy,
+            Some(ProxySettings {
+                url: String::from("http://proxy.example.com:3128"),
+                no_proxy: vec![String::from("localhost"), String::from(".example.com")],
+            })
+        );
+        assert_eq!(
+            settings.ca_certificates,
+            vec![PathBuf::from("/etc/ssl/corporate.pem")]
+        );
+        assert_eq!(settings.mirrors.len(), 1);
+        assert!(settings.offline());
+    }
+
+    #[test]
+    fn mirror_rewrites_the_most_specific_prefix() {
+        let settings = settings(
+            r#"
+            [mirrors]
+            "https://repo1.maven.org/maven2" = "https://artifactory.example.com/central/"
+            "https://repo1.maven.org/maven2/com/example/" = "https://artifactory.example.com/example"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            settings.mirror("https://repo1.maven.org/maven2/org/demo/demo-1.0.jar"),
+            "https://artifactory.example.com/central/org/demo/demo-1.0.jar"
+        );
+        assert_eq!(
+            settings.mirror("https://repo1.maven.org/maven2/com/example/lib/maven-metadata.xml"),
+            "https://artifactory.example.com/example/lib/maven-metadata.xml"
+        );
+        assert_eq!(
+            settings.mirror("https://api.github.com/repos/a/b/releases"),
+            "https://api.github.com/repos/a/b/releases"
+        );
+    }
+
+    #[test]
+    fn mirror_only_matches_whole_path_segments() {
+        let settings = settings(
+            r#"
+            [mirrors]
+            "https://repo1.maven.org" = "https://artifactory.example.com/central"
+            "https://nexus.example.com/repository/private/" = "https://artifactory.example.com/private"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            settings.mirror("https://repo1.maven.org.attacker.net/a.jar"),
+            "https://repo1.maven.org.attacker.net/a.jar"
+        );
+        assert_eq!(
+            settings.mirror("https://nexus.example.com/repository/private-other/a.jar"),
+            "https://nexus.example.com/repository/private-other/a.jar"
+        );
+        assert_eq!(
+            settings.mirror("https://repo1.maven.org/maven2/a.jar"),
+            "https://artifactory.example.com/central/maven2/a.jar"
+        );
+    }
+
+    #[test]
+    fn missing_settings_file_uses_defaults() {
+        let temp = TempDir::new("settings-missing");
+
+        assert_eq!(
+            UserSettings::load_file(&temp.path().join("settings.toml")).unwrap(),
+            UserSettings::default()
+        );
+    }
+}
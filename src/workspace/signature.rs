@@ -0,0 +1,273 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::util::consts;
+use crate::workspace::download;
+
+static NEXT_KEYRING_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Path of the detached signature stored beside a cached artifact.
+pub fn signature_path(artifact_path: &str) -> String {
+    format!("{artifact_path}.{}", consts::SIGNATURE_EXTENSION)
+}
+
+/// Downloads the `.asc` signature published beside `artifact_url`, unless it is already cached, and
+/// verifies the artifact against it. Returns the fingerprint of the signing key.
+pub fn verify_published_signature(
+    name: &str,
+    artifact_url: &str,
+    artifact_path: &str,
+) -> Result<String, String> {
+    let signature = signature_path(artifact_path);
+    if !Path::new(&signature).exists() {
+        let signature_url = format!("{artifact_url}.{}", consts::SIGNATURE_EXTENSION);
+        download::download_silent(
+            name.to_string(),
+            signature_url.clone(),
+            signature.clone(),
+            None,
+            None,
+        )
+        .map_err(|e| {
+            format!(
+                "Could not download the signature for \"{name}\" from {signature_url}: {e}\nFix: check that the repository publishes `.asc` signatures, or remove `verify_signature` from the dependency."
+            )
+        })?;
+    }
+
+    verify_signature(Path::new(artifact_path), Path::new(&signature))
+}
+
+/// Verifies the signature cached beside an artifact without touching the network.
+pub fn verify_cached_signature(artifact_path: &str) -> Result<String, String> {
+    let signature = signature_path(artifact_path);
+    if !Path::new(&signature).exists() {
+        return Err(format!(
+            "The signature `{signature}` is missing from the cache.\nFix: run `wisteria fetch` to download it again."
+        ));
+    }
+
+    verify_signature(Path::new(artifact_path), Path::new(&signature))
+}
+
+/// Checks a detached signature against the keys in the trusted keys directory, and returns the
+/// fingerprint of the primary key that made it.
+pub fn verify_signature(file: &Path, signature: &Path) -> Result<String, String> {
+    let keys = trusted_key_files()?;
+    let keyring = Keyring::create()?;
+
+    let import = keyring
+        .gpg()
+        .arg("--import")
+        .args(&keys)
+        .output()
+        .map_err(gpg_error)?;
+    if !import.status.success() {
+        return Err(format!(
+            "Could not import the trusted keys in `{}`: {}\nFix: make sure every file there is an exported OpenPGP public key.",
+            consts::TRUSTED_KEYS_PATH,
+            String::from_utf8_lossy(&import.stderr).trim()
+        ));
+    }
+
+    let verify = keyring
+        .gpg()
+        .args(["--status-fd", "1", "--verify"])
+        .arg(signature)
+        .arg(file)
+        .output()
+        .map_err(gpg_error)?;
+
+    parse_verify_status(&String::from_utf8_lossy(&verify.stdout)).map_err(|reason| {
+        format!(
+            "Signature check failed for {}: {reason}\nFix: make sure the artifact comes from its publisher, and that their public key is in `{}`.",
+            file.display(),
+            consts::TRUSTED_KEYS_PATH
+        )
+    })
+}
+
+fn trusted_key_files() -> Result<Vec<PathBuf>, String> {
+    let keys: Vec<PathBuf> = fs::read_dir(consts::TRUSTED_KEYS_PATH)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.is_file())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if keys.is_empty() {
+        return Err(format!(
+            "No trusted keys were found in `{}`.\nFix: export the publisher's public key with `gpg --export --armor <fingerprint>` into that directory.",
+            consts::TRUSTED_KEYS_PATH
+        ));
+    }
+
+    Ok(keys)
+}
+
+/// Reads gpg's machine-readable status output. Only a valid signature from a key that has not been
+/// revoked or expired is accepted.
+fn parse_verify_status(status: &str) -> Result<String, String> {
+    let mut valid: Option<String> = None;
+    let mut failure: Option<String> = None;
+
+    for line in status.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            ["[GNUPG:]", "VALIDSIG", signing_key, rest @ ..] => {
+                // The primary key fingerprint is the last field when gpg reports it.
+                let primary = match rest.len() {
+                    9.. => rest[8],
+                    _ => signing_key,
+                };
+                valid = Some(primary.to_ascii_uppercase());
+            }
+            ["[GNUPG:]", "REVKEYSIG", key, ..] => {
+                failure = Some(format!("the signing key {key} has been revoked"));
+            }
+            ["[GNUPG:]", "BADSIG", key, ..] => {
+                failure = Some(format!(
+                    "the file does not match its signature from key {key}"
+                ));
+            }
+            ["[GNUPG:]", "EXPKEYSIG", key, ..] => {
+                failure = Some(format!("the signing key {key} has expired"));
+            }
+            ["[GNUPG:]", "EXPSIG", key, ..] => {
+                failure = Some(format!("the signature from key {key} has expired"));
+            }
+            ["[GNUPG:]", "ERRSIG", key, ..] => {
+                // gpg follows this with a more specific line, such as NO_PUBKEY, when it has one.
+                failure.get_or_insert_with(|| {
+                    format!("the signature from key {key} could not be checked")
+                });
+            }
+            ["[GNUPG:]", "NO_PUBKEY", key] => {
+                failure = Some(format!(
+                    "it was signed by key {key}, which is not in `{}`",
+                    consts::TRUSTED_KEYS_PATH
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    match (failure, valid) {
+        (Some(failure), _) => Err(failure),
+        (None, Some(fingerprint)) => Ok(fingerprint),
+        (None, None) => Err(String::from("gpg did not report a valid signature")),
+    }
+}
+
+fn gpg_error(error: std::io::Error) -> String {
+    format!(
+        "Failed to run gpg: {error}\nFix: install GnuPG and ensure `gpg` is on your PATH to verify signatures."
+    )
+}
+
+/// A throwaway gpg home, so verification only trusts the project's keys and never touches the
+/// user's own keyring.
+struct Keyring {
+    home: PathBuf,
+}
+
+impl Keyring {
+    fn create() -> Result<Self, String> {
+        let home = std::env::temp_dir().join(format!(
+            "wisteria-keyring-{}-{}",
+            std::process::id(),
+            NEXT_KEYRING_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&home);
+        fs::create_dir_all(&home)
+            .map_err(|e| format!("Could not create keyring at {}: {e}", home.display()))?;
+
+        Ok(Self { home })
+    }
+
+    fn gpg(&self) -> Command {
+        let mut command = Command::new("gpg");
+        command.arg("--homedir").arg(&self.home).args([
+            "--batch",
+            "--no-tty",
+            "--quiet",
+            "--no-permission-warning",
+        ]);
+        command
+    }
+}
+
+impl Drop for Keyring {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.home);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_verify_status_reports_primary_fingerprint() {
+        let status = "[GNUPG:] NEWSIG\n\
+            [GNUPG:] GOODSIG 1111222233334444 Tester <tester@example.com>\n\
+            [GNUPG:] VALIDSIG aaaa 2024-01-01 1704067200 0 4 0 22 10 00 bbbb\n";
+
+        assert_eq!(parse_verify_status(status), Ok(String::from("BBBB")));
+    }
+
+    #[test]
+    fn parse_verify_status_rejects_bad_and_unknown_signatures() {
+        assert!(
+            parse_verify_status("[GNUPG:] BADSIG 1111222233334444 Tester\n")
+                .unwrap_err()
+                .contains("does not match")
+        );
+        assert!(
+            parse_verify_status("[GNUPG:] NO_PUBKEY 1111222233334444\n")
+                .unwrap_err()
+                .contains("not in `.wisteria/trusted-keys`")
+        );
+        assert!(parse_verify_status("").is_err());
+    }
+
+    #[test]
+    fn parse_verify_status_rejects_expired_and_unchecked_signatures_despite_validsig() {
+        let validsig = "[GNUPG:] VALIDSIG aaaa 2024-01-01 1704067200 0 4 0 22 10 00 bbbb\n";
+
+        assert!(
+            parse_verify_status(&format!(
+                "[GNUPG:] EXPKEYSIG 1111222233334444 Tester\n{validsig}"
+            ))
+            .unwrap_err()
+            .contains("signing key 1111222233334444 has expired")
+        );
+        assert!(
+            parse_verify_status(&format!(
+                "[GNUPG:] EXPSIG 1111222233334444 Tester\n{validsig}"
+            ))
+            .unwrap_err()
+            .contains("signature from key 1111222233334444 has expired")
+        );
+        assert!(
+            parse_verify_status(&format!(
+                "[GNUPG:] ERRSIG 1111222233334444 1 8 00 1704067200 9 -\n{validsig}"
+            ))
+            .unwrap_err()
+            .contains("could not be checked")
+        );
+        assert!(
+            parse_verify_status(
+                "[GNUPG:] ERRSIG 1111222233334444 1 8 00 1704067200 9 -\n[GNUPG:] NO_PUBKEY 1111222233334444\n"
+            )
+            .unwrap_err()
+            .contains("not in `.wisteria/trusted-keys`")
+        );
+    }
+}